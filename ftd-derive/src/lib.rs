@@ -0,0 +1,247 @@
+extern crate proc_macro;
+
+/// Implements `ftd::FtdRecord`, generating the `-- record` declaration of a
+/// struct, or the `-- or-type` declaration of an enum with struct and unit
+/// variants.
+///
+/// Names are converted to kebab case, fields keep their rust names so the
+/// values read by `Document::instances()` deserialize as is. Enums need
+/// `#[serde(tag = "type", rename_all = "kebab-case")]` for the same.
+///
+/// - `#[ftd(name = "...")]` on the type, or `#[ftd(rename = "...")]` on a
+///   field or variant, overrides the name used in ftd
+/// - `#[ftd(caption)]` / `#[ftd(body)]` on a `String` (or `Option<String>`)
+///   field makes it the caption / body of the record
+#[proc_macro_derive(FtdRecord, attributes(ftd))]
+pub fn derive_ftd_record(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match expand(&input) {
+        Ok(t) => t.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let name = ftd_name(Attrs::from(&input.attrs)?.rename, ident);
+
+    let body = match &input.data {
+        syn::Data::Struct(s) => {
+            let fields = fields(&s.fields)?;
+            quote::quote! {
+                let mut lines = vec![format!("-- record {}:", name)];
+                #(#fields)*
+                declarations.finish(lines.join("\n"));
+            }
+        }
+        syn::Data::Enum(e) => {
+            let mut variants = vec![];
+            for variant in e.variants.iter() {
+                let variant_name = ftd_name(Attrs::from(&variant.attrs)?.rename, &variant.ident);
+                let fields = fields(&variant.fields)?;
+                variants.push(quote::quote! {
+                    let mut lines = vec![format!("--- {}:", #variant_name)];
+                    #(#fields)*
+                    variants.push(lines.join("\n"));
+                });
+            }
+            quote::quote! {
+                let mut variants: Vec<String> = vec![];
+                #(#variants)*
+                declarations.finish(format!("-- or-type {}:\n\n{}", name, variants.join("\n\n")));
+            }
+        }
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "FtdRecord can not be derived for unions",
+            ))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote::quote! {
+        impl #impl_generics ftd::FtdRecord for #ident #ty_generics #where_clause {
+            fn ftd_name() -> String {
+                #name
+            }
+
+            fn ftd_declarations(declarations: &mut ftd::Declarations) {
+                let name = Self::ftd_name();
+                if !declarations.start(name.as_str()) {
+                    return;
+                }
+                #body
+            }
+        }
+    })
+}
+
+// statements adding the declaration of each field to `lines`, after declaring
+// the types the field uses
+fn fields(fields: &syn::Fields) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let named = match fields {
+        syn::Fields::Named(named) => named,
+        syn::Fields::Unit => return Ok(vec![]),
+        syn::Fields::Unnamed(_) => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "FtdRecord only supports named fields",
+            ))
+        }
+    };
+
+    let mut statements = vec![];
+    for field in named.named.iter() {
+        let attrs = Attrs::from(&field.attrs)?;
+        let field_name = match attrs.rename {
+            Some(name) => name,
+            None => field.ident.as_ref().unwrap().to_string(),
+        };
+        let text = match (attrs.caption, attrs.body) {
+            (true, true) => {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "a field can not be both caption and body",
+                ))
+            }
+            (true, false) => Some("caption"),
+            (false, true) => Some("body"),
+            (false, false) => None,
+        };
+        let (kind, dependencies) = kind(&field.ty, text)?;
+        statements.push(quote::quote! {
+            #(#dependencies)*
+            lines.push(format!("{} {}:", #kind, #field_name));
+        });
+    }
+    Ok(statements)
+}
+
+// an expression evaluating to the ftd kind of `ty`, and the statements that
+// declare the records and or-types it refers to
+fn kind(
+    ty: &syn::Type,
+    text: Option<&str>,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>)> {
+    if let syn::Type::Reference(r) = ty {
+        return kind(&r.elem, text);
+    }
+    if let Some(inner) = generic_argument(ty, "Box") {
+        return kind(inner, text);
+    }
+    if let Some(inner) = generic_argument(ty, "Option") {
+        let (k, dependencies) = kind(inner, text)?;
+        return Ok((quote::quote! { format!("optional {}", #k) }, dependencies));
+    }
+    if let Some(inner) = generic_argument(ty, "Vec") {
+        if text.is_some() {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "caption and body can not be lists",
+            ));
+        }
+        let (k, dependencies) = kind(inner, None)?;
+        return Ok((quote::quote! { format!("{} list", #k) }, dependencies));
+    }
+
+    let scalar = match last_ident(ty).as_deref() {
+        Some("String") | Some("str") => Some(text.unwrap_or("string")),
+        Some("i8") | Some("i16") | Some("i32") | Some("i64") | Some("isize") | Some("u8")
+        | Some("u16") | Some("u32") | Some("u64") | Some("usize") => Some("integer"),
+        Some("f32") | Some("f64") => Some("decimal"),
+        Some("bool") => Some("boolean"),
+        _ => None,
+    };
+    match (scalar, text) {
+        (Some(k), Some(_)) if k != "caption" && k != "body" => Err(syn::Error::new_spanned(
+            ty,
+            "only string fields can be caption or body",
+        )),
+        (Some(k), _) => Ok((quote::quote! { #k }, vec![])),
+        (None, Some(_)) => Err(syn::Error::new_spanned(
+            ty,
+            "only string fields can be caption or body",
+        )),
+        (None, None) => Ok((
+            quote::quote! { <#ty as ftd::FtdRecord>::ftd_name() },
+            vec![quote::quote! { <#ty as ftd::FtdRecord>::ftd_declarations(declarations); }],
+        )),
+    }
+}
+
+fn last_ident(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+// `T` if `ty` is `wrapper<T>`
+fn generic_argument<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(p) => p.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(a) => match a.args.first()? {
+            syn::GenericArgument::Type(t) if a.args.len() == 1 => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Attrs {
+    rename: Option<String>,
+    caption: bool,
+    body: bool,
+}
+
+impl Attrs {
+    fn from(attrs: &[syn::Attribute]) -> syn::Result<Attrs> {
+        let mut a: Attrs = Default::default();
+        for attr in attrs.iter().filter(|a| a.path.is_ident("ftd")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                m => return Err(syn::Error::new_spanned(m, "expected #[ftd(...)]")),
+            };
+            for nested in list.nested.iter() {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::Path(p)) if p.is_ident("caption") => {
+                        a.caption = true
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(p)) if p.is_ident("body") => {
+                        a.body = true
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                        if nv.path.is_ident("name") || nv.path.is_ident("rename") =>
+                    {
+                        match &nv.lit {
+                            syn::Lit::Str(s) => a.rename = Some(s.value()),
+                            l => return Err(syn::Error::new_spanned(l, "expected a string")),
+                        }
+                    }
+                    n => return Err(syn::Error::new_spanned(n, "unknown ftd attribute")),
+                }
+            }
+        }
+        Ok(a)
+    }
+}
+
+// an expression evaluating to the name of `ident` in ftd, the conversion to
+// kebab case is left to `ftd::kebab_case` so it matches the serializer
+fn ftd_name(rename: Option<String>, ident: &syn::Ident) -> proc_macro2::TokenStream {
+    match rename {
+        Some(name) => quote::quote! { #name.to_string() },
+        None => {
+            let ident = ident.to_string();
+            quote::quote! { ftd::kebab_case(#ident) }
+        }
+    }
+}
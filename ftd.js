@@ -10,7 +10,7 @@ let ftd_utils = {
                 } else if (reference[key] !== undefined && reference[key] !== null) {
                     result[key] = (data[reference[key]] !== undefined && data[reference[key]].value !== undefined) ? data[reference[key]].value : value[key];
                 } else {
-                    result[key] = (value[key] === "$VALUE" && obj.value !== undefined) ? obj.value : value[key];
+                    result[key] = ftd_utils.event_value(value[key], obj);
                 }
             }
             for (var key of Object.keys(reference)) {
@@ -22,10 +22,44 @@ let ftd_utils = {
         } else if (reference !== null && reference !== undefined && data[reference] !== undefined && data[reference].value !== undefined) {
             return data[reference]["value"];
         } else {
-            return (value === "$VALUE" && obj.value !== undefined) ? obj.value : value;
+            return ftd_utils.event_value(value, obj);
         }
     },
 
+    // `$VALUE` and `$VALID` are the value and the validity of the element the
+    // event is on, the value of a radio group is the value of its checked radio
+    event_value: function (value, obj) {
+        if (value === "$VALUE" && obj.value !== undefined) {
+            return obj.value;
+        }
+        if (value === "$VALUE" && obj.getAttribute !== undefined && obj.getAttribute("role") === "radiogroup") {
+            let checked = obj.querySelector("input[type=radio]:checked");
+            return (checked !== null) ? checked.value : "";
+        }
+        if (value === "$VALID" && obj.validity !== undefined) {
+            return String(obj.validity.valid);
+        }
+        return value;
+    },
+
+    // the values of the variables in `fields`, by field name
+    form_payload: function (fields, data) {
+        let payload = {};
+        for (const field in fields) {
+            if (data[fields[field]] !== undefined) {
+                payload[field] = data[fields[field]].value;
+            }
+        }
+        return payload;
+    },
+
+    form_body: function (payload, encoding) {
+        if (encoding === "json") {
+            return JSON.stringify(payload);
+        }
+        return new URLSearchParams(payload).toString();
+    },
+
     is_visible: function (id, affected_id) {
         return (document.querySelector(`[data-id="${affected_id}:${id}"]`).style.display !== "none");
     },
@@ -368,8 +402,10 @@ let ftd_utils = {
         if (ftd_utils.isJson(new_value)) {
             new_value = JSON.parse(new_value);
         }
+        let kind = "light";
         if (!!new_value && !!new_value["$kind$"]) {
-            new_value = new_value[new_value["$kind$"]];
+            kind = new_value["$kind$"];
+            new_value = new_value[kind];
         }
         let target = ftd_utils.get_name_and_remaining(target_variable)[0];
 
@@ -396,7 +432,26 @@ let ftd_utils = {
                         data[target].dependencies = deps;
                     } else {
                         let doc = document.querySelector(`[data-id="${dependency}:${id}"]`);
-                        if (doc.src !== undefined) {
+                        if ("poster" in json_dependency.parameters) {
+                            doc.poster = new_value;
+                        } else if (doc.type === "checkbox") {
+                            doc.checked = (String(new_value) === "true");
+                        } else if (doc.tagName === "INPUT" || doc.tagName === "TEXTAREA" || doc.tagName === "SELECT") {
+                            // the input itself may have changed the value
+                            if (doc.value !== String(new_value)) {
+                                doc.value = new_value;
+                            }
+                        } else if (doc.getAttribute("role") === "radiogroup") {
+                            for (const radio of doc.querySelectorAll("input[type=radio]")) {
+                                radio.checked = (radio.value === String(new_value));
+                            }
+                        } else if (doc.tagName === "PICTURE") {
+                            // the sources for other screens follow the dark mode of `src`
+                            for (const source of doc.querySelectorAll("source")) {
+                                source.srcset = source.dataset[kind] || source.dataset.light;
+                            }
+                            doc.querySelector("img").src = new_value;
+                        } else if (doc.src !== undefined) {
                             doc.src = new_value;
                         } else {
                             doc.innerText = new_value;
@@ -728,6 +783,33 @@ window.ftd = (function () {
                 value = JSON.stringify(list);
             }
             ftd_utils.handle_action(id, target, value, data, ftd_external_children);
+        } else if (act === "submit-form") {
+            if (action["parameters"].fields === undefined) {
+                // an element in the form, the form is submitted if its inputs are valid
+                let form = obj.closest("form");
+                if (form !== null) {
+                    form.requestSubmit();
+                }
+                return;
+            }
+            evt.preventDefault();
+            submit_form(id, action["parameters"]);
+        } else if (act === "sort-table") {
+            sort_table(obj.closest("th"));
+        } else if (act === "select-panel") {
+            let container = obj.closest(".ft_tabs, .ft_accordion");
+            let index = parseInt(obj.dataset.panelIndex);
+            if (obj.getAttribute("aria-expanded") === "true") {
+                // a click on the open panel of an accordion closes it
+                index = -1;
+            }
+            select_panel(id, container, index, action["target"]);
+        } else if (act === "panel-key") {
+            panel_key(evt, id, obj, action["target"]);
+        } else if (act === "toggle-details") {
+            if (ftd_data[id][action["target"]] !== undefined) {
+                exports.set_bool(id, action["target"], obj.open);
+            }
         } else {
             console.log("unknown action:", act);
             return;
@@ -735,6 +817,128 @@ window.ftd = (function () {
 
     }
 
+    function submit_form(id, parameters) {
+        let fields = JSON.parse(parameters.fields[0].value);
+        let encoding = parameters.encoding[0].value;
+        let payload = ftd_utils.form_payload(fields, ftd_data[id]);
+        if (parameters["message-host"] !== undefined) {
+            // called like the `message-host` action, with the variables the
+            // payload is read from as the reference
+            let func = parameters["message-host"][0].value.trim();
+            let data = {
+                "function": func,
+                "encoding": encoding,
+                "body": ftd_utils.form_body(payload, encoding),
+            };
+            window[func.replaceAll("-", "_")](id, data, fields);
+            return;
+        }
+        // a form the browser submits, with the payload in hidden inputs
+        let form = document.createElement("form");
+        form.method = parameters.method[0].value;
+        form.action = parameters.action[0].value;
+        form.style.display = "none";
+        for (const field in payload) {
+            let input = document.createElement("input");
+            input.type = "hidden";
+            input.name = field;
+            input.value = payload[field];
+            form.appendChild(input);
+        }
+        document.body.appendChild(form);
+        form.submit();
+    }
+
+    // sorts the rows by the column of `th`, the order flips on every click
+    function sort_table(th) {
+        let table = th.closest("table");
+        let column = th.cellIndex;
+        let ascending = th.getAttribute("aria-sort") !== "ascending";
+        for (const header of table.tHead.rows[0].cells) {
+            header.setAttribute("aria-sort", "none");
+        }
+        th.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+        let body = table.tBodies[0];
+        let rows = Array.from(body.rows);
+        rows.sort(function (a, b) {
+            let x = a.cells[column].dataset.sort;
+            let y = b.cells[column].dataset.sort;
+            let order = (x !== "" && y !== "" && !isNaN(x) && !isNaN(y))
+                ? x - y
+                : x.localeCompare(y, undefined, {numeric: true});
+            return ascending ? order : -order;
+        });
+        for (const row of rows) {
+            body.appendChild(row);
+        }
+    }
+
+    // the buttons of the panels of tabs or an accordion, not of the ones in them
+    function panel_buttons(container) {
+        return Array.from(container.querySelectorAll("[data-panel-index]")).filter(function (b) {
+            return b.closest(".ft_tabs, .ft_accordion") === container;
+        });
+    }
+
+    // opens the panel at `index` and closes the others, -1 closes all of them,
+    // the variable is set to the index or to the variant the panel is for
+    function select_panel(id, container, index, variable) {
+        let tabs = container.classList.contains("ft_tabs");
+        let value = index;
+        let keyed = false;
+        for (const button of panel_buttons(container)) {
+            let open = parseInt(button.dataset.panelIndex) === index;
+            if (button.dataset.panelVariant !== undefined) {
+                keyed = true;
+                if (open) {
+                    value = button.dataset.panelVariant;
+                }
+            }
+            if (tabs) {
+                button.setAttribute("aria-selected", String(open));
+                button.tabIndex = open ? 0 : -1;
+            } else {
+                button.setAttribute("aria-expanded", String(open));
+            }
+            let panel = document.getElementById(button.getAttribute("aria-controls"));
+            if (panel !== null) {
+                panel.style.display = open ? "flex" : "none";
+            }
+        }
+        // an or-type variable keeps its variant when all the panels are closed
+        let target = ftd_utils.get_name_and_remaining(variable)[0];
+        if (variable !== "" && ftd_data[id][target] !== undefined && !(keyed && index === -1)) {
+            ftd_utils.handle_action(id, variable, value, ftd_data[id], ftd_external_children);
+        }
+    }
+
+    // arrow keys, Home and End move the focus between the tabs, and open the
+    // focused one, or between the headers of an accordion
+    function panel_key(evt, id, obj, variable) {
+        let container = obj.closest(".ft_tabs, .ft_accordion");
+        let buttons = panel_buttons(container);
+        let current = buttons.indexOf(document.activeElement);
+        if (current === -1) {
+            return;
+        }
+        let tabs = container.classList.contains("ft_tabs");
+        let next = {
+            Home: 0,
+            End: buttons.length - 1,
+            [tabs ? "ArrowLeft" : "ArrowUp"]: current - 1,
+            [tabs ? "ArrowRight" : "ArrowDown"]: current + 1,
+        }[evt.key];
+        if (next === undefined) {
+            return;
+        }
+        evt.preventDefault();
+        let button = buttons[(next + buttons.length) % buttons.length];
+        button.focus();
+        if (tabs) {
+            select_panel(id, container, parseInt(button.dataset.panelIndex), variable);
+        }
+    }
+
     let exports = {};
 
     exports.handle_event = function (evt, id, event, obj) {
@@ -851,6 +1055,184 @@ window.ftd = (function () {
         window.ftd.post_init();
     }
 
+    // the dialogs, popovers and tooltips of the document, as listed by
+    // `Document::body_events()`
+    exports.overlays = function (id, overlays) {
+        let element = function (overlay) {
+            return document.querySelector(`[data-id="${overlay.data_id}"]`);
+        };
+        let anchor = function (overlay) {
+            return overlay.anchor === null ? null : document.getElementById(overlay.anchor);
+        };
+        let is_open = function (overlay) {
+            let e = element(overlay);
+            return e !== null && e.style.display !== "none";
+        };
+        let close = function (overlay) {
+            exports.set_bool(id, overlay.open, false);
+        };
+        let place_all = function () {
+            for (const overlay of overlays) {
+                if (overlay.kind === "popover" && is_open(overlay)) {
+                    place_overlay(element(overlay), anchor(overlay), overlay.placement);
+                }
+            }
+        };
+        // focus goes into a dialog when it opens, and back to where it was
+        // when it closes
+        let sync = function () {
+            for (const overlay of overlays) {
+                let open = is_open(overlay);
+                if (overlay.kind === "tooltip" || open === overlay.was_open) {
+                    continue;
+                }
+                overlay.was_open = open;
+                if (overlay.kind === "popover") {
+                    place_all();
+                } else if (open) {
+                    overlay.opener = document.activeElement;
+                    focusable(element(overlay))[0].focus();
+                } else if (overlay.opener) {
+                    overlay.opener.focus();
+                }
+            }
+        };
+        // a click outside of an open dialog does not reach the page under it
+        let swallow_click = false;
+
+        for (const overlay of overlays) {
+            overlay.was_open = is_open(overlay);
+            let target = anchor(overlay);
+            if (overlay.kind !== "tooltip" || target === null) {
+                continue;
+            }
+            let tooltip = element(overlay);
+            if (!tooltip.id) {
+                tooltip.id = "ft-tooltip-" + overlay.data_id.replace(/[^\w-]/g, "-");
+            }
+            target.setAttribute("aria-describedby", tooltip.id);
+            let show = function () {
+                tooltip.style.display = "flex";
+                place_overlay(tooltip, target, overlay.placement);
+            };
+            let hide = function () {
+                tooltip.style.display = "none";
+            };
+            target.addEventListener("mouseenter", show);
+            target.addEventListener("focusin", show);
+            target.addEventListener("mouseleave", hide);
+            target.addEventListener("focusout", hide);
+        }
+
+        // the pointer goes down before the click that may open an overlay, so
+        // only the overlays open before the click are closed
+        document.addEventListener("pointerdown", function (event) {
+            for (const overlay of overlays) {
+                if (overlay.kind === "tooltip" || !is_open(overlay)) {
+                    continue;
+                }
+                let target = anchor(overlay);
+                if (element(overlay).contains(event.target)
+                    || (target !== null && target.contains(event.target))) {
+                    continue;
+                }
+                if (overlay.kind === "dialog") {
+                    swallow_click = true;
+                }
+                if (overlay.dismissible) {
+                    close(overlay);
+                }
+            }
+            sync();
+        });
+        document.addEventListener("click", function (event) {
+            if (swallow_click) {
+                swallow_click = false;
+                event.preventDefault();
+                event.stopPropagation();
+            }
+        }, true);
+        document.addEventListener("click", sync);
+        document.addEventListener("keydown", function (event) {
+            let open = overlays.filter(function (overlay) {
+                return is_open(overlay);
+            });
+            if (event.key === "Escape") {
+                for (const overlay of open) {
+                    if (overlay.kind === "tooltip") {
+                        element(overlay).style.display = "none";
+                    } else if (overlay.dismissible) {
+                        close(overlay);
+                    }
+                }
+            } else if (event.key === "Tab") {
+                let dialog = open.find(function (overlay) {
+                    return overlay.kind === "dialog";
+                });
+                if (dialog !== undefined) {
+                    trap_focus(event, element(dialog));
+                }
+            }
+            sync();
+        });
+        document.addEventListener("focusin", function (event) {
+            for (const overlay of overlays) {
+                let e = element(overlay);
+                if (overlay.kind === "dialog" && is_open(overlay) && !e.contains(event.target)) {
+                    focusable(e)[0].focus();
+                }
+            }
+        });
+        window.addEventListener("resize", place_all);
+        window.addEventListener("scroll", place_all, true);
+        place_all();
+    }
+
+    // what can get focus in `element`, or `element` itself if nothing can
+    function focusable(element) {
+        let items = Array.from(element.querySelectorAll(
+            'a[href], button, input, select, textarea, [tabindex]:not([tabindex="-1"])'
+        )).filter(function (e) {
+            return !e.disabled && e.getClientRects().length > 0;
+        });
+        return items.length === 0 ? [element] : items;
+    }
+
+    // Tab and Shift+Tab go round the elements of the dialog
+    function trap_focus(event, dialog) {
+        let items = focusable(dialog);
+        let first = items[0];
+        let last = items[items.length - 1];
+        let inside = dialog.contains(document.activeElement);
+        if (event.shiftKey && (!inside || document.activeElement === first)) {
+            event.preventDefault();
+            last.focus();
+        } else if (!event.shiftKey && (!inside || document.activeElement === last)) {
+            event.preventDefault();
+            first.focus();
+        }
+    }
+
+    // places the fixed `overlay` next to `anchor`, inside the window
+    function place_overlay(overlay, anchor, placement) {
+        if (anchor === null) {
+            return;
+        }
+        let gap = 8;
+        let a = anchor.getBoundingClientRect();
+        let o = overlay.getBoundingClientRect();
+        let top = a.bottom + gap;
+        let left = a.left + (a.width - o.width) / 2;
+        if (placement === "top") {
+            top = a.top - o.height - gap;
+        } else if (placement === "left" || placement === "right") {
+            top = a.top + (a.height - o.height) / 2;
+            left = placement === "left" ? a.left - o.width - gap : a.right + gap;
+        }
+        overlay.style.top = Math.max(0, Math.min(top, window.innerHeight - o.height)) + "px";
+        overlay.style.left = Math.max(0, Math.min(left, window.innerWidth - o.width)) + "px";
+    }
+
     exports.set_bool_for_all = function (variable, value) {
         for (let id in ftd_data) {
             if (!ftd_data.hasOwnProperty(id)) {
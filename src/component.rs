@@ -9,10 +9,20 @@ pub struct Component {
     pub events: Vec<ftd::p2::Event>,
     pub condition: Option<ftd::p2::Boolean>,
     pub kernel: bool,
-    pub invocations: Vec<std::collections::BTreeMap<String, ftd::Value>>,
+    pub invocations: Vec<ftd::Invocation>,
     pub line_number: usize,
 }
 
+/// A call of a component, `line_number` is the line of the call in the
+/// document `doc_id` it is written in, directly or in a component.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Invocation<T = std::collections::BTreeMap<String, ftd::Value>> {
+    #[serde(default)]
+    pub doc_id: String,
+    pub line_number: usize,
+    pub arguments: T,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type")]
 pub enum Instruction {
@@ -136,10 +146,8 @@ impl ChildComponent {
         &self,
         children: &[Self],
         doc: &mut ftd::p2::TDoc,
-        invocations: &mut std::collections::BTreeMap<
-            String,
-            Vec<std::collections::BTreeMap<String, ftd::Value>>,
-        >,
+        invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+        state: &mut ftd::execute_doc::State,
         local_container: &[usize],
     ) -> ftd::p1::Result<ElementWithContainer> {
         let id = ftd::p2::utils::string_optional(
@@ -153,7 +161,7 @@ impl ChildComponent {
             mut element,
             child_container,
             ..
-        } = self.call(doc, invocations, false, local_container, id.clone())?;
+        } = self.call(doc, invocations, state, false, local_container, id.clone())?;
         element.set_container_id(id.clone());
         element.set_element_id(id);
 
@@ -184,8 +192,9 @@ impl ChildComponent {
                     local_variables: doc.local_variables,
                     instructions: &instructions,
                     invocations,
+                    state,
                 }
-                .execute(local_container, None)?
+                .execute(local_container, None, self.line_number)?
                 .children;
                 container_children.extend(elements);
             }
@@ -196,7 +205,8 @@ impl ChildComponent {
                     self.line_number,
                 )?;
                 match root_name.as_str() {
-                    "ftd#row" | "ftd#column" | "ftd#scene" | "ftd#grid" | "ftd#text" => {}
+                    "ftd#row" | "ftd#column" | "ftd#form" | "ftd#scene" | "ftd#grid"
+                    | "ftd#tabs" | "ftd#accordion" | "ftd#panel" | "ftd#details" | "ftd#text" => {}
                     t => {
                         return ftd::e2(
                             format!("{} cant have children", t),
@@ -229,9 +239,10 @@ impl ChildComponent {
                     self.line_number,
                     doc,
                     invocations,
+                    state,
                     local_container,
                 )?;
-                reevalute_markups(markups, named_container, doc)?;
+                reevalute_markups(markups, named_container, doc, state)?;
             }
         }
 
@@ -245,10 +256,8 @@ impl ChildComponent {
     pub fn recursive_call(
         &self,
         doc: &mut ftd::p2::TDoc,
-        invocations: &mut std::collections::BTreeMap<
-            String,
-            Vec<std::collections::BTreeMap<String, ftd::Value>>,
-        >,
+        invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+        state: &mut ftd::execute_doc::State,
         is_child: bool,
         local_container: &[usize],
     ) -> ftd::p1::Result<Vec<ElementWithContainer>> {
@@ -281,6 +290,7 @@ impl ChildComponent {
                     &root,
                     doc,
                     invocations,
+                    state,
                     is_child,
                     local_container,
                 )?;
@@ -300,6 +310,7 @@ impl ChildComponent {
                         &root,
                         doc,
                         invocations,
+                        state,
                         is_child,
                         local_container,
                     )?;
@@ -345,10 +356,8 @@ impl ChildComponent {
             index: usize,
             root: &ftd::Component,
             doc: &mut ftd::p2::TDoc,
-            invocations: &mut std::collections::BTreeMap<
-                String,
-                Vec<std::collections::BTreeMap<String, ftd::Value>>,
-            >,
+            invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+            state: &mut ftd::execute_doc::State,
             is_child: bool,
             local_container: &[usize],
         ) -> ftd::p1::Result<ElementWithContainer> {
@@ -419,11 +428,13 @@ impl ChildComponent {
                 &child_component.properties,
                 doc,
                 invocations,
+                state,
                 &None,
                 is_child,
                 &child_component.events,
                 local_container.as_slice(),
                 None,
+                child_component.line_number,
             )?;
 
             if let Some(condition) = &child_component.condition {
@@ -450,10 +461,8 @@ impl ChildComponent {
     pub fn call(
         &self,
         doc: &mut ftd::p2::TDoc,
-        invocations: &mut std::collections::BTreeMap<
-            String,
-            Vec<std::collections::BTreeMap<String, ftd::Value>>,
-        >,
+        invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+        state: &mut ftd::execute_doc::State,
         is_child: bool,
         local_container: &[usize],
         id: Option<String>,
@@ -486,11 +495,13 @@ impl ChildComponent {
             &self.properties,
             doc,
             invocations,
+            state,
             &self.condition,
             is_child,
             &self.events,
             local_container,
             id,
+            self.line_number,
         )?;
 
         if let Some(common) = element.element.get_mut_common() {
@@ -504,12 +515,13 @@ impl ChildComponent {
                 self.line_number,
                 doc,
                 invocations,
+                state,
                 local_container,
             ) {
                 Ok(n) => n,
                 _ => return Ok(element),
             };
-            reevalute_markups(markups, named_container, doc).ok();
+            reevalute_markups(markups, named_container, doc, state).ok();
         }
 
         Ok(element)
@@ -645,10 +657,8 @@ fn markup_get_named_container(
     root: &str,
     line_number: usize,
     doc: &mut ftd::p2::TDoc,
-    invocations: &mut std::collections::BTreeMap<
-        String,
-        Vec<std::collections::BTreeMap<String, ftd::Value>>,
-    >,
+    invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+    state: &mut ftd::execute_doc::State,
     local_container: &[usize],
 ) -> ftd::p1::Result<std::collections::BTreeMap<String, ftd::Element>> {
     let children = {
@@ -690,8 +700,9 @@ fn markup_get_named_container(
         local_variables: doc.local_variables,
         instructions: &instructions,
         invocations,
+        state,
     }
-    .execute(local_container, None)?
+    .execute(local_container, None, line_number)?
     .children;
 
     return convert_to_named_container(&container_children, &elements_name, doc);
@@ -747,6 +758,7 @@ fn reevalute_markups(
     markups: &mut ftd::Markups,
     named_container: std::collections::BTreeMap<String, ftd::Element>,
     doc: &mut ftd::p2::TDoc,
+    state: &mut ftd::execute_doc::State,
 ) -> ftd::p1::Result<()> {
     if !markups.children.is_empty() {
         // no need to re-evalute
@@ -775,7 +787,7 @@ fn reevalute_markups(
             itext: ftd::IText::Markup(markups.clone()),
             children: vec![],
         };
-        reevalute_markup(&mut markup, &named_container, doc)?;
+        reevalute_markup(&mut markup, &named_container, doc, state)?;
         if let ftd::IText::Markup(m) = markup.itext {
             *markups = m;
         }
@@ -784,7 +796,7 @@ fn reevalute_markups(
         return Ok(());
     }
     for markup in all_children.iter_mut() {
-        reevalute_markup(markup, &named_container, doc)?;
+        reevalute_markup(markup, &named_container, doc, state)?;
     }
     markups.children = all_children;
 
@@ -795,6 +807,7 @@ fn reevalute_markup(
     markup: &mut ftd::Markup,
     named_container: &std::collections::BTreeMap<String, ftd::Element>,
     doc: &mut ftd::p2::TDoc,
+    state: &mut ftd::execute_doc::State,
 ) -> ftd::p1::Result<()> {
     let text = match &markup.itext {
         ftd::IText::Text(ftd::Text { text, .. })
@@ -826,10 +839,10 @@ fn reevalute_markup(
 
             let container = match named_container.get(style) {
                 Some(style) => style.to_owned(),
-                None => get_element_doc(doc, style)?,
+                None => get_element_doc(doc, style, state)?,
             };
 
-            let itext = element_to_itext(&container, doc, text, style, named_container)?;
+            let itext = element_to_itext(&container, doc, text, style, named_container, state)?;
 
             children.push(ftd::Markup {
                 itext,
@@ -856,7 +869,7 @@ fn reevalute_markup(
         if let ftd::IText::Markup(_) = child.itext {
             continue;
         }
-        reevalute_markup(child, named_container, doc)?;
+        reevalute_markup(child, named_container, doc, state)?;
     }
     markup.children = children;
 
@@ -896,6 +909,7 @@ fn reevalute_markup(
         text: Option<&str>,
         root: &str,
         named_container: &std::collections::BTreeMap<String, ftd::Element>,
+        state: &mut ftd::execute_doc::State,
     ) -> ftd::p1::Result<ftd::IText> {
         Ok(match element {
             ftd::Element::Text(t) => {
@@ -962,9 +976,15 @@ fn reevalute_markup(
                     }
                     t
                 };
-                let named_container = if let Ok(mut get) =
-                    markup_get_named_container(&[], root, 0, doc, &mut Default::default(), &[])
-                {
+                let named_container = if let Ok(mut get) = markup_get_named_container(
+                    &[],
+                    root,
+                    0,
+                    doc,
+                    &mut Default::default(),
+                    state,
+                    &[],
+                ) {
                     get.extend(named_container.clone());
                     get
                 } else {
@@ -978,7 +998,7 @@ fn reevalute_markup(
                     // `bar` here won't be present inside doc.bag
                     named_container.clone()
                 };
-                reevalute_markups(&mut t, named_container, doc)?;
+                reevalute_markups(&mut t, named_container, doc, state)?;
                 ftd::IText::Markup(t)
             }
             t => {
@@ -994,7 +1014,11 @@ fn reevalute_markup(
         })
     }
 
-    fn get_element_doc(doc: &mut ftd::p2::TDoc, name: &str) -> ftd::p1::Result<ftd::Element> {
+    fn get_element_doc(
+        doc: &mut ftd::p2::TDoc,
+        name: &str,
+        state: &mut ftd::execute_doc::State,
+    ) -> ftd::p1::Result<ftd::Element> {
         let mut root = doc
             .get_component(0, name)
             .map_err(|_| ftd::p1::Error::ParseError {
@@ -1038,7 +1062,7 @@ fn reevalute_markup(
             }
         }
         root.arguments = std::collections::BTreeMap::new();
-        Ok(root.call_without_values(doc)?.element)
+        Ok(root.call_without_values(doc, state)?.element)
     }
 }
 
@@ -1554,10 +1578,8 @@ impl Component {
     fn call_sub_functions(
         &self,
         doc: &mut ftd::p2::TDoc,
-        invocations: &mut std::collections::BTreeMap<
-            String,
-            Vec<std::collections::BTreeMap<String, ftd::Value>>,
-        >,
+        invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+        state: &mut ftd::execute_doc::State,
         call_container: &[usize],
         id: Option<String>,
     ) -> ftd::p1::Result<ElementWithContainer> {
@@ -1590,8 +1612,9 @@ impl Component {
             local_variables: doc.local_variables,
             instructions: &new_instruction,
             invocations,
+            state,
         }
-        .execute(call_container, id);
+        .execute(call_container, id, self.line_number);
 
         fn reference_to_child_component(
             child: &mut ChildComponent,
@@ -1757,16 +1780,19 @@ impl Component {
     fn call_without_values(
         &self,
         doc: &mut ftd::p2::TDoc,
+        state: &mut ftd::execute_doc::State,
     ) -> ftd::p1::Result<ElementWithContainer> {
         self.call(
             &Default::default(),
             doc,
             &mut Default::default(),
+            state,
             &Default::default(),
             false,
             &[],
             &[],
             Default::default(),
+            self.line_number,
         )
     }
 
@@ -1775,20 +1801,57 @@ impl Component {
         &self,
         arguments: &std::collections::BTreeMap<String, Property>,
         doc: &mut ftd::p2::TDoc,
-        invocations: &mut std::collections::BTreeMap<
-            String,
-            Vec<std::collections::BTreeMap<String, ftd::Value>>,
-        >,
+        invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+        state: &mut ftd::execute_doc::State,
         condition: &Option<ftd::p2::Boolean>,
         is_child: bool,
         events: &[ftd::p2::Event],
         local_container: &[usize],
         id: Option<String>,
+        line_number: usize,
     ) -> ftd::p1::Result<ElementWithContainer> {
         invocations
             .entry(self.full_name.clone())
             .or_default()
-            .push(resolve_properties(0, arguments, doc)?);
+            .push(ftd::Invocation {
+                doc_id: state.doc_id(doc.name).to_string(),
+                line_number,
+                arguments: resolve_properties(line_number, arguments, doc)?,
+            });
+        // the root and the children of this component are written where it
+        // is defined
+        let doc_id = self.full_name.split_once('#').map_or(doc.name, |(d, _)| d);
+        state.doc_ids.push(doc_id.to_string());
+        let r = self.call_(
+            arguments,
+            doc,
+            invocations,
+            state,
+            condition,
+            is_child,
+            events,
+            local_container,
+            id,
+            line_number,
+        );
+        state.doc_ids.pop();
+        r
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn call_(
+        &self,
+        arguments: &std::collections::BTreeMap<String, Property>,
+        doc: &mut ftd::p2::TDoc,
+        invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+        state: &mut ftd::execute_doc::State,
+        condition: &Option<ftd::p2::Boolean>,
+        is_child: bool,
+        events: &[ftd::p2::Event],
+        local_container: &[usize],
+        id: Option<String>,
+        line_number: usize,
+    ) -> ftd::p1::Result<ElementWithContainer> {
         if self.root == "ftd.kernel" {
             let element = match self.full_name.as_str() {
                 /*"ftd#text" => ftd::Element::Text(ftd::p2::element::text_from_properties(
@@ -1811,7 +1874,38 @@ impl Component {
                 "ftd#column" => ftd::Element::Column(ftd::p2::element::column_from_properties(
                     arguments, doc, condition, is_child, events,
                 )?),
+                "ftd#form" => ftd::Element::Column(ftd::p2::element::form_from_properties(
+                    arguments, doc, condition, is_child, events,
+                )?),
+                "ftd#dialog" | "ftd#popover" | "ftd#tooltip" => {
+                    ftd::Element::Column(ftd::p2::element::overlay_from_properties(
+                        self.full_name.as_str(),
+                        arguments,
+                        doc,
+                        condition,
+                        is_child,
+                        events,
+                    )?)
+                }
+                "ftd#tabs" | "ftd#accordion" | "ftd#panel" | "ftd#details" => {
+                    ftd::Element::Column(ftd::p2::element::disclosure_from_properties(
+                        self.full_name.as_str(),
+                        arguments,
+                        doc,
+                        condition,
+                        is_child,
+                        events,
+                    )?)
+                }
                 "ftd#iframe" => ftd::Element::IFrame(ftd::p2::element::iframe_from_properties(
+                    arguments,
+                    doc,
+                    condition,
+                    is_child,
+                    events,
+                    &state.embed_providers,
+                )?),
+                "ftd#toc" => ftd::Element::Toc(ftd::p2::element::toc_from_properties(
                     arguments, doc, condition, is_child, events,
                 )?),
                 "ftd#integer" => ftd::Element::Integer(ftd::p2::element::integer_from_properties(
@@ -1826,6 +1920,61 @@ impl Component {
                 "ftd#input" => ftd::Element::Input(ftd::p2::element::input_from_properties(
                     arguments, doc, condition, is_child, events,
                 )?),
+                "ftd#checkbox" => {
+                    ftd::Element::Checkbox(ftd::p2::element::checkbox_from_properties(
+                        arguments, doc, condition, is_child, events,
+                    )?)
+                }
+                "ftd#toggle" => ftd::Element::Toggle(ftd::p2::element::checkbox_from_properties(
+                    arguments, doc, condition, is_child, events,
+                )?),
+                "ftd#radio" => ftd::Element::Radio(ftd::p2::element::select_from_properties(
+                    arguments, doc, condition, is_child, events,
+                )?),
+                "ftd#select" => ftd::Element::Select(ftd::p2::element::select_from_properties(
+                    arguments, doc, condition, is_child, events,
+                )?),
+                "ftd#svg" | "ftd#icon" => ftd::Element::Svg(ftd::p2::element::svg_from_properties(
+                    arguments,
+                    doc,
+                    condition,
+                    is_child,
+                    events,
+                    &state.icon_sets,
+                )?),
+                "ftd#table" => {
+                    let rows = ftd::p2::element::table_rows(
+                        &resolve_properties(line_number, arguments, doc)?,
+                        doc,
+                    )?;
+                    let mut table = ftd::p2::element::table_from_properties(
+                        arguments, doc, condition, is_child, events, &rows,
+                    )?;
+                    for (c, column) in table.columns.iter().enumerate() {
+                        let component = match column.component {
+                            Some(ref component) => component,
+                            None => continue,
+                        };
+                        for (r, row) in rows.iter().enumerate() {
+                            table.rows[r][c].element = Some(table_cell(
+                                component,
+                                row[column.field.as_str()].clone(),
+                                doc,
+                                invocations,
+                                state,
+                                &[local_container, &[r, c]].concat(),
+                                line_number,
+                            )?);
+                        }
+                    }
+                    ftd::Element::Table(table)
+                }
+                "ftd#video" => ftd::Element::Video(ftd::p2::element::media_from_properties(
+                    arguments, doc, condition, is_child, events,
+                )?),
+                "ftd#audio" => ftd::Element::Audio(ftd::p2::element::media_from_properties(
+                    arguments, doc, condition, is_child, events,
+                )?),
                 "ftd#scene" => ftd::Element::Scene(ftd::p2::element::scene_from_properties(
                     arguments, doc, condition, is_child, events,
                 )?),
@@ -1835,7 +1984,18 @@ impl Component {
                 "ftd#text" => ftd::Element::Markup(ftd::p2::element::markup_from_properties(
                     arguments, doc, condition, is_child, events,
                 )?),
-                _ => unreachable!(),
+                name => match state.plugins.get(name) {
+                    Some(plugin) => ftd::Element::Native(ftd::p2::element::native_from_properties(
+                        plugin, arguments, doc, condition, is_child, events,
+                    )?),
+                    None => {
+                        return ftd::e2(
+                            format!("unknown kernel component {}", name),
+                            state.doc_id(doc.name),
+                            line_number,
+                        )
+                    }
+                },
             };
             Ok(ElementWithContainer {
                 element,
@@ -1879,11 +2039,13 @@ impl Component {
                     &self.properties,
                     doc,
                     invocations,
+                    state,
                     &self.condition,
                     is_child,
                     &self.events,
                     local_container,
                     None,
+                    self.line_number,
                 )?
             } else {
                 ElementWithContainer {
@@ -1914,6 +2076,16 @@ impl Component {
                 | ftd::Element::Image(_)
                 | ftd::Element::IFrame(_)
                 | ftd::Element::Input(_)
+                | ftd::Element::Table(_)
+                | ftd::Element::Svg(_)
+                | ftd::Element::Audio(_)
+                | ftd::Element::Video(_)
+                | ftd::Element::Checkbox(_)
+                | ftd::Element::Toggle(_)
+                | ftd::Element::Radio(_)
+                | ftd::Element::Select(_)
+                | ftd::Element::Toc(_)
+                | ftd::Element::Native(_)
                 | ftd::Element::Integer(_)
                 | ftd::Element::Decimal(_)
                 | ftd::Element::Boolean(_)
@@ -1935,7 +2107,7 @@ impl Component {
                         children,
                         child_container,
                         ..
-                    } = self.call_sub_functions(doc, invocations, local_container, id)?;
+                    } = self.call_sub_functions(doc, invocations, state, local_container, id)?;
 
                     if let Some(ref append_at) = container.append_at {
                         if let Some(ref child_container) = child_container {
@@ -1968,6 +2140,9 @@ impl Component {
             if let Some(common) = element.get_mut_common() {
                 common.conditional_attribute.extend(conditional_attribute);
                 common.events.extend(events);
+                if !self.full_name.starts_with("ftd#") {
+                    common.components.push(self.full_name.clone());
+                }
             }
 
             Ok(ElementWithContainer {
@@ -2206,6 +2381,42 @@ pub fn recursive_child_component(
     }
 }
 
+// `component` called with `value`, for a cell of `ftd.table`
+fn table_cell(
+    component: &str,
+    value: ftd::Value,
+    doc: &mut ftd::p2::TDoc,
+    invocations: &mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+    state: &mut ftd::execute_doc::State,
+    local_container: &[usize],
+    line_number: usize,
+) -> ftd::p1::Result<ftd::Element> {
+    let root = doc.get_component(line_number, component)?;
+    if !root.arguments.contains_key("value") {
+        return ftd::e2(
+            format!("{} can not show a table cell, it has no value", component),
+            doc.name,
+            line_number,
+        );
+    }
+    let child = ChildComponent {
+        root: root.full_name,
+        properties: std::array::IntoIter::new([(
+            "value".to_string(),
+            Property {
+                default: Some(ftd::PropertyValue::Value { value }),
+                ..Default::default()
+            },
+        )])
+        .collect(),
+        line_number,
+        ..Default::default()
+    };
+    Ok(child
+        .call(doc, invocations, state, true, local_container, None)?
+        .element)
+}
+
 fn is_component(name: &str) -> bool {
     !(name.starts_with("component ")
         || name.starts_with("var ")
@@ -2220,11 +2431,28 @@ fn is_component(name: &str) -> bool {
         || (name == "ftd.image")
         || (name == "ftd.row")
         || (name == "ftd.column")
+        || (name == "ftd.form")
+        || (name == "ftd.dialog")
+        || (name == "ftd.popover")
+        || (name == "ftd.tooltip")
+        || (name == "ftd.tabs")
+        || (name == "ftd.accordion")
+        || (name == "ftd.panel")
+        || (name == "ftd.details")
         || (name == "ftd.iframe")
         || (name == "ftd.integer")
         || (name == "ftd.decimal")
         || (name == "ftd.boolean")
         || (name == "ftd.input")
+        || (name == "ftd.checkbox")
+        || (name == "ftd.toggle")
+        || (name == "ftd.radio")
+        || (name == "ftd.select")
+        || (name == "ftd.table")
+        || (name == "ftd.svg")
+        || (name == "ftd.icon")
+        || (name == "ftd.video")
+        || (name == "ftd.audio")
         || (name == "ftd.scene")
         || (name == "ftd.grid")
         || (name == "ftd.markup"))
@@ -2394,6 +2622,68 @@ pub fn read_properties(
                     &root_arguments,
                     Some(source.clone()),
                 )?,
+                // `value` of `ftd.input` can also be an integer or a decimal
+                // variable, for `type: number` and `type: range`
+                Err(e) if root == "ftd#input" && name == "value" => {
+                    [ftd::p2::Kind::integer(), ftd::p2::Kind::decimal()]
+                        .iter()
+                        .find_map(|k| {
+                            ftd::PropertyValue::resolve_value(
+                                line_number,
+                                value.as_str(),
+                                Some(k.to_owned().into_optional()),
+                                doc,
+                                arguments,
+                                Some(source.clone()),
+                            )
+                            .ok()
+                        })
+                        .ok_or(e)?
+                }
+                // `data` of `ftd.table` can be a list of any record
+                Err(e) if root == "ftd#table" && name == "data" => {
+                    let p = match ftd::PropertyValue::resolve_value(
+                        line_number,
+                        value.as_str(),
+                        None,
+                        doc,
+                        arguments,
+                        Some(source.clone()),
+                    ) {
+                        Ok(p) => p,
+                        Err(_) => return Err(e),
+                    };
+                    match p.kind().inner() {
+                        ftd::p2::Kind::List { kind, .. }
+                            if matches!(kind.inner(), ftd::p2::Kind::Record { .. }) => {}
+                        _ => return Err(e),
+                    }
+                    p
+                }
+                // `value` of `ftd.radio` and `ftd.select` can also be an or-type
+                // variable, its variants are the options, and so can `selected`
+                // of `ftd.tabs` and `ftd.accordion`, its variants key the panels
+                Err(e)
+                    if (matches!(root, "ftd#radio" | "ftd#select") && name == "value")
+                        || (matches!(root, "ftd#tabs" | "ftd#accordion") && name == "selected") =>
+                {
+                    let p = match ftd::PropertyValue::resolve_value(
+                        line_number,
+                        value.as_str(),
+                        None,
+                        doc,
+                        arguments,
+                        Some(source.clone()),
+                    ) {
+                        Ok(p) => p,
+                        Err(_) => return Err(e),
+                    };
+                    match p.kind().inner() {
+                        ftd::p2::Kind::OrType { .. } | ftd::p2::Kind::OrTypeWithVariant { .. } => {}
+                        _ => return Err(e),
+                    }
+                    p
+                }
                 Err(e) => return Err(e),
             };
 
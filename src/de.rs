@@ -0,0 +1,410 @@
+/// Reads a value written by `ftd::to_string()`. A struct is read from the only
+/// section in `s`, a list is read from all of them. Section names are not
+/// checked, so the same source can be read into any type with matching fields.
+///
+/// Headers, sub-sections and the `$caption` and `$body` fields are mapped the
+/// same way `ftd::to_string()` writes them. A header or sub-section that is
+/// given more than once is read as a list, and a single one can be read as a
+/// list with one item.
+pub fn from_str<T: serde::de::DeserializeOwned>(s: &str) -> ftd::p1::Result<T> {
+    let mut sections = ftd::p1::parse(s, "")?
+        .iter()
+        .filter(|s| !s.is_commented)
+        .map(Value::from_section)
+        .collect::<Vec<_>>();
+    let value = if sections.len() == 1 {
+        sections.remove(0)
+    } else {
+        Value::List(sections)
+    };
+    T::deserialize(value)
+}
+
+enum Value {
+    Scalar {
+        value: String,
+        line_number: usize,
+    },
+    List(Vec<Value>),
+    Record {
+        variant: Option<String>,
+        fields: Vec<(String, Value)>,
+        line_number: usize,
+    },
+}
+
+impl Value {
+    fn from_section(section: &ftd::p1::Section) -> Value {
+        let mut record = Value::record(
+            section.name.as_str(),
+            section.line_number,
+            &section.caption,
+            &section.header,
+            &section.body_without_comment(),
+        );
+        for sub in section.sub_sections.0.iter().filter(|s| !s.is_commented) {
+            let (key, value) = Value::from_sub_section(sub);
+            record.add_field(key, value);
+        }
+        record
+    }
+
+    fn from_sub_section(sub: &ftd::p1::SubSection) -> (String, Value) {
+        let key = match sub.name.split_once('.') {
+            Some((key, _)) => key,
+            None => sub.name.as_str(),
+        };
+        let value = Value::record(
+            sub.name.as_str(),
+            sub.line_number,
+            &sub.caption,
+            &sub.header,
+            &sub.body_without_comment(),
+        );
+        (key.to_string(), value)
+    }
+
+    fn record(
+        name: &str,
+        line_number: usize,
+        caption: &Option<String>,
+        header: &ftd::p1::Header,
+        body: &Option<(usize, String)>,
+    ) -> Value {
+        let mut record = Value::Record {
+            variant: name.split_once('.').map(|(_, v)| v.to_string()),
+            fields: vec![],
+            line_number,
+        };
+        if let Some(caption) = caption {
+            record.add_field(ftd::ser::CAPTION.to_string(), scalar(caption, line_number));
+        }
+        for (line_number, k, v) in header.0.iter() {
+            if k.starts_with('/') {
+                continue;
+            }
+            record.add_field(k.to_string(), scalar(v, *line_number));
+        }
+        if let Some((line_number, body)) = body {
+            record.add_field(ftd::ser::BODY.to_string(), scalar(body, *line_number));
+        }
+        record
+    }
+
+    fn add_field(&mut self, key: String, value: Value) {
+        let fields = match self {
+            Value::Record { fields, .. } => fields,
+            _ => unreachable!(),
+        };
+        match fields.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, Value::List(items))) => items.push(value),
+            Some((_, existing)) => {
+                let first = std::mem::replace(existing, Value::List(vec![]));
+                *existing = Value::List(vec![first, value]);
+            }
+            None => fields.push((key, value)),
+        }
+    }
+
+    fn line_number(&self) -> usize {
+        match self {
+            Value::Scalar { line_number, .. } | Value::Record { line_number, .. } => *line_number,
+            Value::List(items) => items.first().map(Value::line_number).unwrap_or(0),
+        }
+    }
+}
+
+fn scalar(value: &str, line_number: usize) -> Value {
+    Value::Scalar {
+        value: value.to_string(),
+        line_number,
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: serde::de::Visitor<'de>>(self, visitor: V) -> ftd::p1::Result<V::Value> {
+                match self {
+                    Value::Scalar { value, line_number } => match value.trim().parse() {
+                        Ok(v) => visitor.$visit(v),
+                        Err(_) => ftd::e2(format!("can't parse {:?}", value), "", line_number),
+                    },
+                    v => serde::Deserializer::deserialize_any(v, visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> serde::Deserializer<'de> for Value {
+    type Error = ftd::p1::Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> ftd::p1::Result<V::Value> {
+        match self {
+            Value::Scalar { value, .. } => visitor.visit_string(value),
+            Value::List(items) => {
+                visitor.visit_seq(serde::de::value::SeqDeserializer::new(items.into_iter()))
+            }
+            Value::Record { fields, .. } => {
+                visitor.visit_map(serde::de::value::MapDeserializer::new(fields.into_iter()))
+            }
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: serde::de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: serde::de::Visitor<'de>>(self, visitor: V) -> ftd::p1::Result<V::Value> {
+        match self {
+            Value::List(items) => {
+                visitor.visit_seq(serde::de::value::SeqDeserializer::new(items.into_iter()))
+            }
+            v => visitor.visit_seq(serde::de::value::SeqDeserializer::new(std::iter::once(v))),
+        }
+    }
+
+    fn deserialize_tuple<V: serde::de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        serde::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        serde::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: serde::de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        let line_number = self.line_number();
+        match self {
+            Value::Scalar { value, .. } => {
+                let variant: serde::de::value::StringDeserializer<ftd::p1::Error> =
+                    serde::de::IntoDeserializer::into_deserializer(value);
+                visitor.visit_enum(variant)
+            }
+            Value::Record {
+                variant: Some(variant),
+                fields,
+                line_number,
+            } => visitor.visit_enum(Enum {
+                variant,
+                value: Value::Record {
+                    variant: None,
+                    fields,
+                    line_number,
+                },
+            }),
+            _ => ftd::e2(format!("expected a variant of {}", name), "", line_number),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> serde::de::IntoDeserializer<'de, ftd::p1::Error> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Value {
+        self
+    }
+}
+
+struct Enum {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> serde::de::EnumAccess<'de> for Enum {
+    type Error = ftd::p1::Error;
+    type Variant = Value;
+
+    fn variant_seed<V: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> ftd::p1::Result<(V::Value, Value)> {
+        let variant: serde::de::value::StringDeserializer<ftd::p1::Error> =
+            serde::de::IntoDeserializer::into_deserializer(self.variant);
+        Ok((seed.deserialize(variant)?, self.value))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for Value {
+    type Error = ftd::p1::Error;
+
+    fn unit_variant(self) -> ftd::p1::Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> ftd::p1::Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        serde::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> ftd::p1::Result<V::Value> {
+        serde::Deserializer::deserialize_any(self, visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct TocItem {
+        #[serde(rename = "$caption")]
+        title: String,
+        url: Option<String>,
+        number: i64,
+        tags: Vec<String>,
+        #[serde(default)]
+        children: Vec<Link>,
+        shape: Vec<Shape>,
+        #[serde(rename = "$body")]
+        description: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Link {
+        #[serde(rename = "$caption")]
+        title: String,
+        url: String,
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Shape {
+        Dot,
+        Circle { radius: f64 },
+    }
+
+    #[test]
+    fn round_trip() {
+        let item = TocItem {
+            title: "Getting Started".to_string(),
+            url: None,
+            number: 1,
+            tags: vec!["intro".to_string(), "setup".to_string()],
+            children: vec![Link {
+                title: "Install".to_string(),
+                url: "/install/".to_string(),
+            }],
+            shape: vec![Shape::Circle { radius: 1.5 }],
+            description: Some("hello\n\n-- world".to_string()),
+        };
+
+        let source = ftd::to_string(&item).unwrap();
+        pretty_assertions::assert_eq!(
+            source,
+            indoc::indoc!(
+                "
+                -- toc-item: Getting Started
+                number: 1
+                tags: intro
+                tags: setup
+
+                hello
+
+                \\-- world
+
+                --- children: Install
+                url: /install/
+
+                --- shape.circle:
+                radius: 1.5
+                "
+            )
+        );
+        pretty_assertions::assert_eq!(ftd::from_str::<TocItem>(source.as_str()).unwrap(), item);
+
+        let items: Vec<Link> = ftd::from_str(indoc::indoc!(
+            "
+            -- link: Home
+            url: /
+
+            /-- link: Draft
+            url: /draft/
+
+            -- link: About
+            url: /about/
+            "
+        ))
+        .unwrap();
+        pretty_assertions::assert_eq!(
+            items,
+            vec![
+                Link {
+                    title: "Home".to_string(),
+                    url: "/".to_string(),
+                },
+                Link {
+                    title: "About".to_string(),
+                    url: "/about/".to_string(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(ftd::from_str::<TocItem>("-- toc-item: a\nnumber: one\nshape: dot\n").is_err());
+        assert!(ftd::to_string(&"hello").is_err());
+        assert!(ftd::to_string(&Link {
+            title: "two\nlines".to_string(),
+            url: "/".to_string(),
+        })
+        .is_err());
+    }
+}
@@ -41,6 +41,21 @@ impl DNode {
     }
 
     pub fn to_html(&self, id: &str) -> String {
+        let mut html = String::new();
+        match self.write_html::<std::convert::Infallible>(id, &mut html, &mut |_| Ok(())) {
+            Ok(()) => html,
+            Err(e) => match e {},
+        }
+    }
+
+    /// appends the html of this node to `html`, `check` is called with the
+    /// html written so far after every child, so a render can stop early
+    pub(crate) fn write_html<E>(
+        &self,
+        id: &str,
+        html: &mut String,
+        check: &mut dyn FnMut(&str) -> Result<(), E>,
+    ) -> Result<(), E> {
         let style = format!("style=\"{}\"", self.style_to_html(self.visible));
         let classes = format!("class=\"{}\"", self.class_to_html());
 
@@ -62,27 +77,35 @@ impl DNode {
         };
 
         if self.node == "img" {
-            return format!("<img {attrs} {style}>", attrs = attrs, style = style);
+            html.push_str(&format!(
+                "<img {attrs} {style}>",
+                attrs = attrs,
+                style = style
+            ));
+            return check(html);
         }
-        let body = match self.text.as_ref() {
-            Some(v) => v.to_string(),
-            None => self
-                .children
-                .iter()
-                .map(|v| v.to_html(id))
-                .collect::<Vec<String>>()
-                .join("\n"),
-        };
 
         // TODO: indent things properly
-        format!(
-            "<{node} {attrs} {style} {classes}>{body}</{node}>",
+        html.push_str(&format!(
+            "<{node} {attrs} {style} {classes}>",
             node = self.node.as_str(),
             attrs = attrs,
             style = style,
             classes = classes,
-            body = body,
-        )
+        ));
+        match self.text.as_ref() {
+            Some(v) => html.push_str(v),
+            None => {
+                for (i, child) in self.children.iter().enumerate() {
+                    if i != 0 {
+                        html.push('\n');
+                    }
+                    child.write_html(id, html, check)?;
+                }
+            }
+        }
+        html.push_str(&format!("</{}>", self.node));
+        check(html)
     }
 }
 
@@ -0,0 +1,350 @@
+/// Turns the url of a page on some site, eg a Vimeo video, into the url of a
+/// player for it that can be shown in `ftd.iframe` with `embed: <url>`.
+///
+/// Providers a `Library` returns from `embed_providers()` are asked before the
+/// built in ones (YouTube, Vimeo, Loom, Figma, CodePen, Google Maps and
+/// Twitter).
+pub trait EmbedProvider: Send + Sync {
+    fn name(&self) -> String;
+
+    /// `None` if `url` is not a page of this provider. The returned `src` is
+    /// used as the `src` of the iframe, so it must not be built from anything
+    /// in `url` that is not checked.
+    fn embed(&self, url: &str) -> Option<Embed>;
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Embed {
+    pub src: String,
+    /// width and height, the iframe keeps this ratio unless it has a height
+    pub aspect_ratio: Option<(i64, i64)>,
+}
+
+lazy_static::lazy_static! {
+    static ref BUILTIN: Vec<std::sync::Arc<dyn EmbedProvider>> = builtin();
+}
+
+/// the embed providers of the document being rendered, a provider replaces
+/// the built in one with the same name
+#[derive(Clone, Default)]
+pub struct EmbedProviders(Vec<std::sync::Arc<dyn EmbedProvider>>);
+
+impl EmbedProviders {
+    pub(crate) fn from_library(providers: Vec<std::sync::Arc<dyn EmbedProvider>>) -> Self {
+        EmbedProviders(providers)
+    }
+
+    pub(crate) fn embed(&self, url: &str) -> Option<Embed> {
+        let url = url.trim();
+        let builtin = BUILTIN
+            .iter()
+            .filter(|b| !self.0.iter().any(|p| p.name() == b.name()));
+        self.0.iter().chain(builtin).find_map(|p| p.embed(url))
+    }
+}
+
+impl std::fmt::Debug for EmbedProviders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|p| p.name()))
+            .finish()
+    }
+}
+
+impl PartialEq for EmbedProviders {
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .map(|p| p.name())
+            .eq(other.0.iter().map(|p| p.name()))
+    }
+}
+
+// a built in provider, the urls of which are matched by one of `patterns`
+struct Pattern {
+    name: &'static str,
+    patterns: Vec<regex::Regex>,
+    src: fn(url: &str, captures: &regex::Captures) -> Option<String>,
+    aspect_ratio: Option<(i64, i64)>,
+}
+
+impl EmbedProvider for Pattern {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn embed(&self, url: &str) -> Option<Embed> {
+        self.patterns.iter().find_map(|pattern| {
+            pattern
+                .captures(url)
+                .and_then(|c| (self.src)(url, &c))
+                .map(|src| Embed {
+                    src,
+                    aspect_ratio: self.aspect_ratio,
+                })
+        })
+    }
+}
+
+fn builtin() -> Vec<std::sync::Arc<dyn EmbedProvider>> {
+    // all the patterns are fixed, and tested
+    let re = |pattern: &str| regex::Regex::new(pattern).unwrap();
+    let providers = vec![
+        Pattern {
+            name: "youtube",
+            patterns: vec![re(
+                r"^(https?://)?(www\.|m\.)?(youtube\.\w\w\w?|youtu\.be)/",
+            )],
+            src: |url, _| ftd::youtube_id::from_raw(url),
+            aspect_ratio: Some((16, 9)),
+        },
+        Pattern {
+            name: "vimeo",
+            patterns: vec![
+                re(r"^(https?://)?(www\.)?vimeo\.com/(channels/[\w-]+/)?(?P<id>\d+)/?(\?.*)?$"),
+                re(r"^(https?://)?player\.vimeo\.com/video/(?P<id>\d+)/?(\?.*)?$"),
+            ],
+            src: |_, c| Some(format!("https://player.vimeo.com/video/{}", &c["id"])),
+            aspect_ratio: Some((16, 9)),
+        },
+        Pattern {
+            name: "loom",
+            patterns: vec![re(
+                r"^(https?://)?(www\.)?loom\.com/(share|embed)/(?P<id>[a-f0-9]+)/?(\?.*)?$",
+            )],
+            src: |_, c| Some(format!("https://www.loom.com/embed/{}", &c["id"])),
+            aspect_ratio: Some((16, 9)),
+        },
+        Pattern {
+            name: "figma",
+            patterns: vec![re(
+                r"^https://(www\.)?figma\.com/(file|proto|design|board)/[a-zA-Z0-9]+(/\S*)?$",
+            )],
+            src: |url, _| {
+                Some(format!(
+                    "https://www.figma.com/embed?embed_host=share&url={}",
+                    encode_uri_component(url)
+                ))
+            },
+            aspect_ratio: Some((16, 9)),
+        },
+        Pattern {
+            name: "codepen",
+            patterns: vec![re(
+                r"^(https?://)?codepen\.io/(?P<user>[\w-]+)/(pen|embed|full)/(?P<id>\w+)/?(\?.*)?$",
+            )],
+            src: |_, c| {
+                Some(format!(
+                    "https://codepen.io/{}/embed/{}?default-tab=result",
+                    &c["user"], &c["id"]
+                ))
+            },
+            aspect_ratio: Some((4, 3)),
+        },
+        Pattern {
+            name: "google-maps",
+            patterns: vec![
+                re(r"^https://(www\.)?google\.com/maps/embed\?pb=[\w%!.+-]+$"),
+                re(r"^https://(www\.)?google\.[a-z.]+/maps/(place|search)/(?P<q>[\w%+.,-]+)"),
+                re(r"^https://maps\.google\.[a-z.]+/(maps)?\?q=(?P<q>[\w%+.,-]+)"),
+            ],
+            src: |url, c| match c.name("q") {
+                Some(q) => Some(format!(
+                    "https://maps.google.com/maps?q={}&output=embed",
+                    q.as_str()
+                )),
+                None => Some(url.to_string()),
+            },
+            aspect_ratio: Some((4, 3)),
+        },
+        Pattern {
+            name: "twitter",
+            patterns: vec![re(
+                r"^(https?://)?(www\.|mobile\.)?(twitter|x)\.com/\w+/status/(?P<id>\d+)/?(\?.*)?$",
+            )],
+            src: |_, c| {
+                Some(format!(
+                    "https://platform.twitter.com/embed/Tweet.html?id={}",
+                    &c["id"]
+                ))
+            },
+            aspect_ratio: Some((1, 1)),
+        },
+    ];
+    providers
+        .into_iter()
+        .map(|p| std::sync::Arc::new(p) as std::sync::Arc<dyn EmbedProvider>)
+        .collect()
+}
+
+// same as `encodeURIComponent()` in js
+fn encode_uri_component(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    fn embed(url: &str) -> Option<(String, Option<(i64, i64)>)> {
+        super::EmbedProviders::default()
+            .embed(url)
+            .map(|e| (e.src, e.aspect_ratio))
+    }
+
+    #[test]
+    fn builtin() {
+        let video = Some((16, 9));
+        for (url, src, aspect_ratio) in [
+            (
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                "https://youtube.com/embed/dQw4w9WgXcQ",
+                video,
+            ),
+            (
+                "https://youtu.be/dQw4w9WgXcQ",
+                "https://youtube.com/embed/dQw4w9WgXcQ",
+                video,
+            ),
+            (
+                "https://vimeo.com/76979871",
+                "https://player.vimeo.com/video/76979871",
+                video,
+            ),
+            (
+                "https://www.loom.com/share/0123abcd",
+                "https://www.loom.com/embed/0123abcd",
+                video,
+            ),
+            (
+                "https://www.figma.com/file/AbC123/Design?node-id=1",
+                "https://www.figma.com/embed?embed_host=share&url=https%3A%2F%2Fwww.figma.com\
+                %2Ffile%2FAbC123%2FDesign%3Fnode-id%3D1",
+                video,
+            ),
+            (
+                "https://codepen.io/amit/pen/xyzAbc",
+                "https://codepen.io/amit/embed/xyzAbc?default-tab=result",
+                Some((4, 3)),
+            ),
+            (
+                "https://www.google.com/maps/place/Eiffel+Tower/@48.8,2.29",
+                "https://maps.google.com/maps?q=Eiffel+Tower&output=embed",
+                Some((4, 3)),
+            ),
+            (
+                "https://x.com/fifthtry/status/1234567890",
+                "https://platform.twitter.com/embed/Tweet.html?id=1234567890",
+                Some((1, 1)),
+            ),
+        ] {
+            pretty_assertions::assert_eq!(embed(url), Some((src.to_string(), aspect_ratio)));
+        }
+
+        for url in [
+            "dQw4w9WgXcQ",
+            "https://example.com/watch?v=dQw4w9WgXcQ",
+            "https://vimeo.com/76979871\"><script>",
+            "javascript:alert(1)",
+        ] {
+            pretty_assertions::assert_eq!(embed(url), None, "{}", url);
+        }
+    }
+
+    struct Slides;
+
+    impl super::EmbedProvider for Slides {
+        fn name(&self) -> String {
+            "test-slides".to_string()
+        }
+
+        fn embed(&self, url: &str) -> Option<super::Embed> {
+            url.strip_prefix("https://slides.test/")
+                .filter(|id| id.chars().all(char::is_alphanumeric))
+                .map(|id| super::Embed {
+                    src: format!("https://slides.test/embed/{}", id),
+                    aspect_ratio: None,
+                })
+        }
+    }
+
+    #[test]
+    fn iframe() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- ftd.iframe:
+                embed: https://vimeo.com/76979871
+                sandbox: allow-scripts allow-same-origin
+
+                -- ftd.iframe:
+                embed: https://slides.test/intro
+                loading: eager
+                allow: autoplay
+
+                -- ftd.iframe:
+                src: https://example.com/
+                height: 200
+                "
+            ),
+            &ftd::test::Library {
+                embed_providers: vec![std::sync::Arc::new(Slides)],
+                ..Default::default()
+            },
+        )
+        .expect("found error");
+
+        let nodes = doc
+            .main
+            .container
+            .children
+            .iter()
+            .map(|c| match c {
+                ftd::Element::IFrame(i) => i.to_node("foo/bar"),
+                t => panic!("expected iframe, found: {:?}", t),
+            })
+            .collect::<Vec<_>>();
+        let attr = |i: usize, name: &str| nodes[i].attrs.get(name).map(String::as_str);
+        pretty_assertions::assert_eq!(
+            attr(0, "src"),
+            Some("https://player.vimeo.com/video/76979871")
+        );
+        pretty_assertions::assert_eq!(attr(0, "sandbox"), Some("allow-scripts allow-same-origin"));
+        pretty_assertions::assert_eq!(attr(0, "loading"), Some("lazy"));
+        pretty_assertions::assert_eq!(attr(0, "allow"), Some("fullscreen"));
+        pretty_assertions::assert_eq!(
+            nodes[0].style.get("aspect-ratio").map(String::as_str),
+            Some("16 / 9")
+        );
+        pretty_assertions::assert_eq!(attr(1, "src"), Some("https://slides.test/embed/intro"));
+        pretty_assertions::assert_eq!(attr(1, "loading"), Some("eager"));
+        pretty_assertions::assert_eq!(attr(1, "allow"), Some("autoplay"));
+        assert!(!nodes[1].style.contains_key("aspect-ratio"));
+        assert!(!nodes[2].attrs.contains_key("sandbox"));
+
+        ftd::test::assert_invalid(
+            "",
+            &[
+                "-- ftd.iframe:\nembed: https://example.com/",
+                "-- ftd.iframe:\nembed: https://slides.test/intro",
+                "-- ftd.iframe:\nembed: https://vimeo.com/1\nsrc: https://example.com/",
+                "-- ftd.iframe:\nsrc: https://example.com/\nloading: later",
+            ],
+        );
+    }
+}
@@ -5,20 +5,76 @@ pub struct ExecuteDoc<'a> {
     pub bag: &'a std::collections::BTreeMap<String, ftd::p2::Thing>,
     pub local_variables: &'a mut std::collections::BTreeMap<String, ftd::p2::Thing>,
     pub instructions: &'a [ftd::Instruction],
-    pub invocations: &'a mut std::collections::BTreeMap<
-        String,
-        Vec<std::collections::BTreeMap<String, ftd::Value>>,
-    >,
+    pub invocations: &'a mut std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
+    pub state: &'a mut State,
+}
+
+/// kept for the whole render, across the `ExecuteDoc`s of the components it
+/// expands
+#[derive(Debug, Default, PartialEq)]
+pub struct State {
+    /// time taken by every component expansion, `None` unless profiling
+    pub components: Option<Vec<ftd::Timing>>,
+    /// time taken by the expansions nested in every component being expanded,
+    /// innermost last
+    pub(crate) nested: Vec<std::time::Duration>,
+    pub budget: ftd::limits::Budget,
+    pub plugins: ftd::plugin::Plugins,
+    pub embed_providers: ftd::embed::EmbedProviders,
+    pub icon_sets: ftd::icon::IconSets,
+    /// documents the components being called are defined in, innermost last
+    pub(crate) doc_ids: Vec<String>,
+}
+
+impl State {
+    fn start_component(&mut self) -> std::time::Instant {
+        if self.components.is_some() {
+            self.nested.push(Default::default());
+        }
+        std::time::Instant::now()
+    }
+
+    fn end_component(
+        &mut self,
+        name: &str,
+        doc_id: &str,
+        line_number: usize,
+        start: std::time::Instant,
+    ) {
+        let components = match self.components.as_mut() {
+            Some(components) => components,
+            None => return,
+        };
+        let mut timing = ftd::Timing::since(name, doc_id, line_number, start);
+        let nested = self.nested.pop().unwrap_or_default();
+        timing.self_duration = timing.duration.saturating_sub(nested);
+        if let Some(parent) = self.nested.last_mut() {
+            *parent += timing.duration;
+        }
+        components.push(timing);
+    }
+
+    /// the document the call being executed is written in
+    pub(crate) fn doc_id<'a>(&'a self, main: &'a str) -> &'a str {
+        self.doc_ids.last().map(|d| d.as_str()).unwrap_or(main)
+    }
 }
 
 impl<'a> ExecuteDoc<'a> {
+    /// `line_number` is the line of the section whose children are executed,
+    /// in the document the call being executed is written in
     pub(crate) fn execute(
         &mut self,
         parent_container: &[usize],
         id: Option<String>,
+        line_number: usize,
     ) -> ftd::p1::Result<ftd::component::ElementWithContainer> {
+        let doc_id = self.state.doc_id(self.name).to_string();
+        self.state.budget.enter(doc_id.as_str(), line_number)?;
         let mut index = 0;
-        self.execute_(&mut index, false, parent_container, 0, None, id)
+        let r = self.execute_(&mut index, false, parent_container, 0, None, id);
+        self.state.budget.leave();
+        r
     }
 
     fn execute_(
@@ -96,11 +152,27 @@ impl<'a> ExecuteDoc<'a> {
                         (parent, inner)
                     };
 
+                    let start = self.state.start_component();
                     let ftd::component::ElementWithContainer {
                         element,
                         children: container_children,
                         child_container,
-                    } = parent.super_call(&inner, &mut doc, self.invocations, &local_container)?;
+                    } = parent.super_call(
+                        &inner,
+                        &mut doc,
+                        self.invocations,
+                        self.state,
+                        &local_container,
+                    )?;
+                    self.state
+                        .budget
+                        .add_elements(1, doc.name, parent.line_number)?;
+                    self.state.end_component(
+                        parent.root.as_str(),
+                        doc.name,
+                        parent.line_number,
+                        start,
+                    );
 
                     children = self.add_element(
                         children,
@@ -165,6 +237,7 @@ impl<'a> ExecuteDoc<'a> {
                         }
                     };
 
+                    let start = self.state.start_component();
                     let ftd::component::ElementWithContainer {
                         element: mut e,
                         child_container,
@@ -172,10 +245,14 @@ impl<'a> ExecuteDoc<'a> {
                     } = f.call(
                         &mut doc,
                         self.invocations,
+                        self.state,
                         true,
                         &local_container,
                         new_id.clone(),
                     )?;
+                    self.state.budget.add_elements(1, doc.name, f.line_number)?;
+                    self.state
+                        .end_component(f.root.as_str(), doc.name, f.line_number, start);
                     e.set_element_id(new_id);
                     if !is_visible {
                         e.set_non_visibility(!is_visible);
@@ -195,8 +272,19 @@ impl<'a> ExecuteDoc<'a> {
                 }
                 ftd::Instruction::RecursiveChildComponent { child: f }
                 | ftd::Instruction::ChildComponent { child: f } => {
-                    let elements =
-                        f.recursive_call(&mut doc, self.invocations, true, &local_container)?;
+                    let start = self.state.start_component();
+                    let elements = f.recursive_call(
+                        &mut doc,
+                        self.invocations,
+                        self.state,
+                        true,
+                        &local_container,
+                    )?;
+                    self.state
+                        .budget
+                        .add_elements(elements.len(), doc.name, f.line_number)?;
+                    self.state
+                        .end_component(f.root.as_str(), doc.name, f.line_number, start);
                     for e in elements {
                         children = self.add_element(
                             children,
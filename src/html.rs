@@ -261,6 +261,16 @@ impl ftd::Element {
             Self::Column(i) => (i.to_node(doc_id)),
             Self::IFrame(i) => (i.to_node(doc_id)),
             Self::Input(i) => (i.to_node(doc_id)),
+            Self::Table(i) => (i.to_node(doc_id)),
+            Self::Svg(i) => (i.to_node(doc_id)),
+            Self::Audio(i) => (i.to_audio_node(doc_id)),
+            Self::Video(i) => (i.to_video_node(doc_id)),
+            Self::Checkbox(i) => (i.to_node(doc_id)),
+            Self::Toggle(i) => (i.to_switch_node(doc_id)),
+            Self::Radio(i) => (i.to_radio_node(doc_id)),
+            Self::Select(i) => (i.to_node(doc_id)),
+            Self::Toc(i) => (i.to_node(doc_id)),
+            Self::Native(i) => (i.to_node(doc_id)),
             Self::Integer(i) => (i.to_node(doc_id)),
             Self::Boolean(i) => (i.to_node(doc_id)),
             Self::Decimal(i) => (i.to_node(doc_id)),
@@ -319,10 +329,8 @@ impl Node {
         children_style.extend(container.children_style());
         let node = match common.link {
             Some(_) => "a",
-            None => match common.submit {
-                Some(_) => "form",
-                None => "div",
-            },
+            None if common.submit.is_some() || common.form.is_some() => "form",
+            None => "div",
         };
 
         let (id, external_children_container, external_children) = {
@@ -644,10 +652,212 @@ impl ftd::Column {
             children
         };
 
+        if let Some(ref form) = self.common.form {
+            // `ftd::Element::check_forms` found no error on render
+            let fields = ftd::Element::form_fields(&self.container, doc_id).unwrap_or_default();
+            n.attrs.insert(s("method"), s(form.method.to_str()));
+            if let Some(ref action) = form.action {
+                n.attrs.insert(s("action"), escape(action));
+            }
+            n.events.push(form_submit_event(form, &fields));
+        }
+
+        if let Some(ref overlay) = self.common.overlay {
+            n.classes.push(format!("ft_{}", overlay.kind.to_str()));
+            // over `position: relative` a container may get for its children
+            n.style.insert(s("position"), s("fixed"));
+            match overlay.kind {
+                ftd::OverlayKind::Dialog => {
+                    n.attrs.insert(s("role"), s("dialog"));
+                    n.attrs.insert(s("aria-modal"), s("true"));
+                    // so the dialog can be focused when nothing in it can be
+                    n.attrs.insert(s("tabindex"), s("-1"));
+                }
+                ftd::OverlayKind::Popover => {
+                    n.attrs.insert(s("role"), s("dialog"));
+                }
+                ftd::OverlayKind::Tooltip => {
+                    n.attrs.insert(s("role"), s("tooltip"));
+                }
+            }
+            if let Some(ref label) = overlay.label {
+                n.attrs.insert(s("aria-label"), escape(label));
+            }
+        }
+
+        if let Some(ref disclosure) = self.common.disclosure {
+            disclosure_parts(
+                &mut n,
+                disclosure,
+                &self.container.children,
+                self.common.data_id.as_deref().unwrap_or_default(),
+            );
+        }
+
         n
     }
 }
 
+// the tab list or the accordion headers for the panels in `children`, and the
+// summary of details; the panels themselves are shown by `is_not_visible`.
+// `data_id` is the data id of the container, the ids of its panels and of their
+// buttons are made from it and the index of the panel
+fn disclosure_parts(
+    n: &mut Node,
+    disclosure: &ftd::Disclosure,
+    children: &[ftd::Element],
+    data_id: &str,
+) {
+    // same as the ids of tooltips in ftd.js
+    let data_id = data_id.replace(
+        |c: char| !(c.is_alphanumeric() || c == '_' || c == '-'),
+        "-",
+    );
+    let event = |name: &str, action: &str| ftd::Event {
+        name: s(name),
+        action: ftd::Action {
+            action: s(action),
+            target: disclosure.variable.clone().unwrap_or_default(),
+            parameters: Default::default(),
+        },
+    };
+    match disclosure.kind {
+        ftd::DisclosureKind::Panel => {}
+        ftd::DisclosureKind::Details => {
+            n.node = s("details");
+            if disclosure.open {
+                n.attrs.insert(s("open"), s(""));
+            }
+            n.children.insert(
+                0,
+                Node {
+                    node: s("summary"),
+                    text: disclosure.title.as_deref().map(escape),
+                    ..Default::default()
+                },
+            );
+            if disclosure.variable.is_some() {
+                n.events.push(event("ontoggle", "toggle-details"));
+            }
+        }
+        ftd::DisclosureKind::Tabs | ftd::DisclosureKind::Accordion => {
+            let tabs = disclosure.kind == ftd::DisclosureKind::Tabs;
+            n.classes.push(format!("ft_{}", disclosure.kind.to_str()));
+            let mut list = vec![];
+            let mut nodes = vec![];
+            for (child, mut node) in children.iter().zip(std::mem::take(&mut n.children)) {
+                let panel = match child.get_common() {
+                    Some(ftd::Common {
+                        disclosure: Some(panel),
+                        ..
+                    }) if panel.kind == ftd::DisclosureKind::Panel => panel,
+                    _ => {
+                        nodes.push(node);
+                        continue;
+                    }
+                };
+                let index = panel.selected.unwrap_or_default();
+                let open = disclosure.selected == Some(index);
+                let panel_id = node
+                    .attrs
+                    .get("id")
+                    .cloned()
+                    .unwrap_or_else(|| format!("ft-panel-{}-{}", data_id, index));
+                let button_id = format!("ft-panel-button-{}-{}", data_id, index);
+                let mut button = Node {
+                    node: s("button"),
+                    text: panel.title.as_deref().map(escape),
+                    ..Default::default()
+                };
+                button.attrs.insert(s("type"), s("button"));
+                button.attrs.insert(s("id"), button_id.clone());
+                button.attrs.insert(s("aria-controls"), panel_id.clone());
+                button
+                    .attrs
+                    .insert(s("data-panel-index"), index.to_string());
+                if let Some(variant) = disclosure.variants.get(index as usize) {
+                    button
+                        .attrs
+                        .insert(s("data-panel-variant"), variant.clone());
+                }
+                button.events.push(event("onclick", "select-panel"));
+                node.attrs.insert(s("id"), panel_id);
+                node.attrs.insert(s("aria-labelledby"), button_id);
+                if tabs {
+                    button.attrs.insert(s("role"), s("tab"));
+                    button.attrs.insert(s("aria-selected"), open.to_string());
+                    // only the open tab is in the tab order, arrow keys move
+                    // between the tabs
+                    button
+                        .attrs
+                        .insert(s("tabindex"), s(if open { "0" } else { "-1" }));
+                    node.attrs.insert(s("role"), s("tabpanel"));
+                    list.push(button);
+                } else {
+                    button.attrs.insert(s("aria-expanded"), open.to_string());
+                    node.attrs.insert(s("role"), s("region"));
+                    let mut heading = Node {
+                        node: s("h3"),
+                        children: vec![button],
+                        ..Default::default()
+                    };
+                    heading.classes.push(s("ft_accordion_header"));
+                    nodes.push(heading);
+                }
+                nodes.push(node);
+            }
+            if tabs {
+                let mut tablist = Node {
+                    node: s("div"),
+                    children: list,
+                    ..Default::default()
+                };
+                tablist.attrs.insert(s("role"), s("tablist"));
+                tablist.classes.push(s("ft_tablist"));
+                nodes.insert(0, tablist);
+            }
+            n.events.push(event("onkeydown", "panel-key"));
+            n.children = nodes;
+        }
+    }
+}
+
+// the variables in `fields` are read when the form is submitted, the form is
+// not submitted by the browser itself
+fn form_submit_event(
+    form: &ftd::Form,
+    fields: &std::collections::BTreeMap<String, String>,
+) -> ftd::Event {
+    let parameter = |value: &str| {
+        vec![ftd::event::ParameterData {
+            value: value.to_string(),
+            reference: None,
+        }]
+    };
+    let mut parameters: std::collections::BTreeMap<String, Vec<ftd::event::ParameterData>> =
+        Default::default();
+    parameters.insert(
+        s("fields"),
+        parameter(serde_json::to_string(fields).unwrap_or_default().as_str()),
+    );
+    parameters.insert(s("method"), parameter(form.method.to_str()));
+    parameters.insert(s("encoding"), parameter(form.encoding.to_str()));
+    if let Some(ref action) = form.action {
+        parameters.insert(s("action"), parameter(action));
+    }
+    if let Some(ref function) = form.message_host {
+        parameters.insert(s("message-host"), parameter(function));
+    }
+    ftd::Event {
+        name: s("onsubmit"),
+        action: ftd::Action {
+            action: s("submit-form"),
+            target: s(""),
+            parameters,
+        },
+    }
+}
+
 impl ftd::Text {
     pub fn to_node(&self, doc_id: &str) -> Node {
         // TODO: proper tag based on self.common.region
@@ -825,45 +1035,96 @@ impl ftd::Code {
 
 impl ftd::Image {
     pub fn to_node(&self, doc_id: &str) -> Node {
-        let mut n = Node::from_common("img", &self.common, doc_id);
-        if self.common.link.is_some() {
-            n.node = s("a");
-            let mut img = Node {
-                condition: None,
-                events: vec![],
-                classes: vec![],
-                node: s("img"),
-                attrs: Default::default(),
-                style: Default::default(),
-                children: vec![],
-                external_children: vec![],
-                open_id: None,
-                external_children_container: vec![],
-                children_style: Default::default(),
-                text: None,
-                null: false,
-            };
-            img.style.insert(s("width"), s("100%"));
-            img.attrs.insert(s("src"), escape(self.src.light.as_str()));
-            img.attrs
-                .insert(s("alt"), escape(self.description.as_str()));
-            if self.crop {
-                img.style.insert(s("object-fit"), s("cover"));
-                img.style.insert(s("object-position"), s("0 0"));
+        if self.common.link.is_none() && self.sources.is_empty() {
+            let mut n = Node::from_common("img", &self.common, doc_id);
+            self.img_attrs(&mut n);
+            if (self.crop || self.fit.is_some()) && self.common.width.is_none() {
+                n.style.insert(s("width"), s("100%"));
             }
+            return n;
+        }
+
+        let mut img = Node {
+            node: s("img"),
+            ..Default::default()
+        };
+        self.img_attrs(&mut img);
+        img.style.insert(s("width"), s("100%"));
+        if self.sources.is_empty() {
+            let mut n = Node::from_common("a", &self.common, doc_id);
             n.children.push(img);
-        } else {
-            n.attrs.insert(s("src"), escape(self.src.light.as_str()));
-            n.attrs.insert(s("alt"), escape(self.description.as_str()));
-            if self.crop {
+            return n;
+        }
+
+        img.style.insert(s("display"), s("block"));
+        let mut children: Vec<Node> = self
+            .sources
+            .iter()
+            .map(|v| v.to_node(self.sizes.as_deref()))
+            .collect();
+        children.push(img);
+        match self.common.link {
+            Some(_) => {
+                let mut n = Node::from_common("a", &self.common, doc_id);
+                n.children.push(Node {
+                    node: s("picture"),
+                    children,
+                    ..Default::default()
+                });
+                n
+            }
+            None => {
+                let mut n = Node::from_common("picture", &self.common, doc_id);
+                n.children = children;
+                n
+            }
+        }
+    }
+
+    fn img_attrs(&self, n: &mut Node) {
+        n.attrs.insert(s("src"), escape(self.src.light.as_str()));
+        n.attrs.insert(s("alt"), escape(self.description.as_str()));
+        if let Some(ref l) = self.loading {
+            n.attrs.insert(s("loading"), s(l.to_str()));
+        }
+        if let Some(ref sizes) = self.sizes {
+            n.attrs.insert(s("sizes"), escape(sizes));
+        }
+        if let Some(ref p) = self.fetch_priority {
+            n.attrs.insert(s("fetchpriority"), s(p.to_str()));
+        }
+        if let Some(ref r) = self.aspect_ratio {
+            n.style.insert(s("aspect-ratio"), escape(r.as_str()));
+        }
+        match self.fit {
+            Some(ref fit) => {
+                n.style.insert(s("object-fit"), s(fit.to_str()));
+            }
+            None if self.crop => {
                 n.style.insert(s("object-fit"), s("cover"));
                 n.style.insert(s("object-position"), s("0 0"));
-                if self.common.width.is_none() {
-                    n.style.insert(s("width"), s("100%"));
-                }
             }
+            None => {}
         }
+    }
+}
 
+impl ftd::ImageSource {
+    /// ftd.js switches `srcset` to `data-dark` in dark mode
+    pub fn to_node(&self, sizes: Option<&str>) -> Node {
+        let mut n = Node {
+            node: s("source"),
+            ..Default::default()
+        };
+        n.attrs.insert(s("media"), escape(self.media.as_str()));
+        n.attrs.insert(s("srcset"), escape(self.src.light.as_str()));
+        n.attrs
+            .insert(s("data-light"), escape(self.src.light.as_str()));
+        n.attrs
+            .insert(s("data-dark"), escape(self.src.dark.as_str()));
+        if let Some(sizes) = sizes {
+            n.attrs.insert(s("sizes"), escape(sizes));
+        }
         n
     }
 }
@@ -872,8 +1133,202 @@ impl ftd::IFrame {
     pub fn to_node(&self, doc_id: &str) -> Node {
         let mut n = Node::from_common("iframe", &self.common, doc_id);
         n.attrs.insert(s("src"), escape(self.src.as_str()));
-        n.attrs.insert(s("allow"), s("fullscreen"));
+        n.attrs.insert(
+            s("allow"),
+            escape(self.allow.as_deref().unwrap_or("fullscreen")),
+        );
         n.attrs.insert(s("allowfullscreen"), s("allowfullscreen"));
+        n.attrs.insert(s("loading"), s(self.loading.to_str()));
+        if let Some(ref sandbox) = self.sandbox {
+            n.attrs.insert(s("sandbox"), escape(sandbox));
+        }
+        if let (Some((w, h)), None) = (self.aspect_ratio, &self.common.height) {
+            n.style.insert(s("aspect-ratio"), format!("{} / {}", w, h));
+            n.style.insert(s("height"), s("auto"));
+            if self.common.width.is_none() {
+                n.style.insert(s("width"), s("100%"));
+            }
+        }
+        n
+    }
+}
+
+impl ftd::Svg {
+    pub fn to_node(&self, doc_id: &str) -> Node {
+        // the svg is drawn with `currentColor`, so `color` of the span colours it
+        let mut n = Node::from_common("span", &self.common, doc_id);
+        n.classes.push(s("ft_svg"));
+        if self.icon.is_some() {
+            n.classes.push(s("ft_icon"));
+        }
+        match self.label {
+            Some(ref l) => {
+                n.attrs.insert(s("role"), s("img"));
+                n.attrs.insert(s("aria-label"), escape(l));
+            }
+            None => {
+                n.attrs.insert(s("aria-hidden"), s("true"));
+            }
+        }
+        n.text = Some(self.svg.clone());
+        n
+    }
+}
+
+impl ftd::Table {
+    pub fn to_node(&self, doc_id: &str) -> Node {
+        let mut n = Node::from_common("table", &self.common, doc_id);
+        n.classes.push(s("ft_table"));
+        if self.sticky_header {
+            n.classes.push(s("ft_table_sticky"));
+        }
+        if let Some(ref stripe) = self.stripe_color {
+            // ftd.css colours the rows with the one of these for the colour scheme
+            n.classes.push(s("ft_table_striped"));
+            n.style.insert(s("--ft-table-stripe"), color(&stripe.light));
+            n.style
+                .insert(s("--ft-table-stripe-dark"), color(&stripe.dark));
+        }
+
+        let header = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut th = Node {
+                    node: s("th"),
+                    text: Some(escape(column.caption.as_str())),
+                    ..Default::default()
+                };
+                th.attrs.insert(s("scope"), s("col"));
+                if let Some(ref width) = column.width {
+                    let (key, value) = length(width, "width");
+                    th.style.insert(s(key.as_str()), value);
+                }
+                if let Some(ref align) = column.align {
+                    let (key, value) = text_align(align);
+                    th.style.insert(key, value);
+                }
+                if self.sortable {
+                    // a button, so the header can be sorted with the keyboard too
+                    let mut button = Node {
+                        node: s("button"),
+                        text: th.text.take(),
+                        ..Default::default()
+                    };
+                    button.attrs.insert(s("type"), s("button"));
+                    button.classes.push(s("ft_table_sort"));
+                    button.events.push(ftd::Event {
+                        name: s("onclick"),
+                        action: ftd::Action {
+                            action: s("sort-table"),
+                            target: s(""),
+                            parameters: Default::default(),
+                        },
+                    });
+                    th.attrs.insert(s("aria-sort"), s("none"));
+                    th.children.push(button);
+                }
+                th
+            })
+            .collect();
+
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| Node {
+                node: s("tr"),
+                children: row
+                    .iter()
+                    .zip(self.columns.iter())
+                    .map(|(cell, column)| {
+                        let mut td = Node {
+                            node: s("td"),
+                            ..Default::default()
+                        };
+                        td.attrs.insert(s("data-sort"), escape(cell.text.as_str()));
+                        if let Some(ref align) = column.align {
+                            let (key, value) = text_align(align);
+                            td.style.insert(key, value);
+                        }
+                        match cell.element {
+                            Some(ref element) => td.children.push(element.to_node(doc_id)),
+                            None => td.text = Some(escape(cell.text.as_str())),
+                        }
+                        td
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect();
+
+        n.children = vec![
+            Node {
+                node: s("thead"),
+                children: vec![Node {
+                    node: s("tr"),
+                    children: header,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Node {
+                node: s("tbody"),
+                children: rows,
+                ..Default::default()
+            },
+        ];
+        n
+    }
+}
+
+impl ftd::Media {
+    pub fn to_video_node(&self, doc_id: &str) -> Node {
+        let mut n = self.to_node("video", doc_id);
+        if let Some(ref poster) = self.poster {
+            n.attrs.insert(s("poster"), escape(poster.light.as_str()));
+        }
+        n
+    }
+
+    pub fn to_audio_node(&self, doc_id: &str) -> Node {
+        self.to_node("audio", doc_id)
+    }
+
+    fn to_node(&self, node: &str, doc_id: &str) -> Node {
+        let mut n = Node::from_common(node, &self.common, doc_id);
+        n.attrs.insert(s("src"), escape(self.src.light.as_str()));
+        for (attr, on) in [
+            ("autoplay", self.autoplay),
+            ("loop", self.loop_),
+            ("muted", self.muted),
+            ("controls", self.controls),
+        ] {
+            if on {
+                n.attrs.insert(s(attr), s(""));
+            }
+        }
+        n.children = self
+            .tracks
+            .iter()
+            .map(|t| {
+                let mut track = Node {
+                    node: s("track"),
+                    ..Default::default()
+                };
+                track.attrs.insert(s("kind"), s(t.kind.as_str()));
+                track.attrs.insert(s("src"), escape(t.src.as_str()));
+                if let Some(ref lang) = t.lang {
+                    track.attrs.insert(s("srclang"), escape(lang));
+                }
+                if let Some(ref label) = t.label {
+                    track.attrs.insert(s("label"), escape(label));
+                }
+                if t.default {
+                    track.attrs.insert(s("default"), s(""));
+                }
+                track
+            })
+            .collect();
         n
     }
 }
@@ -976,14 +1431,188 @@ impl ftd::Markup {
 
 impl ftd::Input {
     pub fn to_node(&self, doc_id: &str) -> Node {
-        let mut n = Node::from_common("input", &self.common, doc_id);
+        let mut n = match self.type_ {
+            ftd::InputType::TextArea => {
+                let mut n = Node::from_common("textarea", &self.common, doc_id);
+                n.text = self.value.as_ref().map(|v| escape(v));
+                n
+            }
+            _ => {
+                let mut n = Node::from_common("input", &self.common, doc_id);
+                if self.type_ != ftd::InputType::Text {
+                    n.attrs.insert(s("type"), s(self.type_.to_str()));
+                }
+                if let Some(ref v) = self.value {
+                    n.attrs.insert(s("value"), escape(v));
+                }
+                n
+            }
+        };
         if let Some(ref p) = self.placeholder {
             n.attrs.insert(s("placeholder"), escape(p));
         }
+        for (name, value) in [
+            ("min", &self.min),
+            ("max", &self.max),
+            ("step", &self.step),
+            ("pattern", &self.pattern),
+        ] {
+            if let Some(v) = value {
+                n.attrs.insert(s(name), escape(v));
+            }
+        }
+        if self.required {
+            n.attrs.insert(s("required"), s(""));
+        }
+        if let Some(l) = self.max_length {
+            n.attrs.insert(s("maxlength"), l.to_string());
+        }
         n
     }
 }
 
+impl ftd::Checkbox {
+    pub fn to_node(&self, doc_id: &str) -> Node {
+        let mut n = Node::from_common("input", &self.common, doc_id);
+        n.attrs.insert(s("type"), s("checkbox"));
+        if self.checked {
+            n.attrs.insert(s("checked"), s(""));
+        }
+        if let Some(ref l) = self.label {
+            n.attrs.insert(s("aria-label"), escape(l));
+        }
+        n
+    }
+
+    /// a checkbox announced, and drawn, as an on / off switch
+    pub fn to_switch_node(&self, doc_id: &str) -> Node {
+        let mut n = self.to_node(doc_id);
+        n.attrs.insert(s("role"), s("switch"));
+        n.classes.push(s("ft_switch"));
+        n
+    }
+}
+
+impl ftd::Select {
+    pub fn to_node(&self, doc_id: &str) -> Node {
+        let mut n = Node::from_common("select", &self.common, doc_id);
+        if let Some(ref l) = self.label {
+            n.attrs.insert(s("aria-label"), escape(l));
+        }
+        // an empty option is picked till the variable is set to one of the options
+        if !self.options.iter().any(|o| Some(o) == self.value.as_ref()) {
+            n.children.push(select_option("", true));
+        }
+        n.children.extend(
+            self.options
+                .iter()
+                .map(|o| select_option(o, Some(o) == self.value.as_ref())),
+        );
+        n
+    }
+
+    pub fn to_radio_node(&self, doc_id: &str) -> Node {
+        let mut n = Node::from_common("div", &self.common, doc_id);
+        n.attrs.insert(s("role"), s("radiogroup"));
+        if let Some(ref l) = self.label {
+            n.attrs.insert(s("aria-label"), escape(l));
+        }
+        // the data id is unique to the group once `ftd::Element::set_id` ran,
+        // as it does on every render
+        let name = format!(
+            "radio:{}:{}",
+            self.common.data_id.as_deref().unwrap_or_default(),
+            doc_id
+        );
+        n.children = self
+            .options
+            .iter()
+            .map(|o| {
+                let mut radio = Node {
+                    node: s("input"),
+                    ..Default::default()
+                };
+                radio.attrs.insert(s("type"), s("radio"));
+                radio.attrs.insert(s("name"), escape(name.as_str()));
+                radio.attrs.insert(s("value"), escape(o));
+                if Some(o) == self.value.as_ref() {
+                    radio.attrs.insert(s("checked"), s(""));
+                }
+                let text = Node {
+                    node: s("span"),
+                    text: Some(escape(o)),
+                    ..Default::default()
+                };
+                Node {
+                    node: s("label"),
+                    children: vec![radio, text],
+                    ..Default::default()
+                }
+            })
+            .collect();
+        n
+    }
+}
+
+fn select_option(value: &str, selected: bool) -> Node {
+    let mut n = Node {
+        node: s("option"),
+        text: Some(escape(value)),
+        ..Default::default()
+    };
+    n.attrs.insert(s("value"), escape(value));
+    if selected {
+        n.attrs.insert(s("selected"), s(""));
+    }
+    n
+}
+
+impl ftd::Native {
+    pub fn to_node(&self, doc_id: &str) -> Node {
+        let mut n = Node::from_common(self.node.node.as_str(), &self.common, doc_id);
+        n.attrs.extend(self.node.attrs.clone());
+        n.style.extend(self.node.style.clone());
+        n.classes.extend(self.node.classes.clone());
+        n.children = self.node.children.clone();
+        n.text = self.node.text.clone();
+        n
+    }
+}
+
+impl ftd::Toc {
+    pub fn to_node(&self, doc_id: &str) -> Node {
+        let mut n = Node::from_common("nav", &self.common, doc_id);
+        n.children = toc_list(&self.outline).into_iter().collect();
+        n
+    }
+}
+
+// nested `<ul>` of links to the headings in `outline`
+fn toc_list(outline: &[ftd::OutlineItem]) -> Option<Node> {
+    if outline.is_empty() {
+        return None;
+    }
+    let items = outline.iter().map(|h| {
+        let mut link = Node {
+            node: s("a"),
+            text: Some(escape(h.text.original.as_str())),
+            ..Default::default()
+        };
+        link.attrs
+            .insert(s("href"), format!("#{}", escape(h.slug.as_str())));
+        Node {
+            node: s("li"),
+            children: std::iter::once(link).chain(toc_list(&h.children)).collect(),
+            ..Default::default()
+        }
+    });
+    Some(Node {
+        node: s("ul"),
+        children: items.collect(),
+        ..Default::default()
+    })
+}
+
 impl ftd::Common {
     fn add_class(&self) -> Vec<String> {
         let d: Vec<String> = vec![s("ft_md")];
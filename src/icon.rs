@@ -0,0 +1,434 @@
+/// A set of icons, eg Heroicons, that `ftd.icon` can show in the documents
+/// interpreted with a `Library` that returns it from `icon_sets()`:
+///
+/// ```ftd
+/// -- ftd.icon: arrow-right
+/// set: heroicons
+/// ```
+///
+/// Icons should draw with `currentColor`, so they take the `color` of the
+/// `ftd.icon`.
+pub trait IconSet: Send + Sync {
+    fn name(&self) -> String;
+
+    /// the `<svg>` of the icon `name`, it is checked same as the svg of
+    /// `ftd.svg` before it is used
+    fn icon(&self, name: &str) -> Option<String>;
+}
+
+/// the icon sets of the document being rendered
+#[derive(Clone, Default)]
+pub struct IconSets(Vec<std::sync::Arc<dyn IconSet>>);
+
+impl IconSets {
+    pub(crate) fn from_library(sets: Vec<std::sync::Arc<dyn IconSet>>) -> Self {
+        IconSets(sets)
+    }
+
+    /// `name` from `set`, or from the first icon set that has it
+    pub(crate) fn icon(&self, set: Option<&str>, name: &str) -> Option<String> {
+        self.0
+            .iter()
+            .filter(|s| set.map_or(true, |set| s.name() == set))
+            .find_map(|s| s.icon(name))
+    }
+}
+
+impl std::fmt::Debug for IconSets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|s| s.name()))
+            .finish()
+    }
+}
+
+impl PartialEq for IconSets {
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .map(|s| s.name())
+            .eq(other.0.iter().map(|s| s.name()))
+    }
+}
+
+// the elements and attributes an inline svg can have, none of them can run
+// script or load anything
+const ELEMENTS: &[&str] = &[
+    "svg",
+    "g",
+    "defs",
+    "symbol",
+    "use",
+    "title",
+    "desc",
+    "path",
+    "rect",
+    "circle",
+    "ellipse",
+    "line",
+    "polyline",
+    "polygon",
+    "text",
+    "tspan",
+    "linearGradient",
+    "radialGradient",
+    "stop",
+    "clipPath",
+    "mask",
+    "pattern",
+];
+
+const ATTRIBUTES: &[&str] = &[
+    "xmlns",
+    "xmlns:xlink",
+    "version",
+    "id",
+    "class",
+    "role",
+    "focusable",
+    "aria-hidden",
+    "aria-label",
+    "viewBox",
+    "preserveAspectRatio",
+    "width",
+    "height",
+    "x",
+    "y",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "cx",
+    "cy",
+    "r",
+    "rx",
+    "ry",
+    "fx",
+    "fy",
+    "d",
+    "points",
+    "pathLength",
+    "transform",
+    "href",
+    "xlink:href",
+    "color",
+    "display",
+    "visibility",
+    "opacity",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "clip-rule",
+    "clip-path",
+    "clipPathUnits",
+    "mask",
+    "maskUnits",
+    "stroke",
+    "stroke-width",
+    "stroke-opacity",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "vector-effect",
+    "offset",
+    "stop-color",
+    "stop-opacity",
+    "gradientUnits",
+    "gradientTransform",
+    "spreadMethod",
+    "patternUnits",
+    "patternTransform",
+    "font-family",
+    "font-size",
+    "font-weight",
+    "text-anchor",
+    "dominant-baseline",
+];
+
+/// `svg` if it is a single `<svg>` element that can be inlined in the page,
+/// made of the elements and attributes in `ELEMENTS` and `ATTRIBUTES` only
+pub(crate) fn check_svg(svg: &str, doc_id: &str, line_number: usize) -> ftd::p1::Result<String> {
+    let svg = svg.trim();
+    if !svg.starts_with("<svg") || !svg.ends_with("</svg>") {
+        return ftd::e2("expected an <svg> element", doc_id, line_number);
+    }
+    if let Some(found) = not_allowed(svg) {
+        return ftd::e2(
+            format!("svg can not contain: {}", found),
+            doc_id,
+            line_number,
+        );
+    }
+    Ok(svg.to_string())
+}
+
+// the first thing in `svg` that is not allowed; only the markup this reads the
+// same way the browser does is allowed, so attribute values must be quoted,
+// and there are no comments, CDATA sections or entities other than the ones
+// for markup characters
+fn not_allowed(svg: &str) -> Option<String> {
+    let mut open: Vec<&str> = vec![];
+    let mut rest = svg;
+    while !rest.is_empty() {
+        let (text, tag) = rest.split_at(rest.find('<').unwrap_or(rest.len()));
+        if open.is_empty() && !text.trim().is_empty() {
+            return Some(text.trim().to_string());
+        }
+        if let Some(found) = not_allowed_text(text) {
+            return Some(found);
+        }
+        if tag.is_empty() {
+            break;
+        }
+        let end = match tag.find('>') {
+            Some(end) => end,
+            None => return Some(tag.to_string()),
+        };
+        let inner = &tag[1..end];
+        rest = &tag[end + 1..];
+
+        if let Some(name) = inner.strip_prefix('/') {
+            if open.pop() != Some(name) {
+                return Some(format!("<{}>", inner));
+            }
+            continue;
+        }
+        // an element after the `<svg>`
+        if open.is_empty() && tag.len() != svg.len() {
+            return Some(format!("<{}>", inner));
+        }
+
+        let (inner, self_closing) = match inner.strip_suffix('/') {
+            Some(inner) => (inner, true),
+            None => (inner, false),
+        };
+        let (name, mut attrs) = inner.split_at(
+            inner
+                .find(|c: char| c.is_ascii_whitespace())
+                .unwrap_or(inner.len()),
+        );
+        if !ELEMENTS.contains(&name) {
+            return Some(format!("<{}>", name));
+        }
+        loop {
+            attrs = attrs.trim_start();
+            if attrs.is_empty() {
+                break;
+            }
+            let (attr, value) = match attrs.split_once('=') {
+                Some((attr, value)) => (attr.trim_end(), value.trim_start()),
+                None => return Some(attrs.to_string()),
+            };
+            if !ATTRIBUTES.contains(&attr) && !is_data_attribute(attr) {
+                return Some(attr.to_string());
+            }
+            let quote = match value.chars().next() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return Some(attr.to_string()),
+            };
+            let (value, after) = match value[1..].split_once(quote) {
+                Some(v) => v,
+                None => return Some(attr.to_string()),
+            };
+            if let Some(found) = not_allowed_text(value).or_else(|| not_allowed_url(attr, value)) {
+                return Some(found);
+            }
+            if !after.is_empty() && !after.starts_with(|c: char| c.is_ascii_whitespace()) {
+                return Some(after.to_string());
+            }
+            attrs = after;
+        }
+        if !self_closing {
+            open.push(name);
+        }
+    }
+    open.pop().map(|name| format!("<{}>", name))
+}
+
+fn is_data_attribute(attr: &str) -> bool {
+    attr.strip_prefix("data-").map_or(false, |a| {
+        !a.is_empty()
+            && a.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    })
+}
+
+// entities could hide anything from the other checks, eg `java&Tab;script:`
+fn not_allowed_text(text: &str) -> Option<String> {
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        rest = &rest[i..];
+        if !["&amp;", "&lt;", "&gt;", "&quot;", "&apos;"]
+            .iter()
+            .any(|e| rest.starts_with(e))
+        {
+            return Some(rest.chars().take(10).collect());
+        }
+        rest = &rest[1..];
+    }
+    None
+}
+
+// links can only point in the svg, eg `href="#arrow"` or `fill="url(#fade)"`
+fn not_allowed_url(attr: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    if (attr == "href" || attr == "xlink:href") && !value.starts_with('#') {
+        return Some(format!("{}=\"{}\"", attr, value));
+    }
+    let lower = value.to_ascii_lowercase();
+    let mut rest = lower.as_str();
+    while let Some(i) = rest.find("url(") {
+        rest = &rest[i + 4..];
+        if !rest.trim_start().starts_with('#') {
+            return Some(format!("{}=\"{}\"", attr, value));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    struct Arrows;
+
+    impl super::IconSet for Arrows {
+        fn name(&self) -> String {
+            "test-arrows".to_string()
+        }
+
+        fn icon(&self, name: &str) -> Option<String> {
+            match name {
+                "right" => Some(r#"<svg viewBox="0 0 24 24"><path d="M5 12h14"/></svg>"#),
+                "bad" => Some(r#"<svg onload="alert(1)"></svg>"#),
+                _ => None,
+            }
+            .map(ToString::to_string)
+        }
+    }
+
+    #[test]
+    fn icon() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                r#"
+                -- boolean active: false
+
+                -- ftd.color grey: grey
+                dark: white
+
+                -- ftd.color green: green
+                dark: green
+
+                -- ftd.icon: right
+                set: test-arrows
+                label: Next
+                color: $grey
+                color if $active: $green
+
+                -- ftd.svg:
+
+                <svg viewBox="0 0 10 10"><circle cx="5" cy="5" r="4"/></svg>
+                "#
+            ),
+            &ftd::test::Library {
+                icon_sets: vec![std::sync::Arc::new(Arrows)],
+                ..Default::default()
+            },
+        )
+        .expect("found error");
+
+        let (icon, svg) = match (
+            &doc.main.container.children[0],
+            &doc.main.container.children[1],
+        ) {
+            (ftd::Element::Svg(i), ftd::Element::Svg(s)) => (i, s),
+            t => panic!("expected svgs, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(
+            icon.svg,
+            r#"<svg viewBox="0 0 24 24"><path d="M5 12h14"/></svg>"#
+        );
+        pretty_assertions::assert_eq!(icon.icon, Some("right".to_string()));
+        assert!(!icon.common.conditional_attribute.is_empty());
+
+        let node = icon.to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, "span");
+        pretty_assertions::assert_eq!(node.text, Some(icon.svg.clone()));
+        pretty_assertions::assert_eq!(node.attrs.get("role").map(String::as_str), Some("img"));
+        pretty_assertions::assert_eq!(
+            node.attrs.get("aria-label").map(String::as_str),
+            Some("Next")
+        );
+        assert!(node.classes.contains(&"ft_icon".to_string()));
+        let node = svg.to_node("foo/bar");
+        pretty_assertions::assert_eq!(
+            node.attrs.get("aria-hidden").map(String::as_str),
+            Some("true")
+        );
+        assert!(!node.classes.contains(&"ft_icon".to_string()));
+
+        let lib = ftd::test::Library {
+            icon_sets: vec![std::sync::Arc::new(Arrows)],
+            ..Default::default()
+        };
+        for source in [
+            "-- ftd.icon: left\nset: test-arrows",
+            "-- ftd.icon: right\nset: other",
+            "-- ftd.icon: bad",
+        ] {
+            assert!(
+                ftd::p2::Document::from("foo/bar", source, &lib).is_err(),
+                "{}",
+                source
+            );
+        }
+
+        ftd::test::assert_invalid(
+            "",
+            &[
+                "-- ftd.icon: right",
+                "-- ftd.svg: <div></div>",
+                "-- ftd.svg:\n\n<svg><script>alert(1)</script></svg>",
+            ],
+        );
+    }
+
+    #[test]
+    fn check_svg() {
+        for svg in [
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none"
+                stroke="currentColor" aria-hidden="true" data-slot="icon">
+                <path stroke-linecap="round" d="M5 12h14" />
+            </svg>"#,
+            r##"<svg viewBox='0 0 10 10'><defs><linearGradient id="fade"><stop offset="0"
+                stop-color="red"/></linearGradient></defs><circle r="4" fill="url(#fade)"/>
+                <use href="#fade"/><text x="1">&lt;1 &amp; 2&gt;</text></svg>"##,
+        ] {
+            assert!(super::check_svg(svg, "foo/bar", 0).is_ok(), "{}", svg);
+        }
+
+        for svg in [
+            "<svg/onload=alert(1)></svg>",
+            "<svg onload=\"alert(1)\"></svg>",
+            "<svg><style>*{background:url(//x)}</style></svg>",
+            "<svg><a href=\"JavaScript:alert(1)\"></a></svg>",
+            "<svg><use href=\"java&Tab;script:alert(1)\"/></svg>",
+            "<svg><use href=\"java\tscript:alert(1)\"/></svg>",
+            "<svg><use href=\"java\nscript:alert(1)\"/></svg>",
+            "<svg><use href=\"&#106;avascript:alert(1)\"/></svg>",
+            "<svg><use href=\"https://example.com/x.svg#a\"/></svg>",
+            "<svg><rect fill=\"url( //example.com/x)\"/></svg>",
+            "<svg><set attributeName=\"href\" to=\"javascript:alert(1)\"/></svg>",
+            "<svg><foreignObject><img src=x onerror=alert(1)></foreignObject></svg>",
+            "<svg><!-- --><script>alert(1)</script></svg>",
+            "<svg><rect width=10 /></svg>",
+            "<svg><rect width=\"10\"height=\"10\"/></svg>",
+            "<svg><g></svg>",
+            "<svg></svg><svg></svg>",
+        ] {
+            assert!(super::check_svg(svg, "foo/bar", 0).is_err(), "{}", svg);
+        }
+    }
+}
@@ -6,33 +6,59 @@ pub(crate) mod test;
 
 mod component;
 mod condition;
+mod de;
 mod dnode;
+mod embed;
 mod event;
 mod execute_doc;
 mod html;
+mod icon;
+mod limits;
 pub mod main;
 mod or_type;
+mod outline;
 pub mod p1;
 pub mod p2;
+mod plugin;
+mod profile;
+mod query;
 pub mod render;
 mod rt;
+mod search;
+mod ser;
 mod ui;
 mod value_with_default;
 pub(crate) mod variable;
 mod youtube_id;
 
-pub use component::{ChildComponent, Component, Instruction};
+pub use component::{ChildComponent, Component, Instruction, Invocation};
 pub use condition::Condition;
+pub use de::from_str;
+pub use embed::{Embed, EmbedProvider};
 pub use event::{Action, Event};
 pub use ftd::value_with_default::ValueWithDefault;
+#[cfg(feature = "derive")]
+pub use ftd_derive::FtdRecord;
 pub use html::{anchor, color, length, overflow, Node};
+pub use icon::IconSet;
+pub use limits::{Limit, Limits};
 pub use or_type::OrType;
+pub use outline::OutlineItem;
+pub use p2::{Declarations, FtdRecord};
+pub use plugin::Plugin;
+pub use profile::{RenderProfile, Timing};
+pub use query::{Match, Selector};
 pub use rt::RT;
+pub use search::SearchRecord;
+pub use ser::{kebab_case, to_string};
 pub use ui::{
-    Anchor, AttributeType, Code, Color, ColorValue, Column, Common, ConditionalAttribute,
-    ConditionalValue, Container, Element, FontDisplay, GradientDirection, Grid, IFrame, IText,
-    Image, ImageSrc, Input, Length, Markup, Markups, NamedFont, Overflow, Position, Region, Row,
-    Scene, Spacing, Style, Text, TextAlign, TextBlock, TextFormat, Type,
+    Anchor, AttributeType, Checkbox, Code, Color, ColorValue, Column, Common, ConditionalAttribute,
+    ConditionalValue, Container, Disclosure, DisclosureKind, Element, FetchPriority, FontDisplay,
+    Form, FormEncoding, FormMethod, GradientDirection, Grid, IFrame, IText, Image, ImageFit,
+    ImageSource, ImageSrc, Input, InputType, Length, Loading, Markup, Markups, Media, NamedFont,
+    Native, Overflow, Overlay, OverlayKind, Placement, Position, Region, Row, Scene, Select,
+    Spacing, Style, Svg, Table, TableCell, TableColumn, Text, TextAlign, TextBlock, TextFormat,
+    Toc, Track, Type,
 };
 pub use variable::{PropertyValue, TextSource, Value, Variable, VariableFlags};
 
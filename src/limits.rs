@@ -0,0 +1,126 @@
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Limits {
+    /// how deep `ExecuteDoc::execute` can nest, every component call that has
+    /// children (including recursive components) adds one level
+    pub max_recursion_depth: Option<usize>,
+    pub max_elements: Option<usize>,
+    pub max_import_depth: Option<usize>,
+    pub max_html_bytes: Option<usize>,
+    /// wall-clock budget, applies separately to interpreting + executing the
+    /// document and to generating its html
+    pub time_budget: Option<std::time::Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Limit {
+    RecursionDepth(usize),
+    Elements(usize),
+    ImportDepth(usize),
+    HtmlBytes(usize),
+    Time(std::time::Duration),
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Limit::RecursionDepth(max) => write!(f, "recursion depth of {}", max),
+            Limit::Elements(max) => write!(f, "element count of {}", max),
+            Limit::ImportDepth(max) => write!(f, "import depth of {}", max),
+            Limit::HtmlBytes(max) => write!(f, "html size of {} bytes", max),
+            Limit::Time(max) => write!(f, "time budget of {:?}", max),
+        }
+    }
+}
+
+impl Limits {
+    pub(crate) fn check_import_depth(
+        &self,
+        depth: usize,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::p1::Result<()> {
+        match self.max_import_depth {
+            Some(max) if depth > max => exceeded(Limit::ImportDepth(max), doc_id, line_number),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn exceeded<T>(limit: Limit, doc_id: &str, line_number: usize) -> ftd::p1::Result<T> {
+    Err(ftd::p1::Error::LimitExceeded {
+        limit,
+        doc_id: doc_id.to_string(),
+        line_number,
+    })
+}
+
+/// how much of its `Limits` a render has used so far, passed along from the
+/// interpreter to every `ExecuteDoc` of the render
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Budget {
+    pub(crate) limits: Limits,
+    deadline: Option<std::time::Instant>,
+    depth: usize,
+    elements: usize,
+}
+
+impl Budget {
+    /// the time budget starts now
+    pub(crate) fn new(limits: &Limits) -> Budget {
+        Budget {
+            limits: limits.clone(),
+            deadline: limits.time_budget.map(|d| std::time::Instant::now() + d),
+            depth: 0,
+            elements: 0,
+        }
+    }
+
+    /// one level deeper, for the section at `line_number` of `doc_id`,
+    /// `leave()` has to be called even if the level fails
+    pub(crate) fn enter(&mut self, doc_id: &str, line_number: usize) -> ftd::p1::Result<()> {
+        if let Some(max) = self.limits.max_recursion_depth {
+            if self.depth >= max {
+                return exceeded(Limit::RecursionDepth(max), doc_id, line_number);
+            }
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    pub(crate) fn add_elements(
+        &mut self,
+        elements: usize,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::p1::Result<()> {
+        self.elements += elements;
+        if let Some(max) = self.limits.max_elements {
+            if self.elements > max {
+                return exceeded(Limit::Elements(max), doc_id, line_number);
+            }
+        }
+        self.check_time(doc_id, line_number)
+    }
+
+    pub(crate) fn check_time(&self, doc_id: &str, line_number: usize) -> ftd::p1::Result<()> {
+        match (self.limits.time_budget, self.deadline) {
+            (Some(budget), Some(deadline)) if std::time::Instant::now() >= deadline => {
+                exceeded(Limit::Time(budget), doc_id, line_number)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `html` is the html generated so far, which has no source lines, so the
+    /// error is reported at the start of `doc_id`
+    pub(crate) fn check_html(&self, html: &str, doc_id: &str) -> ftd::p1::Result<()> {
+        match self.limits.max_html_bytes {
+            Some(max) if html.len() > max => exceeded(Limit::HtmlBytes(max), doc_id, 0),
+            _ => self.check_time(doc_id, 0),
+        }
+    }
+}
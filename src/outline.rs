@@ -0,0 +1,261 @@
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub struct OutlineItem {
+    /// 0 for `h0` to 7 for `h7`
+    pub level: u8,
+    pub text: ftd::Rendered,
+    /// id to link to the heading with, `Common::id` of the heading if it has
+    /// one, else the slug of its text
+    pub slug: String,
+    pub children: Vec<OutlineItem>,
+}
+
+impl ftd::Element {
+    /// headings in `children` and their descendants, each nested under the
+    /// closest heading of a higher level before it
+    pub fn outline(children: &[ftd::Element]) -> Vec<OutlineItem> {
+        let mut headings = vec![];
+        headings_(children, &mut headings);
+
+        let mut outline = vec![];
+        let mut open: Vec<OutlineItem> = vec![];
+        for heading in headings {
+            close(&mut open, &mut outline, heading.level);
+            open.push(heading);
+        }
+        close(&mut open, &mut outline, 0);
+        outline
+    }
+}
+
+impl ftd::p2::Document {
+    /// see `ftd::Element::outline()`
+    pub fn outline(&self) -> Vec<OutlineItem> {
+        ftd::Element::outline(&self.main.container.children)
+    }
+}
+
+// all headings in document order, without children
+fn headings_(elements: &[ftd::Element], headings: &mut Vec<OutlineItem>) {
+    for e in elements.iter() {
+        if !is_shown(e) {
+            continue;
+        }
+        if let Some(heading) = heading(e) {
+            headings.push(heading);
+        }
+        if let Some(container) = e.get_container() {
+            headings_(&container.children, headings);
+            if let Some((_, _, ref external_children)) = container.external_children {
+                headings_(external_children, headings);
+            }
+        }
+    }
+}
+
+/// whether `e` is in the rendered page, elements hidden by their `if` and
+/// their children are not
+pub(crate) fn is_shown(e: &ftd::Element) -> bool {
+    e.get_common().map(|c| c.is_dummy || c.is_not_visible) == Some(false)
+}
+
+/// `e` as a heading without children, if it has a heading region
+pub(crate) fn heading(e: &ftd::Element) -> Option<OutlineItem> {
+    let common = e.get_common()?;
+    let level = common.region.as_ref().and_then(level)?;
+    let text = heading_text(e)?;
+    Some(OutlineItem {
+        level,
+        slug: common
+            .id
+            .clone()
+            .unwrap_or_else(|| slug::slugify(text.original.as_str())),
+        text,
+        children: vec![],
+    })
+}
+
+// moves the open headings of `level` or a lower level into their parents
+fn close(open: &mut Vec<OutlineItem>, outline: &mut Vec<OutlineItem>, level: u8) {
+    while open.last().map(|h| h.level >= level) == Some(true) {
+        let heading = open.pop().unwrap();
+        match open.last_mut() {
+            Some(parent) => parent.children.push(heading),
+            None => outline.push(heading),
+        }
+    }
+}
+
+fn level(region: &ftd::Region) -> Option<u8> {
+    Some(match region {
+        ftd::Region::H0 => 0,
+        ftd::Region::H1 => 1,
+        ftd::Region::H2 => 2,
+        ftd::Region::H3 => 3,
+        ftd::Region::H4 => 4,
+        ftd::Region::H5 => 5,
+        ftd::Region::H6 => 6,
+        ftd::Region::H7 => 7,
+        _ => return None,
+    })
+}
+
+/// a heading row or column is titled by the text with `region: title` in it,
+/// else by its first text with a caption
+pub(crate) fn heading_text(e: &ftd::Element) -> Option<ftd::Rendered> {
+    match e {
+        ftd::Element::Text(t) => Some(t.text.clone()),
+        ftd::Element::Markup(t) => Some(t.text.clone()),
+        ftd::Element::Column(ftd::Column { container, .. })
+        | ftd::Element::Row(ftd::Row { container, .. }) => {
+            ftd::p2::Document::find_text(&container.children, |t| {
+                if t.common.region.as_ref().map(|r| r.is_title()) == Some(true) {
+                    Some(t.text.clone())
+                } else {
+                    None
+                }
+            })
+            .or_else(|| {
+                ftd::p2::Document::find_text(&container.children, |t| {
+                    if t.line {
+                        Some(t.text.clone())
+                    } else {
+                        None
+                    }
+                })
+            })
+        }
+        _ => None,
+    }
+}
+
+/// fills in the outline of every `ftd.toc` in `elements`
+pub(crate) fn set_toc(elements: &mut [ftd::Element]) {
+    fn set_toc_(elements: &mut [ftd::Element], outline: &[OutlineItem]) {
+        for e in elements.iter_mut() {
+            if let ftd::Element::Toc(toc) = e {
+                toc.outline = up_to(outline, toc.max_level);
+            }
+            if let Some(container) = e.get_mut_container() {
+                set_toc_(&mut container.children, outline);
+                if let Some((_, _, ref mut external_children)) = container.external_children {
+                    set_toc_(external_children, outline);
+                }
+            }
+        }
+    }
+
+    let outline = ftd::Element::outline(elements);
+    set_toc_(elements, &outline);
+}
+
+// headings of `max_level` or a higher level
+fn up_to(outline: &[OutlineItem], max_level: Option<i64>) -> Vec<OutlineItem> {
+    outline
+        .iter()
+        .filter(|h| max_level.map(|m| h.level as i64 <= m).unwrap_or(true))
+        .map(|h| OutlineItem {
+            children: up_to(&h.children, max_level),
+            ..h.clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    // one line per heading, indented by nesting
+    fn lines(outline: &[super::OutlineItem], depth: usize) -> String {
+        outline
+            .iter()
+            .map(|h| {
+                format!(
+                    "{}{} {} #{}\n{}",
+                    "  ".repeat(depth),
+                    h.level,
+                    h.text.original,
+                    h.slug,
+                    lines(&h.children, depth + 1)
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn outline() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- ftd.toc:
+                max-level: 2
+
+                -- ftd.text: Introduction
+                region: h1
+
+                -- ftd.text: Install
+                region: h2
+
+                -- ftd.text: From source
+                region: h3
+
+                -- ftd.text: Usage
+                region: h1
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        pretty_assertions::assert_eq!(
+            lines(&doc.outline(), 0),
+            indoc::indoc!(
+                "
+                1 Introduction #introduction
+                  2 Install #install
+                    3 From source #from-source
+                1 Usage #usage
+                "
+            )
+        );
+
+        match &doc.main.container.children[0] {
+            ftd::Element::Toc(toc) => pretty_assertions::assert_eq!(
+                lines(&toc.outline, 0),
+                indoc::indoc!(
+                    "
+                    1 Introduction #introduction
+                      2 Install #install
+                    1 Usage #usage
+                    "
+                )
+            ),
+            e => panic!("expected toc, found: {:?}", e),
+        }
+
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("href=\"#install\""));
+        assert!(!html.contains("#from-source"));
+    }
+
+    #[test]
+    fn hidden_heading() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- boolean beta: false
+
+                -- ftd.text: Usage
+                region: h1
+
+                -- ftd.text: Beta
+                region: h1
+                if: $beta
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        pretty_assertions::assert_eq!(lines(&doc.outline(), 0), "1 Usage #usage\n");
+    }
+}
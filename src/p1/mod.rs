@@ -33,6 +33,13 @@ pub enum Error {
         line_number: usize,
     },
 
+    #[error("{doc_id}:{line_number} -> exceeded the {limit} limit")]
+    LimitExceeded {
+        limit: ftd::Limit,
+        doc_id: String,
+        line_number: usize,
+    },
+
     #[error("serde error: {source}")]
     Serde {
         #[from]
@@ -47,3 +54,23 @@ pub enum Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+impl serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::ParseError {
+            message: msg.to_string(),
+            doc_id: "".to_string(),
+            line_number: 0,
+        }
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::ParseError {
+            message: msg.to_string(),
+            doc_id: "".to_string(),
+            line_number: 0,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Compiled libraries, so an imported library does not have to be parsed and
+//! interpreted again by every document importing it.
+//!
+//! A library is compiled to its things, aliases and instructions, and stored
+//! in a `LibraryCache` under its name, along with the version of this crate and
+//! a hash of its source and of the hashes of the libraries it imports. When the
+//! library is imported again, its imports are loaded first, and the compiled
+//! library is used only if the hash and the version still match, so a change
+//! in the library or in anything it imports, directly or not, compiles it
+//! again. A library using a `$processor$` is not cached, and neither is any
+//! library importing it, as the processor may return something else next time.
+
+pub trait LibraryCache: Send + Sync {
+    fn get(&self, library: &str) -> Option<Vec<u8>>;
+    fn set(&self, library: &str, compiled: Vec<u8>);
+}
+
+/// A `LibraryCache` that keeps the compiled libraries for the life of the
+/// process.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    compiled: std::sync::RwLock<std::collections::BTreeMap<String, Vec<u8>>>,
+}
+
+impl LibraryCache for MemoryCache {
+    fn get(&self, library: &str) -> Option<Vec<u8>> {
+        self.compiled.read().ok()?.get(library).cloned()
+    }
+
+    fn set(&self, library: &str, compiled: Vec<u8>) {
+        if let Ok(mut c) = self.compiled.write() {
+            c.insert(library.to_string(), compiled);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Compiled {
+    pub ftd: String,
+    /// `HASHER` when the library was compiled
+    pub hasher: String,
+    pub hash: u64,
+    /// caption and line number of each `-- import:` in the library
+    pub imports: Vec<(Option<String>, usize)>,
+    pub aliases: std::collections::BTreeMap<String, String>,
+    /// things the library added to, or changed in, the bag
+    pub things: std::collections::BTreeMap<String, ftd::p2::Thing>,
+    pub instructions: Vec<ftd::Instruction>,
+}
+
+impl Compiled {
+    pub fn encode(&self) -> Option<Vec<u8>> {
+        serde_cbor::to_vec(self).ok()
+    }
+
+    /// `None` if `compiled` is not a library compiled by this version of ftd,
+    /// with this hash
+    pub fn decode(compiled: &[u8]) -> Option<Compiled> {
+        serde_cbor::from_slice::<Compiled>(compiled)
+            .ok()
+            .filter(|c| c.ftd == env!("CARGO_PKG_VERSION") && c.hasher == HASHER)
+    }
+}
+
+/// name of the algorithm of `hash()`, stored with every compiled library, so
+/// that a library hashed in some other way is compiled again
+pub(crate) const HASHER: &str = "fnv-1a-64";
+
+/// hash of a library with `source`, importing libraries with `imports` hashes
+///
+/// The hash is stored, so it is computed here, and not with `DefaultHasher`,
+/// whose algorithm may change between releases of rust. It is 64-bit FNV-1a
+/// (offset basis 0xcbf29ce484222325, prime 0x100000001b3) over the length of
+/// the source and the hash of each import, as little endian `u64`s, with the
+/// bytes of the source in between.
+pub(crate) fn hash(source: &str, imports: &[u64]) -> u64 {
+    fn write(hash: u64, bytes: &[u8]) -> u64 {
+        bytes.iter().fold(hash, |h, b| {
+            (h ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    let mut hash = write(0xcbf2_9ce4_8422_2325, &(source.len() as u64).to_le_bytes());
+    hash = write(hash, source.as_bytes());
+    for import in imports {
+        hash = write(hash, &import.to_le_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    fn set_title(compiled: &mut super::Compiled, text: &str) {
+        match compiled.things.get_mut("ui#title") {
+            Some(ftd::p2::Thing::Variable(v)) => {
+                v.value = ftd::PropertyValue::Value {
+                    value: ftd::Value::String {
+                        text: text.to_string(),
+                        source: ftd::TextSource::Header,
+                    },
+                }
+            }
+            t => panic!("expected variable, found: {:?}", t),
+        }
+    }
+
+    #[test]
+    fn cache() {
+        use super::LibraryCache;
+
+        let lib: ftd::test::Library = Default::default();
+        lib.set("base", "-- string greeting: Hello");
+        lib.set("ui", "-- import: base\n\n-- string title: $base.greeting");
+        let source = "-- import: ui\n\n-- ftd.text: $ui.title";
+        let title = |cache: &super::MemoryCache| {
+            let options = ftd::p2::Options {
+                cache: Some(cache),
+                ..Default::default()
+            };
+            ftd::p2::Document::from_with_options("foo/bar", source, &lib, options)
+                .unwrap()
+                .get::<String>("ui#title")
+                .unwrap()
+        };
+
+        let cache: super::MemoryCache = Default::default();
+        pretty_assertions::assert_eq!(title(&cache), "Hello");
+        assert!(cache.get("base").is_some());
+
+        // a compiled library is used instead of interpreting the source
+        let mut ui = super::Compiled::decode(&cache.get("ui").unwrap()).unwrap();
+        pretty_assertions::assert_eq!(ui.imports, vec![(Some("base".to_string()), 1)]);
+        set_title(&mut ui, "Cached");
+        cache.set("ui", ui.encode().unwrap());
+        pretty_assertions::assert_eq!(title(&cache), "Cached");
+
+        // a change in an imported library compiles the library again
+        lib.set("base", "-- string greeting: Hi");
+        pretty_assertions::assert_eq!(title(&cache), "Hi");
+
+        // and so does a library compiled by another version of ftd
+        let mut ui = super::Compiled::decode(&cache.get("ui").unwrap()).unwrap();
+        set_title(&mut ui, "Cached");
+        ui.ftd = "0.0.0".to_string();
+        cache.set("ui", ui.encode().unwrap());
+        pretty_assertions::assert_eq!(title(&cache), "Hi");
+    }
+
+    #[test]
+    fn processor() {
+        use super::LibraryCache;
+
+        let lib: ftd::test::Library = Default::default();
+        lib.set("base", "-- string greeting: Hello");
+        lib.set(
+            "version",
+            "-- string version:\n$processor$: read_version_from_cargo_toml",
+        );
+        lib.set(
+            "about",
+            "-- import: version\n\n-- string about: $version.version",
+        );
+        let source = "-- import: base\n\n-- import: about\n\n-- ftd.text: $about.about";
+        let cache: super::MemoryCache = Default::default();
+        let options = ftd::p2::Options {
+            cache: Some(&cache),
+            ..Default::default()
+        };
+        ftd::p2::Document::from_with_options("foo/bar", source, &lib, options).unwrap();
+
+        // a library using a processor is not cached, nor is a library importing it
+        assert!(cache.get("base").is_some());
+        assert!(cache.get("version").is_none());
+        assert!(cache.get("about").is_none());
+    }
+}
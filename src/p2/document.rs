@@ -1,4 +1,5 @@
 #[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Document {
     pub data: std::collections::BTreeMap<String, ftd::p2::Thing>,
     pub name: String,
@@ -8,6 +9,63 @@ pub struct Document {
     pub aliases: std::collections::BTreeMap<String, String>,
 }
 
+/// what `Document::from_with_options` applies to the render, everything is
+/// off by default
+#[derive(Default)]
+pub struct Options<'a> {
+    /// filled with where the time of the render went
+    pub profile: Option<&'a mut ftd::RenderProfile>,
+    pub limits: Option<ftd::Limits>,
+    /// versions of the imported packages, updated with what got resolved
+    pub lock: Option<&'a mut ftd::p2::Lock>,
+    /// imported libraries are loaded from here when they have not changed
+    /// since they were stored in it, see `ftd::p2::LibraryCache`
+    pub cache: Option<&'a dyn ftd::p2::LibraryCache>,
+}
+
+impl<'a> Options<'a> {
+    fn interpreter<'b>(
+        &self,
+        lib: &'b dyn ftd::p2::Library,
+    ) -> ftd::p2::interpreter::Interpreter<'b>
+    where
+        'a: 'b,
+    {
+        let mut interpreter = ftd::p2::interpreter::Interpreter::new(lib);
+        if let Some(limits) = self.limits.as_ref() {
+            interpreter.budget = ftd::limits::Budget::new(limits);
+        }
+        if let Some(lock) = self.lock.as_ref() {
+            interpreter.lock = lock.for_document();
+        }
+        interpreter.cache = self.cache;
+        interpreter
+    }
+
+    fn render(
+        self,
+        name: &str,
+        interpreter: ftd::p2::interpreter::Interpreter,
+        instructions: Vec<ftd::Instruction>,
+    ) -> ftd::p1::Result<Document> {
+        if let Some(lock) = self.lock {
+            *lock = interpreter.lock.pruned();
+        }
+        let budget = interpreter.budget.clone();
+        let plugins = interpreter.plugins.clone();
+        let embed_providers = interpreter.embed_providers.clone();
+        let icon_sets = interpreter.icon_sets.clone();
+        let (d, interpreted) = Document::from_interpreter(name, interpreter, instructions);
+        match self.profile {
+            Some(profile) => {
+                *profile = interpreted;
+                d.render(Some(profile), budget, plugins, embed_providers, icon_sets)
+            }
+            None => d.render(None, budget, plugins, embed_providers, icon_sets),
+        }
+    }
+}
+
 impl ToString for Document {
     fn to_string(&self) -> String {
         ftd::p1::to_string(&self.p1)
@@ -169,22 +227,71 @@ impl Document {
     }
 
     pub fn to_rt(&self, id: &str, doc_id: &str) -> ftd::Document {
-        let external_children =
-            ftd::Element::get_external_children_dependencies(&self.main.container.children);
-
-        ftd::Document {
-            data: self.rt_data(),
-            html: self.html(id, doc_id),
-            external_children,
-            body_events: self.body_events(id),
-        }
+        self.to_rt_with_profile(id, doc_id, None)
     }
 
     pub fn body_events(&self, id: &str) -> String {
         let mut events = vec![];
         body_events_(self.main.container.children.as_slice(), &mut events, id);
+        let mut overlays = vec![];
+        overlays_(self.main.container.children.as_slice(), &mut overlays, id);
+
+        return format!(
+            "{}{}",
+            events_to_string(events),
+            overlays_to_string(overlays, id)
+        );
 
-        return events_to_string(events);
+        // the keyboard, focus and click handling of the overlays is in ftd.js,
+        // which is loaded after the body events
+        fn overlays_to_string(overlays: Vec<serde_json::Value>, id: &str) -> String {
+            if overlays.is_empty() {
+                return "".to_string();
+            }
+            format!(
+                "document.addEventListener(\"DOMContentLoaded\", function() {{\n    \
+                window.ftd.overlays(\"{}\", {});\n}});",
+                id,
+                serde_json::to_string(&overlays)
+                    .unwrap()
+                    .replace("</", "<\\/")
+            )
+        }
+
+        fn overlays_(children: &[ftd::Element], overlays: &mut Vec<serde_json::Value>, id: &str) {
+            for child in children {
+                let (common, container) = match child {
+                    ftd::Element::Column(ftd::Column {
+                        common, container, ..
+                    })
+                    | ftd::Element::Row(ftd::Row {
+                        common, container, ..
+                    })
+                    | ftd::Element::Scene(ftd::Scene {
+                        common, container, ..
+                    })
+                    | ftd::Element::Grid(ftd::Grid {
+                        common, container, ..
+                    }) => (common, container),
+                    _ => continue,
+                };
+                overlays_(&container.children, overlays, id);
+                if let Some((_, _, external_children)) = &container.external_children {
+                    overlays_(external_children, overlays, id);
+                }
+                // `data_id` is unique once `ftd::Element::set_id` ran, with or without an `id`
+                if let (Some(overlay), Some(data_id)) = (&common.overlay, &common.data_id) {
+                    overlays.push(serde_json::json!({
+                        "data_id": format!("{}:{}", data_id, id),
+                        "kind": overlay.kind.to_str(),
+                        "open": overlay.open,
+                        "anchor": overlay.anchor,
+                        "placement": overlay.placement.to_str(),
+                        "dismissible": overlay.dismissible,
+                    }));
+                }
+            }
+        }
 
         fn events_to_string(events: Vec<(String, String)>) -> String {
             if events.is_empty() {
@@ -245,6 +352,16 @@ impl Document {
                     | ftd::Element::Code(ftd::Code { common, .. })
                     | ftd::Element::IFrame(ftd::IFrame { common, .. })
                     | ftd::Element::Input(ftd::Input { common, .. })
+                    | ftd::Element::Table(ftd::Table { common, .. })
+                    | ftd::Element::Svg(ftd::Svg { common, .. })
+                    | ftd::Element::Audio(ftd::Media { common, .. })
+                    | ftd::Element::Video(ftd::Media { common, .. })
+                    | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+                    | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+                    | ftd::Element::Radio(ftd::Select { common, .. })
+                    | ftd::Element::Select(ftd::Select { common, .. })
+                    | ftd::Element::Toc(ftd::Toc { common, .. })
+                    | ftd::Element::Native(ftd::Native { common, .. })
                     | ftd::Element::Integer(ftd::Text { common, .. })
                     | ftd::Element::Boolean(ftd::Text { common, .. })
                     | ftd::Element::Decimal(ftd::Text { common, .. }) => {
@@ -303,7 +420,72 @@ impl Document {
         }
     }
 
+    pub fn to_rt_with_profile(
+        &self,
+        id: &str,
+        doc_id: &str,
+        profile: Option<&mut ftd::RenderProfile>,
+    ) -> ftd::Document {
+        let start = std::time::Instant::now();
+        let node = self.html_node(doc_id);
+        let data = self.rt_data();
+
+        let document = ftd::Document {
+            html: node.to_html(&Default::default(), &data, id),
+            data,
+            external_children: ftd::Element::get_external_children_dependencies(
+                &self.main.container.children,
+            ),
+            body_events: self.body_events(id),
+        };
+        if let Some(profile) = profile {
+            profile.nodes = ftd::RenderProfile::count_nodes(&node);
+            profile.dependencies = ftd::RenderProfile::count_dependencies(&document.data);
+            profile.html = start.elapsed();
+        }
+        document
+    }
+
+    pub fn to_rt_with_limits(
+        &self,
+        id: &str,
+        doc_id: &str,
+        limits: &ftd::Limits,
+    ) -> ftd::p1::Result<ftd::Document> {
+        let budget = ftd::limits::Budget::new(limits);
+        let node = self.html_node(doc_id);
+        budget.check_time(doc_id, 0)?;
+        let data = self.rt_data();
+        budget.check_time(doc_id, 0)?;
+
+        let mut html = String::new();
+        node.to_dnode(
+            &Default::default(),
+            &data,
+            &mut None,
+            &None,
+            &[],
+            true,
+            id,
+            false,
+        )
+        .write_html(id, &mut html, &mut |html| budget.check_html(html, doc_id))?;
+        Ok(ftd::Document {
+            html,
+            data,
+            external_children: ftd::Element::get_external_children_dependencies(
+                &self.main.container.children,
+            ),
+            body_events: self.body_events(id),
+        })
+    }
+
     pub fn html(&self, id: &str, doc_id: &str) -> String {
+        self.html_node(doc_id)
+            .to_html(&Default::default(), &self.rt_data(), id)
+    }
+
+    fn html_node(&self, doc_id: &str) -> ftd::Node {
         let mut node = self.main.to_node(doc_id);
         node.children = {
             let mut children = vec![];
@@ -328,7 +510,7 @@ impl Document {
             }
             children
         };
-        node.to_html(&Default::default(), &self.rt_data(), id)
+        node
     }
 
     pub fn alias(&self, doc: &str) -> Option<&str> {
@@ -355,6 +537,16 @@ impl Document {
                     | ftd::Element::TextBlock(_)
                     | ftd::Element::Code(_)
                     | ftd::Element::Input(_)
+                    | ftd::Element::Table(_)
+                    | ftd::Element::Svg(_)
+                    | ftd::Element::Audio(_)
+                    | ftd::Element::Video(_)
+                    | ftd::Element::Checkbox(_)
+                    | ftd::Element::Toggle(_)
+                    | ftd::Element::Radio(_)
+                    | ftd::Element::Select(_)
+                    | ftd::Element::Toc(_)
+                    | ftd::Element::Native(_)
                     | ftd::Element::Image(_)
                     | ftd::Element::Markup(_)
                     | ftd::Element::IFrame(_)
@@ -412,31 +604,98 @@ impl Document {
     ) -> ftd::p1::Result<Document> {
         let mut interpreter = ftd::p2::interpreter::Interpreter::new(lib);
         let instructions = interpreter.interpret(name, source)?;
+        Ok(Self::from_interpreter(name, interpreter, instructions).0)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn without_render_async(
+        name: &str,
+        source: &str,
+        lib: &dyn ftd::p2::Library,
+    ) -> ftd::p1::Result<Document> {
+        let mut interpreter = ftd::p2::interpreter::Interpreter::new(lib);
+        let instructions = interpreter.async_interpret(name, source).await?;
+        Ok(Self::from_interpreter(name, interpreter, instructions).0)
+    }
+
+    fn from_interpreter(
+        name: &str,
+        interpreter: ftd::p2::interpreter::Interpreter,
+        instructions: Vec<ftd::Instruction>,
+    ) -> (Document, ftd::RenderProfile) {
         let rt = ftd::RT::from(name, interpreter.aliases, interpreter.bag, instructions);
 
-        Ok(Document {
-            main: Default::default(),
-            data: rt.bag,
-            instructions: rt.instructions,
-            p1: interpreter.p1,
-            aliases: rt.aliases,
-            name: name.to_string(),
-        })
+        (
+            Document {
+                main: Default::default(),
+                data: rt.bag,
+                instructions: rt.instructions,
+                p1: interpreter.p1,
+                aliases: rt.aliases,
+                name: name.to_string(),
+            },
+            interpreter.profile,
+        )
     }
 
     pub fn from(name: &str, source: &str, lib: &dyn ftd::p2::Library) -> ftd::p1::Result<Document> {
-        let mut d = Self::without_render(name, source, lib)?;
+        Self::from_with_options(name, source, lib, Default::default())
+    }
 
+    #[cfg(feature = "async")]
+    pub async fn from_async(
+        name: &str,
+        source: &str,
+        lib: &dyn ftd::p2::Library,
+    ) -> ftd::p1::Result<Document> {
+        Self::from_with_options_async(name, source, lib, Default::default()).await
+    }
+
+    /// same as `from()`, with everything in `options` applied to the render
+    pub fn from_with_options(
+        name: &str,
+        source: &str,
+        lib: &dyn ftd::p2::Library,
+        options: Options,
+    ) -> ftd::p1::Result<Document> {
+        let mut interpreter = options.interpreter(lib);
+        let instructions = interpreter.interpret(name, source)?;
+        options.render(name, interpreter, instructions)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn from_with_options_async(
+        name: &str,
+        source: &str,
+        lib: &dyn ftd::p2::Library,
+        options: Options<'_>,
+    ) -> ftd::p1::Result<Document> {
+        let mut interpreter = options.interpreter(lib);
+        let instructions = interpreter.async_interpret(name, source).await?;
+        options.render(name, interpreter, instructions)
+    }
+
+    fn render(
+        mut self,
+        profile: Option<&mut ftd::RenderProfile>,
+        budget: ftd::limits::Budget,
+        plugins: ftd::plugin::Plugins,
+        embed_providers: ftd::embed::EmbedProviders,
+        icon_sets: ftd::icon::IconSets,
+    ) -> ftd::p1::Result<Document> {
         let mut rt = ftd::RT::from(
-            d.name.as_str(),
-            d.aliases.clone(),
-            d.data.clone(),
-            d.instructions.clone(),
+            self.name.as_str(),
+            self.aliases.clone(),
+            self.data.clone(),
+            self.instructions.clone(),
         );
+        rt.plugins = plugins;
+        rt.embed_providers = embed_providers;
+        rt.icon_sets = icon_sets;
 
-        d.main = rt.render()?;
-        d.data.extend(rt.bag);
-        Ok(d)
+        self.main = rt.render_with(profile, budget)?;
+        self.data.extend(rt.bag);
+        Ok(self)
     }
 
     pub fn get_heading<F>(children: &[ftd::Element], f: &F) -> Option<ftd::Rendered>
@@ -452,40 +711,15 @@ impl Document {
         }) {
             return Some(t);
         }
-        if let Some(t) = Self::find(children, &|e| match e {
-            ftd::Element::Column(t) => {
-                if t.common.region.as_ref().map(f).unwrap_or(false) {
-                    Some(t.container.children.clone())
-                } else {
-                    None
-                }
-            }
-            ftd::Element::Row(t) => {
-                if t.common.region.as_ref().map(f).unwrap_or(false) {
-                    Some(t.container.children.clone())
-                } else {
-                    None
-                }
+        Self::find(children, &|e| match e {
+            ftd::Element::Column(ftd::Column { common, .. })
+            | ftd::Element::Row(ftd::Row { common, .. })
+                if common.region.as_ref().map(f).unwrap_or(false) =>
+            {
+                ftd::outline::heading_text(e)
             }
             _ => None,
-        }) {
-            if let Some(t) = Self::find_text(&t, |t| {
-                if t.common
-                    .region
-                    .as_ref()
-                    .map(|r| r.is_title())
-                    .unwrap_or(false)
-                {
-                    Some(t.text.clone())
-                } else {
-                    None
-                }
-            }) {
-                return Some(t);
-            };
-            return Self::find_text(&t, |t| if t.line { Some(t.text.clone()) } else { None });
-        }
-        None
+        })
     }
 
     pub fn title(&self) -> Option<ftd::Rendered> {
@@ -580,29 +814,74 @@ impl Document {
         Ok(serde_json::from_value(json)?)
     }
 
-    #[cfg(calls)]
+    /// instances of the record `T` was derived from, in whichever module it
+    /// was declared
+    pub fn typed_instances<T>(&self) -> ftd::p1::Result<Vec<T>>
+    where
+        T: ftd::FtdRecord + serde::de::DeserializeOwned,
+    {
+        let suffix = format!("#{}", T::ftd_name());
+        let names = self
+            .data
+            .iter()
+            .filter(|(k, v)| k.ends_with(suffix.as_str()) && matches!(v, ftd::p2::Thing::Record(_)))
+            .map(|(k, _)| k.to_string())
+            .collect::<Vec<_>>();
+        match names.as_slice() {
+            [] => Ok(vec![]),
+            [name] => self.instances(name),
+            _ => ftd::e2(
+                format!(
+                    "{} is declared in more than one module: {:?}",
+                    T::ftd_name(),
+                    names
+                ),
+                self.name.as_str(),
+                0,
+            ),
+        }
+    }
+
+    /// arguments of every call of `component`, in the order they were made
     pub fn calls<T: serde::de::DeserializeOwned>(
         &self,
         component: &str,
     ) -> ftd::p1::Result<Vec<T>> {
-        let component = self.name(component);
-        let thing = match self.data.get(component.as_str()) {
-            Some(t) => t,
-            None => return Ok(vec![]),
-        };
+        Ok(self
+            .invocations(component)?
+            .into_iter()
+            .map(|i| i.arguments)
+            .collect())
+    }
 
-        let json = match thing {
-            ftd::p2::Thing::Component(c) => {
-                let mut a = vec![];
-                for c in c.invocations.iter() {
-                    a.push(self.object2_to_json(c)?);
-                }
-                serde_json::Value::Array(a)
+    /// every call of `component`, with the document and line it was made on
+    pub fn invocations<T: serde::de::DeserializeOwned>(
+        &self,
+        component: &str,
+    ) -> ftd::p1::Result<Vec<ftd::Invocation<T>>> {
+        let component = self.name(component);
+        let invocations = match self.data.get(component.as_str()) {
+            Some(ftd::p2::Thing::Component(c)) => &c.invocations,
+            Some(_) => {
+                return ftd::e2(
+                    format!("{} is not a component", component),
+                    self.name.as_str(),
+                    0,
+                )
             }
-            t => panic!("{:?} is not a component", t),
+            None => return Ok(vec![]),
         };
 
-        Ok(serde_json::from_value(json)?)
+        invocations
+            .iter()
+            .map(|i| {
+                Ok(ftd::Invocation {
+                    doc_id: i.doc_id.clone(),
+                    line_number: i.line_number,
+                    arguments: serde_json::from_value(self.object2_to_json(&i.arguments)?)?,
+                })
+            })
+            .collect()
     }
 
     pub fn json(&self, key: &str) -> ftd::p1::Result<serde_json::Value> {
@@ -630,7 +909,7 @@ impl Document {
         }
     }
 
-    fn value_to_json(&self, v: &ftd::Value) -> ftd::p1::Result<serde_json::Value> {
+    pub(crate) fn value_to_json(&self, v: &ftd::Value) -> ftd::p1::Result<serde_json::Value> {
         let doc = ftd::p2::TDoc {
             name: self.name.as_str(),
             aliases: &self.aliases,
@@ -680,7 +959,6 @@ impl Document {
         Ok(serde_json::Value::Array(list))
     }
 
-    #[cfg(calls)]
     fn object2_to_json(
         &self,
         fields: &std::collections::BTreeMap<String, ftd::Value>,
@@ -820,6 +1098,330 @@ mod test {
         );
     }
 
+    #[cfg(feature = "async")]
+    #[test]
+    fn from_async() {
+        let source = indoc::indoc!(
+            "
+            -- import: fifthtry/ft
+            -- import: creating-a-tree as ft2
+
+            -- ft.toc:
+
+            foo is the toc
+            "
+        );
+
+        fn assert_send<T: Send>(t: T) -> T {
+            t
+        }
+
+        let bag = futures::executor::block_on(assert_send(super::Document::from_async(
+            "foo/bar",
+            source,
+            &ftd::p2::TestLibrary {},
+        )))
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            bag.get::<String>("fifthtry/ft#toc").unwrap(),
+            "foo is the toc"
+        );
+        pretty_assertions::assert_eq!(
+            bag.main,
+            super::Document::from("foo/bar", source, &ftd::p2::TestLibrary {})
+                .unwrap()
+                .main
+        );
+    }
+
+    #[test]
+    fn profile() {
+        let mut profile = Default::default();
+        let doc = super::Document::from_with_options(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- import: fifthtry/ft
+
+                -- string version:
+                $processor$: read_version_from_cargo_toml
+
+                -- ftd.text: $version
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+            super::Options {
+                profile: Some(&mut profile),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            profile
+                .imports
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fifthtry/ft"]
+        );
+        pretty_assertions::assert_eq!(profile.processors.len(), 1);
+        pretty_assertions::assert_eq!(profile.processors[0].line_number, 3);
+        pretty_assertions::assert_eq!(profile.components.len(), 1);
+        pretty_assertions::assert_eq!(profile.components[0].name, "ftd#text");
+
+        let rt = doc.to_rt_with_profile("main", "foo/bar", Some(&mut profile));
+        pretty_assertions::assert_eq!(rt, doc.to_rt("main", "foo/bar"));
+        assert!(profile.nodes > 1);
+    }
+
+    #[test]
+    fn profile_nested() {
+        let mut profile: ftd::RenderProfile = Default::default();
+        super::Document::from_with_options(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- ftd.column:
+
+                --- ftd.text: hello
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+            super::Options {
+                profile: Some(&mut profile),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // the text is expanded by the column, and timed on its own too
+        pretty_assertions::assert_eq!(
+            profile
+                .components
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ftd#text", "ftd#column"]
+        );
+
+        // but only once in the total
+        let (text, column) = (&profile.components[0], &profile.components[1]);
+        pretty_assertions::assert_eq!(text.self_duration, text.duration);
+        pretty_assertions::assert_eq!(column.self_duration, column.duration - text.duration);
+        pretty_assertions::assert_eq!(profile.total_components(), column.duration);
+    }
+
+    #[test]
+    fn limits() {
+        let source = indoc::indoc!(
+            "
+            -- import: fifthtry/ft
+
+            -- ftd.column:
+
+            -- ftd.text: hello
+
+            -- ftd.text: world
+            "
+        );
+        let lib = ftd::p2::TestLibrary {};
+
+        let limit = |limits: ftd::Limits| {
+            let options = super::Options {
+                limits: Some(limits.clone()),
+                ..Default::default()
+            };
+            match super::Document::from_with_options("foo/bar", source, &lib, options)
+                .and_then(|d| d.to_rt_with_limits("main", "foo/bar", &limits))
+            {
+                Err(ftd::p1::Error::LimitExceeded { limit, .. }) => Some(limit),
+                Err(e) => panic!("unexpected error: {:?}", e),
+                Ok(_) => None,
+            }
+        };
+
+        pretty_assertions::assert_eq!(limit(Default::default()), None);
+        pretty_assertions::assert_eq!(
+            limit(ftd::Limits {
+                max_import_depth: Some(0),
+                ..Default::default()
+            }),
+            Some(ftd::Limit::ImportDepth(0))
+        );
+        pretty_assertions::assert_eq!(
+            limit(ftd::Limits {
+                max_elements: Some(2),
+                ..Default::default()
+            }),
+            Some(ftd::Limit::Elements(2))
+        );
+        pretty_assertions::assert_eq!(
+            limit(ftd::Limits {
+                max_elements: Some(3),
+                ..Default::default()
+            }),
+            None
+        );
+        pretty_assertions::assert_eq!(
+            limit(ftd::Limits {
+                max_html_bytes: Some(10),
+                ..Default::default()
+            }),
+            Some(ftd::Limit::HtmlBytes(10))
+        );
+        pretty_assertions::assert_eq!(
+            limit(ftd::Limits {
+                time_budget: Some(std::time::Duration::from_secs(0)),
+                ..Default::default()
+            }),
+            Some(ftd::Limit::Time(std::time::Duration::from_secs(0)))
+        );
+    }
+
+    #[test]
+    fn recursion_limit() {
+        let source = indoc::indoc!(
+            "
+            -- record toc-record:
+            string title:
+            toc-record list children:
+
+            -- ftd.column toc-item:
+            toc-record toc:
+
+            --- ftd.text: $toc.title
+
+            --- toc-item:
+            $loop$: $toc.children as $obj
+            toc: $obj
+
+            -- toc-record list aa:
+
+            -- aa:
+            title: aa title
+
+            -- toc-record list toc:
+
+            -- toc:
+            title: ab title
+            children: $aa
+
+            -- toc-item:
+            $loop$: $toc as $obj
+            toc: $obj
+            "
+        );
+        let options = |max| super::Options {
+            limits: Some(ftd::Limits {
+                max_recursion_depth: Some(max),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(super::Document::from_with_options(
+            "foo/bar",
+            source,
+            &ftd::p2::TestLibrary {},
+            options(16)
+        )
+        .is_ok());
+
+        match super::Document::from_with_options(
+            "foo/bar",
+            source,
+            &ftd::p2::TestLibrary {},
+            options(2),
+        ) {
+            Err(ftd::p1::Error::LimitExceeded {
+                limit, line_number, ..
+            }) => {
+                pretty_assertions::assert_eq!(limit, ftd::Limit::RecursionDepth(2));
+                // the toc-item that nests too deep
+                pretty_assertions::assert_eq!(line_number, 5);
+            }
+            t => panic!("expected recursion limit error, found: {:?}", t),
+        }
+    }
+
+    #[test]
+    fn versioned_import() {
+        let lib = ftd::p2::TestLibrary {};
+        let mut lock: ftd::p2::Lock = Default::default();
+
+        let doc = super::Document::from_with_options(
+            "foo/bar",
+            "-- import: fifthtry/ui@1.2",
+            &lib,
+            super::Options {
+                lock: Some(&mut lock),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        pretty_assertions::assert_eq!(doc.get::<String>("fifthtry/ui#version").unwrap(), "1.3.1");
+        pretty_assertions::assert_eq!(
+            lock.to_string(),
+            "-- package: fifthtry/ui\nversion: 1.3.1\n"
+        );
+
+        // the locked version is used as long as it still matches
+        let mut lock = ftd::p2::Lock::parse("-- package: fifthtry/ui\nversion: 1.2.0\n").unwrap();
+        let doc = super::Document::from_with_options(
+            "foo/bar",
+            "-- import: fifthtry/ui@1.2",
+            &lib,
+            super::Options {
+                lock: Some(&mut lock),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        pretty_assertions::assert_eq!(doc.get::<String>("fifthtry/ui#version").unwrap(), "1.2.0");
+
+        let err = super::Document::from_with_options(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- import: fifthtry/ui@1.2
+                -- import: uses-ui-2
+                "
+            ),
+            &lib,
+            super::Options {
+                lock: Some(&mut Default::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("conflicting versions of fifthtry/ui"),
+            "{}",
+            err
+        );
+
+        let err = super::Document::from_with_options(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- import: fifthtry/ui@1.2
+                -- import: fifthtry/ui
+                "
+            ),
+            &lib,
+            super::Options {
+                lock: Some(&mut Default::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("imported without a version"), "{}", err);
+    }
+
     #[test]
     fn meta() {
         #[derive(Debug, PartialEq, serde::Deserialize)]
@@ -877,8 +1479,6 @@ mod test {
     }
 
     #[test]
-    #[cfg(calls)]
-    #[ignore] // TODO: this is buggy
     fn calls() {
         #[derive(Debug, PartialEq, serde::Deserialize)]
         struct PR {
@@ -886,20 +1486,21 @@ mod test {
             title: String,
         }
 
-        let bag = super::Document::from(
+        let mut bag = super::Document::from(
             "foo/bar",
             indoc::indoc!(
                 "
-                -- component pr:
-                $number: integer
-                $title: caption
-                component: ftd.text
-                text: ref $title
+                -- ftd.text pr:
+                caption title:
+                integer number:
+                text: $title
 
                 -- pr: some pr
                 number: 24
 
-                -- pr: some other pr
+                -- ftd.column:
+
+                --- pr: some other pr
                 number: 224
                 "
             ),
@@ -907,19 +1508,62 @@ mod test {
         )
         .unwrap();
 
+        let calls = vec![
+            PR {
+                number: 24,
+                title: s("some pr"),
+            },
+            PR {
+                number: 224,
+                title: s("some other pr"),
+            },
+        ];
+        pretty_assertions::assert_eq!(bag.calls::<PR>("pr").unwrap(), calls);
         pretty_assertions::assert_eq!(
-            bag.instances::<PR>("pr").unwrap(),
-            vec![
-                PR {
-                    number: 24,
-                    title: s("some pr")
-                },
-                PR {
-                    number: 224,
-                    title: s("some other pr")
-                }
-            ]
+            bag.invocations::<PR>("pr")
+                .unwrap()
+                .iter()
+                .map(|i| i.line_number)
+                .collect::<Vec<_>>(),
+            vec![6, 11]
+        );
+
+        bag.rerender("main", "foo/bar").unwrap();
+        pretty_assertions::assert_eq!(bag.calls::<PR>("pr").unwrap(), calls);
+        assert!(bag.calls::<PR>("ftd#text").unwrap().is_empty());
+    }
+
+    #[test]
+    fn invocation_doc_id() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Empty {}
+
+        let bag = super::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- import: creating-a-tree
+
+                -- creating-a-tree.ft_toc:
+                "
+            ),
+            &ftd::p2::TestLibrary {},
         )
+        .unwrap();
+
+        let at = |component: &str| {
+            bag.invocations::<Empty>(component)
+                .unwrap()
+                .into_iter()
+                .map(|i| (i.doc_id, i.line_number))
+                .collect::<Vec<_>>()
+        };
+        pretty_assertions::assert_eq!(at("creating-a-tree#ft_toc"), vec![(s("foo/bar"), 3)]);
+        // the children of `ft_toc` are called where `ft_toc` is defined
+        pretty_assertions::assert_eq!(
+            at("creating-a-tree#table-of-content"),
+            vec![(s("creating-a-tree"), 40)]
+        );
     }
 
     #[test]
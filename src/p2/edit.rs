@@ -0,0 +1,472 @@
+//! Changing variables and adding record instances, both in `Document::data`
+//! and in the source sections in `Document::p1`, so `Document::to_string()`
+//! gives back the edited source. Comments, and everything not being changed,
+//! are left as they are. The rendered `Document::main` is not updated.
+
+impl ftd::p2::Document {
+    /// Sets the variable `key`, declared in this document, to `value`.
+    ///
+    /// The value is written to the declaration of the variable, and the
+    /// sections updating the variable later on (for lists, the sections adding
+    /// items) are replaced by it. A `$processor$` on the declaration is
+    /// removed too.
+    pub fn set<T>(&mut self, key: &str, value: &T) -> ftd::p1::Result<()>
+    where
+        T: serde::Serialize,
+    {
+        let name = self.name(key);
+        let kind = match self.data.get(name.as_str()) {
+            Some(ftd::p2::Thing::Variable(v)) => v.value.kind(),
+            Some(t) => return ftd::e2(format!("not a variable: {:?}", t), self.name.as_str(), 0),
+            None => return ftd::e2(format!("{} not found", key), self.name.as_str(), 0),
+        };
+        let value = self.value_from_json(value, kind.clone())?;
+
+        let declaration = {
+            let doc = ftd::p2::TDoc {
+                name: self.name.as_str(),
+                aliases: &self.aliases,
+                bag: &self.data,
+                local_variables: &mut Default::default(),
+            };
+            self.p1
+                .iter()
+                .position(|s| is_declaration(s, key, &kind, &doc))
+        };
+        let declaration = match declaration {
+            Some(i) => i,
+            None => {
+                return ftd::e2(
+                    format!("{} is not declared in this document", key),
+                    self.name.as_str(),
+                    0,
+                )
+            }
+        };
+        let mut i = declaration + 1;
+        while i < self.p1.len() {
+            if !self.p1[i].is_commented && self.p1[i].name == key {
+                self.p1.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        match value.clone().inner() {
+            Some(ftd::Value::List { data, .. }) => {
+                let mut items = vec![];
+                for item in data.iter() {
+                    let mut section = ftd::p1::Section::with_name(key);
+                    self.write(&mut section, key, &resolved(item)?)?;
+                    items.push(section);
+                }
+                let section = &mut self.p1[declaration];
+                section.caption = None;
+                section.body = None;
+                section.header.0.retain(|(_, k, _)| k != "$processor$");
+                self.p1.splice(declaration + 1..declaration + 1, items);
+            }
+            _ => {
+                let mut section = self.p1[declaration].clone();
+                self.write(&mut section, key, &value)?;
+                self.p1[declaration] = section;
+            }
+        }
+
+        if let Some(ftd::p2::Thing::Variable(v)) = self.data.get_mut(name.as_str()) {
+            v.value = ftd::PropertyValue::Value { value };
+            v.conditions = vec![];
+        }
+        Ok(())
+    }
+
+    /// Adds an instance of the record `record` (as it is referred to in this
+    /// document, eg `person` or `people.person`), after its last instance.
+    pub fn push_instance<T>(&mut self, record: &str, value: &T) -> ftd::p1::Result<()>
+    where
+        T: serde::Serialize,
+    {
+        let full_name = {
+            let doc = ftd::p2::TDoc {
+                name: self.name.as_str(),
+                aliases: &self.aliases,
+                bag: &self.data,
+                local_variables: &mut Default::default(),
+            };
+            doc.get_record(0, record)?.name
+        };
+        let kind = ftd::p2::Kind::Record {
+            name: full_name.clone(),
+            default: None,
+        };
+        let fields = match self.value_from_json(value, kind)? {
+            ftd::Value::Record { fields, .. } => fields,
+            v => {
+                return ftd::e2(
+                    format!("expected an instance of {}, found: {:?}", record, v),
+                    self.name.as_str(),
+                    0,
+                )
+            }
+        };
+
+        let mut section = ftd::p1::Section::with_name(record);
+        write_record(self, &mut section, full_name.as_str(), &fields)?;
+        let position = self
+            .p1
+            .iter()
+            .rposition(|s| !s.is_commented && s.name == record)
+            .map(|i| i + 1)
+            .unwrap_or_else(|| self.p1.len());
+        self.p1.insert(position, section);
+
+        if let Some(ftd::p2::Thing::Record(r)) = self.data.get_mut(full_name.as_str()) {
+            r.instances
+                .entry(self.name.to_string())
+                .or_default()
+                .push(fields);
+        }
+        Ok(())
+    }
+
+    fn value_from_json<T>(&self, value: &T, kind: ftd::p2::Kind) -> ftd::p1::Result<ftd::Value>
+    where
+        T: serde::Serialize,
+    {
+        let json = serde_json::to_value(value)?;
+        let doc = ftd::p2::TDoc {
+            name: self.name.as_str(),
+            aliases: &self.aliases,
+            bag: &self.data,
+            local_variables: &mut Default::default(),
+        };
+        doc.from_json_(0, &json, kind)
+    }
+
+    // writes a value that is not a list to the caption, headers, body and
+    // sub-sections of `section`, keeping its comments and flags
+    fn write(
+        &self,
+        section: &mut ftd::p1::Section,
+        key: &str,
+        value: &ftd::Value,
+    ) -> ftd::p1::Result<()> {
+        section.caption = None;
+        section.body = None;
+        section
+            .header
+            .0
+            .retain(|(_, k, _)| k.starts_with('/') || (k.starts_with('$') && k != "$processor$"));
+        section.sub_sections.0.retain(|s| s.is_commented);
+
+        match value.clone().inner() {
+            None => {}
+            Some(ftd::Value::Record { name, fields }) => {
+                write_record(self, section, name.as_str(), &fields)?
+            }
+            Some(ftd::Value::List { .. }) => {}
+            Some(v) => {
+                let text = text(&v, key, self.name.as_str())?;
+                if text.contains('\n') {
+                    section.body = Some((0, text));
+                } else {
+                    section.caption = Some(text);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// `-- string title:` or `-- person list people:` for `key` being `title` or
+// `people`, the section has to declare a variable of `kind` named `key`
+fn is_declaration(
+    section: &ftd::p1::Section,
+    key: &str,
+    kind: &ftd::p2::Kind,
+    doc: &ftd::p2::TDoc,
+) -> bool {
+    if section.is_commented {
+        return false;
+    }
+    match ftd::variable::VariableData::get_name_kind(
+        section.name.as_str(),
+        doc,
+        section.line_number,
+        &[],
+    ) {
+        Ok(v) if v.is_variable() && v.name == key => {}
+        _ => return false,
+    }
+    ftd::p2::Kind::for_variable(
+        section.line_number,
+        section.name.as_str(),
+        None,
+        doc,
+        None,
+        &Default::default(),
+    )
+    .map(|k| k.is_same_as(kind))
+    .unwrap_or(false)
+}
+
+fn resolved(value: &ftd::PropertyValue) -> ftd::p1::Result<ftd::Value> {
+    match value {
+        ftd::PropertyValue::Value { value } => Ok(value.clone()),
+        v => ftd::e2(format!("only values can be written, found: {:?}", v), "", 0),
+    }
+}
+
+fn text(value: &ftd::Value, key: &str, doc_id: &str) -> ftd::p1::Result<String> {
+    Ok(match value {
+        ftd::Value::String { text, .. } => text.to_string(),
+        ftd::Value::Integer { value } => value.to_string(),
+        ftd::Value::Decimal { value } => value.to_string(),
+        ftd::Value::Boolean { value } => value.to_string(),
+        v => return ftd::e2(format!("{}: can not write {:?} as text", key, v), doc_id, 0),
+    })
+}
+
+// caption and body fields of the record go to the caption and body, records
+// and or-types to `--- field:` sub-sections, everything else to headers
+fn write_record(
+    document: &ftd::p2::Document,
+    section: &mut ftd::p1::Section,
+    name: &str,
+    fields: &std::collections::BTreeMap<String, ftd::PropertyValue>,
+) -> ftd::p1::Result<()> {
+    let doc_id = document.name.as_str();
+    let record = match document.data.get(name) {
+        Some(ftd::p2::Thing::Record(r)) => r.clone(),
+        _ => return ftd::e2(format!("record not found: {}", name), doc_id, 0),
+    };
+    for key in record.order.iter() {
+        let (kind, value) = match (record.fields.get(key), fields.get(key)) {
+            (Some(kind), Some(value)) => (kind, resolved(value)?),
+            _ => continue,
+        };
+        let values = match value.inner() {
+            Some(ftd::Value::List { data, .. }) => data
+                .iter()
+                .map(resolved)
+                .collect::<ftd::p1::Result<Vec<_>>>()?,
+            Some(v) => vec![v],
+            None => continue,
+        };
+        for value in values {
+            match value {
+                ftd::Value::Record { name, fields } => {
+                    let parts = sub_section(document, key.as_str(), &name, None, &fields)?;
+                    section.sub_sections.0.push(parts);
+                }
+                ftd::Value::OrType {
+                    name,
+                    variant,
+                    fields,
+                } => {
+                    let v = format!("{}.{}", name, variant);
+                    let parts = sub_section(document, key.as_str(), &v, Some(&variant), &fields)?;
+                    section.sub_sections.0.push(parts);
+                }
+                v => {
+                    let text = text(&v, key, doc_id)?;
+                    match kind.inner() {
+                        ftd::p2::Kind::String { caption: true, .. } if !text.contains('\n') => {
+                            section.caption = Some(text)
+                        }
+                        ftd::p2::Kind::String { body: true, .. } => section.body = Some((0, text)),
+                        _ if text.contains('\n') => {
+                            return ftd::e2(
+                                format!("{}: only caption and body can have more lines", key),
+                                doc_id,
+                                0,
+                            )
+                        }
+                        _ => section.header.add(&0, key, text.as_str()),
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn sub_section(
+    document: &ftd::p2::Document,
+    key: &str,
+    name: &str,
+    variant: Option<&str>,
+    fields: &std::collections::BTreeMap<String, ftd::PropertyValue>,
+) -> ftd::p1::Result<ftd::p1::SubSection> {
+    let mut section = ftd::p1::Section::with_name(key);
+    write_record(document, &mut section, name, fields)?;
+    if !section.sub_sections.0.is_empty() {
+        return ftd::e2(
+            format!("{}: sub-sections can not have sub-sections", key),
+            document.name.as_str(),
+            0,
+        );
+    }
+    Ok(ftd::p1::SubSection {
+        name: match variant {
+            Some(variant) => format!("{}.{}", key, variant),
+            None => key.to_string(),
+        },
+        caption: section.caption,
+        header: section.header,
+        body: section.body,
+        is_commented: false,
+        line_number: 0,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Person {
+        name: String,
+        bio: Option<String>,
+        age: i64,
+    }
+
+    #[test]
+    fn set() {
+        let mut doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- record person:
+                caption name:
+                optional body bio:
+                integer age:
+
+                -- string title: Hello
+
+                /-- string subtitle: draft
+
+                -- person list people:
+
+                -- people: Amit
+                age: 30
+
+                /-- people: Draft
+                age: 1
+
+                -- person: Sourabh
+                age: 20
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        doc.set("title", &"Bye").unwrap();
+        doc.set(
+            "people",
+            &vec![Person {
+                name: "Arpita".to_string(),
+                bio: Some("Writes docs.\n\n-- not a section".to_string()),
+                age: 25,
+            }],
+        )
+        .unwrap();
+        doc.push_instance(
+            "person",
+            &Person {
+                name: "Ganesh".to_string(),
+                bio: None,
+                age: 28,
+            },
+        )
+        .unwrap();
+        assert!(doc.set("person", &"x").is_err());
+        assert!(doc.set("title", &1).is_err());
+
+        let source = doc.to_string();
+        pretty_assertions::assert_eq!(
+            source.split_once("\n\n\n").unwrap().1,
+            indoc::indoc!(
+                "
+                -- string title: Bye
+
+
+                /-- string subtitle: draft
+
+
+                -- person list people:
+
+
+                -- people: Arpita
+                age: 25
+
+                Writes docs.
+
+                \\-- not a section
+
+
+                /-- people: Draft
+                age: 1
+
+
+                -- person: Sourabh
+                age: 20
+
+
+                -- person: Ganesh
+                age: 28"
+            )
+        );
+
+        pretty_assertions::assert_eq!(doc.get::<String>("title").unwrap(), "Bye");
+        pretty_assertions::assert_eq!(
+            doc.instances::<Person>("person").unwrap(),
+            vec![
+                Person {
+                    name: "Sourabh".to_string(),
+                    bio: None,
+                    age: 20,
+                },
+                Person {
+                    name: "Ganesh".to_string(),
+                    bio: None,
+                    age: 28,
+                },
+            ]
+        );
+
+        let reparsed =
+            ftd::p2::Document::from("foo/bar", source.as_str(), &ftd::p2::TestLibrary {})
+                .expect("found error");
+        pretty_assertions::assert_eq!(
+            reparsed.get::<Vec<Person>>("people").unwrap(),
+            doc.get::<Vec<Person>>("people").unwrap()
+        );
+    }
+
+    #[test]
+    fn declaration() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            "-- string title: Hello\n",
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+        let tdoc = ftd::p2::TDoc {
+            name: "foo/bar",
+            aliases: &doc.aliases,
+            bag: &doc.data,
+            local_variables: &mut Default::default(),
+        };
+        let is_declaration = |source: &str| {
+            let p1 = ftd::p1::parse(source, "foo/bar").unwrap();
+            super::is_declaration(&p1[0], "title", &ftd::p2::Kind::string(), &tdoc)
+        };
+
+        assert!(is_declaration("-- string title: Bye"));
+        assert!(!is_declaration("-- ftd.text title:"));
+        assert!(!is_declaration("-- string list title:"));
+        assert!(!is_declaration("-- integer title: 1"));
+        assert!(!is_declaration("-- string subtitle: Bye"));
+        assert!(!is_declaration("/-- string title: Bye"));
+    }
+}
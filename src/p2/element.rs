@@ -76,6 +76,7 @@ pub fn common_from_properties(
         is_dummy: false,
         events: ftd::p2::Event::get_events(0, events, doc)?,
         reference,
+        components: Default::default(),
         region: ftd::Region::from(
             ftd::p2::utils::string_optional("region", properties, doc.name, 0)?,
             doc.name,
@@ -217,6 +218,9 @@ pub fn common_from_properties(
         right: ftd::p2::utils::int_optional("right", properties, doc.name, 0)?,
         cursor: ftd::p2::utils::string_optional("cursor", properties, doc.name, 0)?,
         submit,
+        form: None,
+        overlay: None,
+        disclosure: None,
         shadow_offset_x: ftd::p2::utils::int_optional("shadow-offset-x", properties, doc.name, 0)?,
         shadow_offset_y: ftd::p2::utils::int_optional("shadow-offset-y", properties, doc.name, 0)?,
         shadow_size: ftd::p2::utils::int_optional("shadow-size", properties, doc.name, 0)?,
@@ -619,6 +623,36 @@ pub fn image_function() -> ftd::Component {
                 ),
                 ("align".to_string(), ftd::p2::Kind::string().into_optional()),
                 ("crop".to_string(), ftd::p2::Kind::boolean().into_optional()),
+                (
+                    "mobile-src".to_string(),
+                    ftd::p2::Kind::Record {
+                        name: "ftd#image-src".to_string(),
+                        default: None,
+                    }
+                    .into_optional(),
+                ),
+                (
+                    "xl-src".to_string(),
+                    ftd::p2::Kind::Record {
+                        name: "ftd#image-src".to_string(),
+                        default: None,
+                    }
+                    .into_optional(),
+                ),
+                ("fit".to_string(), ftd::p2::Kind::string().into_optional()),
+                (
+                    "aspect-ratio".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
+                (
+                    "loading".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
+                (
+                    "fetch-priority".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
+                ("sizes".to_string(), ftd::p2::Kind::string().into_optional()),
             ],
             common_arguments(),
         ]
@@ -646,8 +680,44 @@ pub fn image_from_properties(
         ftd::p2::utils::record_and_ref(0, "src", unresolved_properties, doc, condition)?;
     let src_record = ftd::ImageSrc::from(&src, doc, 0)?;
     let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+
+    let mut sources = vec![];
+    for (name, breakpoint) in [
+        ("mobile-src", "ftd#mobile-breakpoint"),
+        ("xl-src", "ftd#desktop-breakpoint"),
+    ] {
+        let src = match ftd::p2::utils::record_optional(name, properties, doc.name, 0)? {
+            Some(src) => ftd::ImageSrc::from(&src, doc, 0)?,
+            None => continue,
+        };
+        let breakpoint = match doc.get_value(0, breakpoint)? {
+            ftd::Value::Integer { value } => value,
+            t => return ftd::e2(format!("expected integer, found: {:?}", t), doc.name, 0),
+        };
+        // same as `get_device()` in ftd.js
+        let media = if name == "mobile-src" {
+            format!("(max-width: {}px)", breakpoint)
+        } else {
+            format!("(min-width: {}px)", breakpoint + 1)
+        };
+        sources.push(ftd::ImageSource { media, src });
+    }
+
+    let aspect_ratio = ftd::p2::utils::string_optional("aspect-ratio", properties, doc.name, 0)?;
+    if let Some(ref r) = aspect_ratio {
+        // `16/9`, or `1.5`
+        let valid = r
+            .split('/')
+            .map(|v| v.trim().parse::<f64>().map_or(false, |v| v > 0.0))
+            .collect::<Vec<_>>();
+        if !matches!(valid.as_slice(), [true] | [true, true]) {
+            return ftd::e2(format!("{} is not a valid aspect-ratio", r), doc.name, 0);
+        }
+    }
+
     Ok(ftd::Image {
         src: src_record,
+        sources,
         description: ftd::p2::utils::string_optional("description", properties, doc.name, 0)?
             .unwrap_or_else(|| "".to_string()),
         common: common_from_properties(
@@ -659,6 +729,20 @@ pub fn image_from_properties(
             reference,
         )?,
         crop: ftd::p2::utils::bool_with_default("crop", false, properties, doc.name, 0)?,
+        fit: ftd::ImageFit::from(
+            ftd::p2::utils::string_optional("fit", properties, doc.name, 0)?,
+            doc.name,
+        )?,
+        aspect_ratio,
+        loading: match ftd::p2::utils::string_optional("loading", properties, doc.name, 0)? {
+            Some(l) => Some(ftd::Loading::from(Some(l), doc.name)?),
+            None => None,
+        },
+        fetch_priority: ftd::FetchPriority::from(
+            ftd::p2::utils::string_optional("fetch-priority", properties, doc.name, 0)?,
+            doc.name,
+        )?,
+        sizes: ftd::p2::utils::string_optional("sizes", properties, doc.name, 0)?,
     })
 }
 
@@ -762,6 +846,298 @@ pub fn column_from_properties(
     })
 }
 
+pub fn form_function() -> ftd::Component {
+    let mut column = column_function();
+    column.full_name = "ftd#form".to_string();
+    column.arguments.extend([
+        (
+            "action".to_string(),
+            ftd::p2::Kind::string().into_optional(),
+        ),
+        (
+            "method".to_string(),
+            ftd::p2::Kind::string().into_optional(),
+        ),
+        (
+            "encoding".to_string(),
+            ftd::p2::Kind::string().into_optional(),
+        ),
+        (
+            "message-host".to_string(),
+            ftd::p2::Kind::string().into_optional(),
+        ),
+        (
+            "record".to_string(),
+            ftd::p2::Kind::string().into_optional(),
+        ),
+    ]);
+    column
+}
+
+pub fn form_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Column> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let action = ftd::p2::utils::string_optional("action", properties, doc.name, 0)?;
+    let message_host = ftd::p2::utils::string_optional("message-host", properties, doc.name, 0)?;
+    if action.is_some() == message_host.is_some() {
+        return ftd::e2("form needs either action or message-host", doc.name, 0);
+    }
+    let record = match ftd::p2::utils::string_optional("record", properties, doc.name, 0)? {
+        Some(r) => Some(doc.get_record(0, r.as_str())?.name),
+        None => None,
+    };
+
+    // the browser submits a form to `action`, and it can not send json
+    let encoding = ftd::FormEncoding::from(
+        ftd::p2::utils::string_optional("encoding", properties, doc.name, 0)?,
+        doc.name,
+    )?;
+    if action.is_some() && encoding == ftd::FormEncoding::Json {
+        return ftd::e2("json encoding needs message-host", doc.name, 0);
+    }
+
+    let mut column =
+        column_from_properties(unresolved_properties, doc, condition, is_child, events)?;
+    column.common.form = Some(ftd::Form {
+        action,
+        method: ftd::FormMethod::from(
+            ftd::p2::utils::string_optional("method", properties, doc.name, 0)?,
+            doc.name,
+        )?,
+        encoding,
+        message_host,
+        record,
+    });
+    Ok(column)
+}
+
+/// `ftd#dialog`, `ftd#popover` and `ftd#tooltip`, columns shown over the page
+pub fn overlay_function(full_name: &str) -> ftd::Component {
+    let mut column = column_function();
+    column.full_name = full_name.to_string();
+    // `open` is whether the overlay is shown, a tooltip has none
+    column.arguments.remove("open");
+    if full_name != "ftd#tooltip" {
+        column.arguments.extend([
+            ("open".to_string(), ftd::p2::Kind::boolean()),
+            (
+                "dismissible".to_string(),
+                ftd::p2::Kind::boolean().into_optional(),
+            ),
+            ("label".to_string(), ftd::p2::Kind::string().into_optional()),
+        ]);
+    }
+    if full_name != "ftd#dialog" {
+        column.arguments.extend([
+            ("anchor-id".to_string(), ftd::p2::Kind::string()),
+            (
+                "placement".to_string(),
+                ftd::p2::Kind::string().into_optional(),
+            ),
+        ]);
+    }
+    column
+}
+
+pub fn overlay_from_properties(
+    full_name: &str,
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Column> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let kind = match full_name {
+        "ftd#dialog" => ftd::OverlayKind::Dialog,
+        "ftd#popover" => ftd::OverlayKind::Popover,
+        _ => ftd::OverlayKind::Tooltip,
+    };
+    if condition.is_some() {
+        return ftd::e2(
+            format!("{} can not have if, it is shown with open", kind.to_str()),
+            doc.name,
+            0,
+        );
+    }
+
+    let mut column =
+        column_from_properties(unresolved_properties, doc, condition, is_child, events)?;
+    // the overlay is not an open container
+    column.container.open = None;
+    let open = if kind == ftd::OverlayKind::Tooltip {
+        column.common.is_not_visible = true;
+        None
+    } else {
+        // the variable is set to false when the overlay is dismissed
+        match ftd::p2::utils::boolean_and_ref(0, "open", unresolved_properties, doc, condition)? {
+            (open, Some(reference)) => {
+                column.common.is_not_visible = !open;
+                column.common.condition = Some(ftd::Condition {
+                    variable: reference.clone(),
+                    value: "true".to_string(),
+                });
+                Some(reference)
+            }
+            (_, None) => {
+                return ftd::e2(
+                    format!("open of {} must be a boolean variable", kind.to_str()),
+                    doc.name,
+                    0,
+                )
+            }
+        }
+    };
+    column.common.overlay = Some(ftd::Overlay {
+        open,
+        anchor: ftd::p2::utils::string_optional("anchor-id", properties, doc.name, 0)?,
+        placement: ftd::Placement::from(
+            ftd::p2::utils::string_optional("placement", properties, doc.name, 0)?,
+            doc.name,
+        )?,
+        dismissible: ftd::p2::utils::bool_with_default(
+            "dismissible",
+            true,
+            properties,
+            doc.name,
+            0,
+        )?,
+        label: ftd::p2::utils::string_optional("label", properties, doc.name, 0)?,
+        kind,
+    });
+    Ok(column)
+}
+
+/// `ftd#tabs`, `ftd#accordion`, `ftd#panel` and `ftd#details`, columns with
+/// parts shown on a click
+pub fn disclosure_function(full_name: &str) -> ftd::Component {
+    let mut column = column_function();
+    column.full_name = full_name.to_string();
+    match full_name {
+        "ftd#tabs" | "ftd#accordion" => {
+            column.arguments.insert(
+                "selected".to_string(),
+                ftd::p2::Kind::integer().into_optional(),
+            );
+        }
+        "ftd#panel" => {
+            column
+                .arguments
+                .insert("title".to_string(), ftd::p2::Kind::caption());
+        }
+        _ => {
+            column.arguments.extend([
+                ("summary".to_string(), ftd::p2::Kind::caption()),
+                ("open".to_string(), ftd::p2::Kind::boolean().into_optional()),
+            ]);
+        }
+    }
+    column
+}
+
+pub fn disclosure_from_properties(
+    full_name: &str,
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Column> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let mut column =
+        column_from_properties(unresolved_properties, doc, condition, is_child, events)?;
+    let mut disclosure = ftd::Disclosure {
+        kind: match full_name {
+            "ftd#tabs" => ftd::DisclosureKind::Tabs,
+            "ftd#accordion" => ftd::DisclosureKind::Accordion,
+            "ftd#panel" => ftd::DisclosureKind::Panel,
+            _ => ftd::DisclosureKind::Details,
+        },
+        ..Default::default()
+    };
+    match disclosure.kind {
+        ftd::DisclosureKind::Tabs | ftd::DisclosureKind::Accordion => {
+            let references =
+                ftd::component::resolve_properties_with_ref(0, unresolved_properties, doc)?;
+            // the panels of an or-type variable are keyed by its variants, a
+            // click sets the variant of the panel opened
+            if let Some((Some(ftd::Value::OrType { name, variant, .. }), reference)) = references
+                .get("selected")
+                .map(|(v, r)| (v.to_owned().inner(), r))
+            {
+                let prefix = format!("{}.", name);
+                disclosure.variants = doc
+                    .get_or_type(0, name.as_str())?
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        v.name
+                            .strip_prefix(prefix.as_str())
+                            .unwrap_or(v.name.as_str())
+                            .to_string()
+                    })
+                    .collect();
+                disclosure.selected = disclosure
+                    .variants
+                    .iter()
+                    .position(|v| v == &variant)
+                    .map(|index| index as i64);
+                disclosure.variable = ftd::p2::utils::complete_reference(reference)
+                    .map(|variable| format!("{}.type", variable));
+            } else {
+                // the variable is set to the index of the panel opened by a click
+                if unresolved_properties.contains_key("selected") {
+                    disclosure.variable = ftd::p2::utils::integer_and_ref(
+                        0,
+                        "selected",
+                        unresolved_properties,
+                        doc,
+                        condition,
+                    )?
+                    .1;
+                }
+                disclosure.selected =
+                    match ftd::p2::utils::int_optional("selected", properties, doc.name, 0)? {
+                        Some(index) if index >= 0 => Some(index),
+                        Some(_) => None,
+                        None if disclosure.variable.is_none()
+                            && disclosure.kind == ftd::DisclosureKind::Tabs =>
+                        {
+                            Some(0)
+                        }
+                        None => None,
+                    };
+            }
+        }
+        ftd::DisclosureKind::Panel => {
+            disclosure.title = Some(ftd::p2::utils::string("title", properties, doc.name, 0)?);
+        }
+        ftd::DisclosureKind::Details => {
+            disclosure.title = Some(ftd::p2::utils::string("summary", properties, doc.name, 0)?);
+            // `open` is whether the details are shown, as for overlays
+            column.container.open = None;
+            if unresolved_properties.contains_key("open") {
+                let (open, variable) = ftd::p2::utils::boolean_and_ref(
+                    0,
+                    "open",
+                    unresolved_properties,
+                    doc,
+                    condition,
+                )?;
+                disclosure.open = open;
+                disclosure.variable = variable;
+            }
+        }
+    }
+    column.common.disclosure = Some(disclosure);
+    Ok(column)
+}
+
 #[allow(dead_code)]
 #[allow(unused_variables)]
 pub fn text_render(
@@ -797,6 +1173,16 @@ pub fn iframe_function() -> ftd::Component {
                     "youtube".to_string(),
                     ftd::p2::Kind::string().into_optional(),
                 ),
+                ("embed".to_string(), ftd::p2::Kind::string().into_optional()),
+                (
+                    "sandbox".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
+                ("allow".to_string(), ftd::p2::Kind::string().into_optional()),
+                (
+                    "loading".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
             ],
             common_arguments(),
         ]
@@ -818,21 +1204,91 @@ pub fn iframe_from_properties(
     condition: &Option<ftd::p2::Boolean>,
     is_child: bool,
     events: &[ftd::p2::Event],
+    embed_providers: &ftd::embed::EmbedProviders,
 ) -> ftd::p1::Result<ftd::IFrame> {
     let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
-    let src = match (
+    let embed = match ftd::p2::utils::string_optional("embed", properties, doc.name, 0)? {
+        Some(url) => match embed_providers.embed(url.as_str()) {
+            Some(embed) => Some(embed),
+            None => return ftd::e2(format!("no embed provider for: {}", url), doc.name, 0),
+        },
+        None => None,
+    };
+    let (src, aspect_ratio) = match (
         ftd::p2::utils::string_optional("src", properties, doc.name, 0)?,
         ftd::p2::utils::string_optional("youtube", properties, doc.name, 0)?
             .and_then(|id| ftd::youtube_id::from_raw(id.as_str())),
+        embed,
     ) {
-        (Some(src), None) => src,
-        (None, Some(id)) => id,
-        (Some(_), Some(_)) => return ftd::e2("both src and youtube id provided", doc.name, 0),
-        (None, None) => return ftd::e2("src or youtube id is required", doc.name, 0),
+        (Some(src), None, None) => (src, None),
+        (None, Some(id), None) => (id, Some((16, 9))),
+        (None, None, Some(embed)) => (embed.src, embed.aspect_ratio),
+        (None, None, None) => return ftd::e2("src, youtube id or embed is required", doc.name, 0),
+        _ => {
+            return ftd::e2(
+                "only one of src, youtube id and embed can be given",
+                doc.name,
+                0,
+            )
+        }
     };
 
     Ok(ftd::IFrame {
         src,
+        aspect_ratio,
+        sandbox: ftd::p2::utils::string_optional("sandbox", properties, doc.name, 0)?,
+        allow: ftd::p2::utils::string_optional("allow", properties, doc.name, 0)?,
+        loading: ftd::Loading::from(
+            ftd::p2::utils::string_optional("loading", properties, doc.name, 0)?,
+            doc.name,
+        )?,
+        common: common_from_properties(
+            unresolved_properties,
+            doc,
+            condition,
+            is_child,
+            events,
+            None,
+        )?,
+    })
+}
+
+pub fn toc_function() -> ftd::Component {
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: "ftd#toc".to_string(),
+        arguments: [
+            vec![(
+                "max-level".to_string(),
+                ftd::p2::Kind::integer().into_optional(),
+            )],
+            common_arguments(),
+        ]
+        .concat()
+        .into_iter()
+        .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+pub fn toc_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Toc> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    Ok(ftd::Toc {
+        max_level: ftd::p2::utils::int_optional("max-level", properties, doc.name, 0)?,
+        outline: vec![],
         common: common_from_properties(
             unresolved_properties,
             doc,
@@ -1470,10 +1926,33 @@ pub fn input_function() -> ftd::Component {
         root: "ftd.kernel".to_string(),
         full_name: "ftd#input".to_string(),
         arguments: [
-            vec![(
-                "placeholder".to_string(),
-                ftd::p2::Kind::string().into_optional(),
-            )],
+            vec![
+                (
+                    "placeholder".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
+                ("type".to_string(), ftd::p2::Kind::string().into_optional()),
+                ("value".to_string(), ftd::p2::Kind::string().into_optional()),
+                ("min".to_string(), ftd::p2::Kind::string().into_optional()),
+                ("max".to_string(), ftd::p2::Kind::string().into_optional()),
+                ("step".to_string(), ftd::p2::Kind::string().into_optional()),
+                (
+                    "pattern".to_string(),
+                    ftd::p2::Kind::string().into_optional(),
+                ),
+                (
+                    "required".to_string(),
+                    ftd::p2::Kind::boolean().into_optional(),
+                ),
+                (
+                    "max-length".to_string(),
+                    ftd::p2::Kind::integer().into_optional(),
+                ),
+                (
+                    "valid".to_string(),
+                    ftd::p2::Kind::boolean().into_optional(),
+                ),
+            ],
             common_arguments(),
         ]
         .concat()
@@ -1496,16 +1975,649 @@ pub fn input_from_properties(
     events: &[ftd::p2::Event],
 ) -> ftd::p1::Result<ftd::Input> {
     let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
-    Ok(ftd::Input {
-        common: common_from_properties(
-            unresolved_properties,
-            doc,
-            condition,
-            is_child,
-            events,
-            None,
-        )?,
+    let references = ftd::component::resolve_properties_with_ref(0, unresolved_properties, doc)?;
+    let reference = |name: &str| {
+        references
+            .get(name)
+            .and_then(|(_, r)| ftd::p2::utils::complete_reference(r))
+    };
+
+    let type_ = ftd::InputType::from(
+        ftd::p2::utils::string_optional("type", properties, doc.name, 0)?,
+        doc.name,
+    )?;
+    let min = ftd::p2::utils::string_optional("min", properties, doc.name, 0)?;
+    let max = ftd::p2::utils::string_optional("max", properties, doc.name, 0)?;
+    let step = ftd::p2::utils::string_optional("step", properties, doc.name, 0)?;
+    if !type_.is_ordered() && (min.is_some() || max.is_some() || step.is_some()) {
+        return ftd::e2(
+            format!(
+                "min, max and step need type number, date or range, found: {}",
+                type_.to_str()
+            ),
+            doc.name,
+            0,
+        );
+    }
+
+    // `value` can also be an integer or a decimal, see `read_properties`
+    let value = properties.get("value");
+    let value_kind = match value.map(|v| v.kind().inner().to_owned()) {
+        Some(ftd::p2::Kind::Integer { .. }) => "integer",
+        Some(ftd::p2::Kind::Decimal { .. }) => "decimal",
+        _ => "string",
+    };
+    if value_kind != "string" && !matches!(type_, ftd::InputType::Number | ftd::InputType::Range) {
+        return ftd::e2(
+            format!(
+                "{} value needs type number or range, found: {}",
+                value_kind,
+                type_.to_str()
+            ),
+            doc.name,
+            0,
+        );
+    }
+    let value = value
+        .and_then(|v| v.to_owned().inner())
+        .filter(|v| !matches!(v, ftd::Value::None { .. }))
+        .and_then(|v| v.to_string());
+
+    // `value: $foo` updates `foo` as the input changes, and `valid: $foo`
+    // sets `foo` to whether the input is valid, `ftd::RT` sets it to
+    // `value_is_valid` after rendering
+    let mut common = common_from_properties(
+        unresolved_properties,
+        doc,
+        condition,
+        is_child,
+        events,
+        reference("value"),
+    )?;
+    if let Some(ref variable) = common.reference {
+        common.events.push(ftd::p2::Event::set_value_event(
+            "oninput", variable, "$VALUE", value_kind,
+        ));
+    }
+    let valid = match (properties.contains_key("valid"), reference("valid")) {
+        (false, _) => None,
+        (true, Some(variable)) => {
+            common.events.push(ftd::p2::Event::set_value_event(
+                "oninput",
+                variable.as_str(),
+                "$VALID",
+                "boolean",
+            ));
+            Some(variable)
+        }
+        (true, None) => return ftd::e2("valid must be a boolean variable", doc.name, 0),
+    };
+
+    let mut input = ftd::Input {
+        common,
         placeholder: ftd::p2::utils::string_optional("placeholder", properties, doc.name, 0)?,
+        type_,
+        value,
+        min,
+        max,
+        step,
+        pattern: ftd::p2::utils::string_optional("pattern", properties, doc.name, 0)?,
+        required: ftd::p2::utils::bool_optional("required", properties, doc.name, 0)?
+            .unwrap_or(false),
+        max_length: ftd::p2::utils::int_optional("max-length", properties, doc.name, 0)?,
+        valid,
+        value_is_valid: false,
+    };
+    input.value_is_valid = input.is_valid();
+    Ok(input)
+}
+
+pub fn checkbox_function(full_name: &str) -> ftd::Component {
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: full_name.to_string(),
+        arguments: [
+            vec![
+                (
+                    "checked".to_string(),
+                    ftd::p2::Kind::boolean().into_optional(),
+                ),
+                ("label".to_string(), ftd::p2::Kind::string().into_optional()),
+            ],
+            common_arguments(),
+        ]
+        .concat()
+        .into_iter()
+        .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+pub fn checkbox_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Checkbox> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let references = ftd::component::resolve_properties_with_ref(0, unresolved_properties, doc)?;
+
+    // `checked: $foo` toggles `foo` as the checkbox is clicked
+    let mut common = common_from_properties(
+        unresolved_properties,
+        doc,
+        condition,
+        is_child,
+        events,
+        references
+            .get("checked")
+            .and_then(|(_, r)| ftd::p2::utils::complete_reference(r)),
+    )?;
+    if let Some(ref variable) = common.reference {
+        common.events.push(ftd::Event {
+            name: "onchange".to_string(),
+            action: ftd::Action {
+                action: "toggle".to_string(),
+                target: variable.to_string(),
+                parameters: Default::default(),
+            },
+        });
+    }
+
+    Ok(ftd::Checkbox {
+        common,
+        checked: ftd::p2::utils::bool_optional("checked", properties, doc.name, 0)?
+            .unwrap_or(false),
+        label: ftd::p2::utils::string_optional("label", properties, doc.name, 0)?,
+    })
+}
+
+pub fn select_function(full_name: &str) -> ftd::Component {
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: full_name.to_string(),
+        arguments: [
+            vec![
+                (
+                    "options".to_string(),
+                    ftd::p2::Kind::list(ftd::p2::Kind::string()).into_optional(),
+                ),
+                ("value".to_string(), ftd::p2::Kind::string().into_optional()),
+                ("label".to_string(), ftd::p2::Kind::string().into_optional()),
+            ],
+            common_arguments(),
+        ]
+        .concat()
+        .into_iter()
+        .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+pub fn select_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Select> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let references = ftd::component::resolve_properties_with_ref(0, unresolved_properties, doc)?;
+
+    let mut options = vec![];
+    let list = properties.get("options").and_then(|v| v.to_owned().inner());
+    if let Some(ftd::Value::List { ref data, .. }) = list {
+        for option in data {
+            match option.resolve(0, doc)?.to_string() {
+                Some(o) => options.push(o),
+                None => return ftd::e2("options must be a list of strings", doc.name, 0),
+            }
+        }
+    }
+
+    // `value: $foo` sets `foo` to the option picked, and if `foo` is an
+    // or-type, the options are its variants and picking one sets the variant
+    let (value, field) = match properties.get("value").and_then(|v| v.to_owned().inner()) {
+        Some(ftd::Value::OrType { name, variant, .. }) => {
+            if list.is_some() {
+                return ftd::e2(
+                    format!("options of an or-type come from its variants: {}", name),
+                    doc.name,
+                    0,
+                );
+            }
+            let prefix = format!("{}.", name);
+            options = doc
+                .get_or_type(0, name.as_str())?
+                .variants
+                .iter()
+                .map(|v| {
+                    v.name
+                        .strip_prefix(prefix.as_str())
+                        .unwrap_or(v.name.as_str())
+                        .to_string()
+                })
+                .collect();
+            (Some(variant), ".type")
+        }
+        _ if list.is_none() => {
+            return ftd::e2(
+                "options are needed, unless value is an or-type",
+                doc.name,
+                0,
+            )
+        }
+        _ => (
+            ftd::p2::utils::string_optional("value", properties, doc.name, 0)?,
+            "",
+        ),
+    };
+    let mut common = common_from_properties(
+        unresolved_properties,
+        doc,
+        condition,
+        is_child,
+        events,
+        references
+            .get("value")
+            .and_then(|(_, r)| ftd::p2::utils::complete_reference(r)),
+    )?;
+    if let Some(ref variable) = common.reference {
+        common.events.push(ftd::p2::Event::set_value_event(
+            "onchange",
+            format!("{}{}", variable, field).as_str(),
+            "$VALUE",
+            "string",
+        ));
+    }
+
+    Ok(ftd::Select {
+        common,
+        options,
+        value,
+        label: ftd::p2::utils::string_optional("label", properties, doc.name, 0)?,
+    })
+}
+
+/// `ftd#svg`, or `ftd#icon` which looks the svg up in the registered icon sets
+pub fn svg_function(full_name: &str) -> ftd::Component {
+    let arguments = if full_name == "ftd#icon" {
+        vec![
+            ("name".to_string(), ftd::p2::Kind::caption()),
+            ("set".to_string(), ftd::p2::Kind::string().into_optional()),
+        ]
+    } else {
+        vec![("svg".to_string(), ftd::p2::Kind::caption_or_body())]
+    };
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: full_name.to_string(),
+        arguments: [
+            arguments,
+            vec![("label".to_string(), ftd::p2::Kind::string().into_optional())],
+            common_arguments(),
+        ]
+        .concat()
+        .into_iter()
+        .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+pub fn svg_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+    icon_sets: &ftd::icon::IconSets,
+) -> ftd::p1::Result<ftd::Svg> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let icon = ftd::p2::utils::string_optional("name", properties, doc.name, 0)?;
+    let svg = match icon {
+        Some(ref name) => {
+            let set = ftd::p2::utils::string_optional("set", properties, doc.name, 0)?;
+            match icon_sets.icon(set.as_deref(), name) {
+                Some(svg) => svg,
+                None => return ftd::e2(format!("icon not found: {}", name), doc.name, 0),
+            }
+        }
+        None => ftd::p2::utils::string("svg", properties, doc.name, 0)?,
+    };
+
+    Ok(ftd::Svg {
+        svg: ftd::icon::check_svg(svg.as_str(), doc.name, 0)?,
+        icon,
+        label: ftd::p2::utils::string_optional("label", properties, doc.name, 0)?,
+        common: common_from_properties(
+            unresolved_properties,
+            doc,
+            condition,
+            is_child,
+            events,
+            None,
+        )?,
+    })
+}
+
+pub fn table_function() -> ftd::Component {
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: "ftd#table".to_string(),
+        arguments: [
+            vec![
+                // a list of any record
+                (
+                    "data".to_string(),
+                    ftd::p2::Kind::list(ftd::p2::Kind::object()),
+                ),
+                (
+                    "columns".to_string(),
+                    ftd::p2::Kind::list(ftd::p2::Kind::Record {
+                        name: "ftd#table-column".to_string(),
+                        default: None,
+                    }),
+                ),
+                (
+                    "sortable".to_string(),
+                    ftd::p2::Kind::boolean().into_optional(),
+                ),
+                (
+                    "sticky-header".to_string(),
+                    ftd::p2::Kind::boolean().into_optional(),
+                ),
+                (
+                    "stripe-color".to_string(),
+                    ftd::p2::Kind::Record {
+                        name: "ftd#color".to_string(),
+                        default: None,
+                    }
+                    .into_optional(),
+                ),
+            ],
+            common_arguments(),
+        ]
+        .concat()
+        .into_iter()
+        .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+/// the cells have the values of `rows` as text, the elements of the columns
+/// with a `component` are added by the caller, see `table_rows()`
+pub fn table_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+    rows: &[std::collections::BTreeMap<String, ftd::Value>],
+) -> ftd::p1::Result<ftd::Table> {
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let mut columns = vec![];
+    if let Some(ftd::Value::List { data, .. }) = properties.get("columns") {
+        for column in data {
+            match column.resolve(0, doc)? {
+                ftd::Value::Record { fields, .. } => {
+                    columns.push(ftd::TableColumn::from(&fields, doc, 0)?)
+                }
+                t => {
+                    return ftd::e2(
+                        format!("expected ftd.table-column, found: {:?}", t),
+                        doc.name,
+                        0,
+                    )
+                }
+            }
+        }
+    }
+
+    let rows = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| match row.get(column.field.as_str()) {
+                    Some(value) => Ok(ftd::TableCell {
+                        text: value.to_string().unwrap_or_default(),
+                        element: None,
+                    }),
+                    None => ftd::e2(
+                        format!("{} is not a field of the table data", column.field),
+                        doc.name,
+                        0,
+                    ),
+                })
+                .collect::<ftd::p1::Result<Vec<ftd::TableCell>>>()
+        })
+        .collect::<ftd::p1::Result<Vec<Vec<ftd::TableCell>>>>()?;
+
+    Ok(ftd::Table {
+        columns,
+        rows,
+        sortable: ftd::p2::utils::bool_with_default("sortable", false, properties, doc.name, 0)?,
+        sticky_header: ftd::p2::utils::bool_with_default(
+            "sticky-header",
+            false,
+            properties,
+            doc.name,
+            0,
+        )?,
+        stripe_color: ftd::Color::from(
+            ftd::p2::utils::record_optional_with_ref(
+                "stripe-color",
+                unresolved_properties,
+                doc,
+                0,
+            )?,
+            doc,
+            0,
+        )?,
+        common: common_from_properties(
+            unresolved_properties,
+            doc,
+            condition,
+            is_child,
+            events,
+            None,
+        )?,
+    })
+}
+
+/// the fields of each record in `data` of `ftd#table`
+pub fn table_rows(
+    properties: &std::collections::BTreeMap<String, ftd::Value>,
+    doc: &ftd::p2::TDoc,
+) -> ftd::p1::Result<Vec<std::collections::BTreeMap<String, ftd::Value>>> {
+    let data = match properties.get("data") {
+        Some(ftd::Value::List { data, .. }) => data,
+        _ => return ftd::e2("expected a list of records in data", doc.name, 0),
+    };
+    data.iter()
+        .map(|row| match row.resolve(0, doc)? {
+            ftd::Value::Record { fields, .. } => fields
+                .iter()
+                .map(|(k, v)| v.resolve(0, doc).map(|v| (k.to_string(), v)))
+                .collect::<ftd::p1::Result<std::collections::BTreeMap<String, ftd::Value>>>(),
+            t => ftd::e2(format!("expected a record, found: {:?}", t), doc.name, 0),
+        })
+        .collect()
+}
+
+/// `ftd#video` and `ftd#audio`, only a video has a `poster`
+pub fn media_function(full_name: &str) -> ftd::Component {
+    let image_src = ftd::p2::Kind::Record {
+        name: "ftd#image-src".to_string(),
+        default: None,
+    };
+    let mut arguments = vec![
+        ("src".to_string(), image_src.clone()),
+        (
+            "autoplay".to_string(),
+            ftd::p2::Kind::boolean().into_optional(),
+        ),
+        ("loop".to_string(), ftd::p2::Kind::boolean().into_optional()),
+        (
+            "muted".to_string(),
+            ftd::p2::Kind::boolean().into_optional(),
+        ),
+        (
+            "controls".to_string(),
+            ftd::p2::Kind::boolean().into_optional(),
+        ),
+        (
+            "tracks".to_string(),
+            ftd::p2::Kind::list(ftd::p2::Kind::Record {
+                name: "ftd#track".to_string(),
+                default: None,
+            })
+            .into_optional(),
+        ),
+    ];
+    if full_name == "ftd#video" {
+        arguments.push(("poster".to_string(), image_src.into_optional()));
+    }
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: full_name.to_string(),
+        arguments: [arguments, common_arguments()]
+            .concat()
+            .into_iter()
+            .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+pub fn media_from_properties(
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Media> {
+    // like `ftd.image`, a `src` variable is followed to switch to its `dark` source
+    let (src, reference) =
+        ftd::p2::utils::record_and_ref(0, "src", unresolved_properties, doc, condition)?;
+    let properties = &ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let poster_reference =
+        ftd::component::resolve_properties_with_ref(0, unresolved_properties, doc)?
+            .remove("poster")
+            .and_then(|(_, reference)| ftd::p2::utils::complete_reference(&reference));
+    let poster = match ftd::p2::utils::record_optional("poster", properties, doc.name, 0)? {
+        Some(poster) => Some(ftd::ImageSrc::from(&poster, doc, 0)?),
+        None => None,
+    };
+
+    let mut tracks = vec![];
+    if let Some(ftd::Value::List { data, .. }) = properties.get("tracks") {
+        for track in data {
+            match track.resolve(0, doc)? {
+                ftd::Value::Record { fields, .. } => {
+                    tracks.push(ftd::Track::from(&fields, doc, 0)?)
+                }
+                t => return ftd::e2(format!("expected ftd.track, found: {:?}", t), doc.name, 0),
+            }
+        }
+    }
+
+    Ok(ftd::Media {
+        src: ftd::ImageSrc::from(&src, doc, 0)?,
+        poster,
+        poster_reference,
+        autoplay: ftd::p2::utils::bool_with_default("autoplay", false, properties, doc.name, 0)?,
+        loop_: ftd::p2::utils::bool_with_default("loop", false, properties, doc.name, 0)?,
+        muted: ftd::p2::utils::bool_with_default("muted", false, properties, doc.name, 0)?,
+        controls: ftd::p2::utils::bool_with_default("controls", true, properties, doc.name, 0)?,
+        tracks,
+        common: common_from_properties(
+            unresolved_properties,
+            doc,
+            condition,
+            is_child,
+            events,
+            reference,
+        )?,
+    })
+}
+
+pub fn native_function(plugin: &dyn ftd::Plugin) -> ftd::Component {
+    ftd::Component {
+        line_number: 0,
+        kernel: true,
+        root: "ftd.kernel".to_string(),
+        full_name: format!("ftd#{}", plugin.name()),
+        arguments: [plugin.arguments(), common_arguments()]
+            .concat()
+            .into_iter()
+            .collect(),
+        locals: Default::default(),
+        properties: Default::default(),
+        instructions: Default::default(),
+        invocations: Default::default(),
+        condition: None,
+        events: vec![],
+    }
+}
+
+pub fn native_from_properties(
+    plugin: &dyn ftd::Plugin,
+    unresolved_properties: &std::collections::BTreeMap<String, ftd::component::Property>,
+    doc: &ftd::p2::TDoc,
+    condition: &Option<ftd::p2::Boolean>,
+    is_child: bool,
+    events: &[ftd::p2::Event],
+) -> ftd::p1::Result<ftd::Native> {
+    let mut properties = ftd::component::resolve_properties(0, unresolved_properties, doc)?;
+    let arguments = plugin.arguments();
+    properties.retain(|k, _| arguments.iter().any(|(name, _)| name == k));
+    Ok(ftd::Native {
+        name: plugin.name(),
+        common: common_from_properties(
+            unresolved_properties,
+            doc,
+            condition,
+            is_child,
+            events,
+            None,
+        )?,
+        node: plugin.render(&properties, doc.name)?,
     })
 }
 
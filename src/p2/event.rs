@@ -136,6 +136,32 @@ impl Event {
             },
         ]
     }
+
+    /// sets `target` to `value` on `name`, `value` can be `$VALUE` or `$VALID`,
+    /// the value or validity of the element the event is on
+    pub fn set_value_event(name: &str, target: &str, value: &str, kind: &str) -> ftd::Event {
+        ftd::Event {
+            name: name.to_string(),
+            action: ftd::Action {
+                action: "set-value".to_string(),
+                target: target.to_string(),
+                parameters: std::array::IntoIter::new([(
+                    "value".to_string(),
+                    vec![
+                        ftd::event::ParameterData {
+                            value: value.to_string(),
+                            reference: None,
+                        },
+                        ftd::event::ParameterData {
+                            value: kind.to_string(),
+                            reference: None,
+                        },
+                    ],
+                )])
+                .collect(),
+            },
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -146,6 +172,8 @@ pub enum EventName {
     OnMouseEnter,
     OnMouseLeave,
     OnClickOutside,
+    OnPlay,
+    OnEnded,
 }
 
 impl EventName {
@@ -157,6 +185,8 @@ impl EventName {
             Self::OnMouseEnter => "onmouseenter",
             Self::OnMouseLeave => "onmouseleave",
             Self::OnClickOutside => "onclickoutside",
+            Self::OnPlay => "onplay",
+            Self::OnEnded => "onended",
         }
     }
 
@@ -168,6 +198,8 @@ impl EventName {
             "mouse-enter" => Ok(Self::OnMouseEnter),
             "mouse-leave" => Ok(Self::OnMouseLeave),
             "click-outside" => Ok(Self::OnClickOutside),
+            "play" => Ok(Self::OnPlay),
+            "ended" => Ok(Self::OnEnded),
             t => return ftd::e2(format!("{} is not a valid event", t), doc_id, 0),
         }
     }
@@ -214,6 +246,7 @@ pub enum ActionKind {
     PreventDefault,
     SetValue,
     MessageHost,
+    SubmitForm,
 }
 
 impl serde::Serialize for ActionKind {
@@ -236,6 +269,7 @@ impl ActionKind {
             ftd::p2::ActionKind::PreventDefault => "prevent-default",
             ftd::p2::ActionKind::SetValue => "set-value",
             ftd::p2::ActionKind::MessageHost => "message-host",
+            ftd::p2::ActionKind::SubmitForm => "submit-form",
             ftd::p2::ActionKind::Clear => "clear",
         }
     }
@@ -260,7 +294,8 @@ impl ActionKind {
             | ftd::p2::ActionKind::StopPropagation
             | ftd::p2::ActionKind::PreventDefault
             | ftd::p2::ActionKind::Clear
-            | ftd::p2::ActionKind::SetValue => {}
+            | ftd::p2::ActionKind::SetValue
+            | ftd::p2::ActionKind::SubmitForm => {}
             ftd::p2::ActionKind::MessageHost => {
                 parameters.insert(
                     "data".to_string(),
@@ -613,6 +648,17 @@ impl Action {
                 },
                 parameters: Default::default(),
             }),
+            // submits the `ftd.form` the element is in
+            _ if a.eq("submit-form") => Ok(Self {
+                action: ActionKind::SubmitForm,
+                target: ftd::PropertyValue::Value {
+                    value: ftd::Value::String {
+                        text: "".to_string(),
+                        source: ftd::TextSource::Header,
+                    },
+                },
+                parameters: Default::default(),
+            }),
             _ if a.contains('=') => {
                 let (part_1, part_2) = ftd::p2::utils::split(a, "=")?;
                 let target = get_target(line_number, part_1, doc, arguments, None)?;
@@ -0,0 +1,256 @@
+//! Reading a submission of an `ftd.form` back as the record the form declares.
+//!
+//! A form submits the variables bound to its inputs by their name, as JSON or
+//! `application/x-www-form-urlencoded`. Inputs only know strings, so values are
+//! converted to the kind of the record field they are for before the record is
+//! checked, same as `TDoc::from_json()` checks data.
+
+impl ftd::p2::Document {
+    /// `body` submitted with `encoding` by the form with id `form`, as the
+    /// record that form declares, eg
+    /// `doc.submission::<Person>("signup", &ftd::FormEncoding::Json, body)`
+    pub fn submission<T>(
+        &self,
+        form: &str,
+        encoding: &ftd::FormEncoding,
+        body: &str,
+    ) -> ftd::p1::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let value = self.submission_value(form, encoding, body)?;
+        Ok(serde_json::from_value(self.value_to_json(&value)?)?)
+    }
+
+    pub fn submission_value(
+        &self,
+        form: &str,
+        encoding: &ftd::FormEncoding,
+        body: &str,
+    ) -> ftd::p1::Result<ftd::Value> {
+        let doc = ftd::p2::TDoc {
+            name: self.name.as_str(),
+            aliases: &self.aliases,
+            bag: &self.data,
+            local_variables: &mut Default::default(),
+        };
+        // the data id without the index `ftd::Element::set_id` appends on render
+        let found = Self::find(&self.main.container.children, &|e| {
+            e.get_common()
+                .filter(|c| c.data_id.as_deref().and_then(|d| d.split(':').next()) == Some(form))
+                .map(|c| c.form.clone())
+        });
+        let record = match found {
+            Some(Some(ftd::Form {
+                record: Some(record),
+                ..
+            })) => record,
+            Some(Some(_)) => {
+                return ftd::e2(
+                    format!("form {} has no record", form),
+                    self.name.as_str(),
+                    0,
+                )
+            }
+            Some(None) => return ftd::e2(format!("{} is not a form", form), self.name.as_str(), 0),
+            None => return ftd::e2(format!("no form with id {}", form), self.name.as_str(), 0),
+        };
+        let record = doc.get_record(0, record.as_str())?;
+
+        let submitted: serde_json::Map<String, serde_json::Value> = match encoding {
+            ftd::FormEncoding::Json => serde_json::from_str(body)?,
+            ftd::FormEncoding::UrlEncoded => url_decode(body, self.name.as_str())?
+                .into_iter()
+                .map(|(k, v)| (k, serde_json::Value::String(v)))
+                .collect(),
+        };
+
+        let mut fields = serde_json::Map::new();
+        for (name, kind) in record.fields.iter() {
+            let value = match (submitted.get(name), kind.get_default_value_str()) {
+                (Some(v), _) => typed(v, kind),
+                (None, Some(default)) => typed(&serde_json::Value::String(default), kind),
+                (None, None) => serde_json::Value::Null,
+            };
+            fields.insert(name.to_string(), value);
+        }
+
+        doc.from_json_(
+            0,
+            &serde_json::Value::Object(fields),
+            ftd::p2::Kind::Record {
+                name: record.name,
+                default: None,
+            },
+        )
+    }
+}
+
+// a string, as submitted by an input, as a value of `kind`
+fn typed(value: &serde_json::Value, kind: &ftd::p2::Kind) -> serde_json::Value {
+    let text = match value {
+        serde_json::Value::String(text) => text.trim(),
+        _ => return value.to_owned(),
+    };
+    if text.is_empty() && kind.is_optional() {
+        return serde_json::Value::Null;
+    }
+    let typed = match kind.inner() {
+        ftd::p2::Kind::Integer { .. } => text.parse::<i64>().ok().map(Into::into),
+        ftd::p2::Kind::Decimal { .. } => text.parse::<f64>().ok().map(Into::into),
+        ftd::p2::Kind::Boolean { .. } => match text {
+            "true" | "on" => Some(true.into()),
+            "false" | "off" => Some(false.into()),
+            _ => None,
+        },
+        _ => None,
+    };
+    typed.unwrap_or_else(|| value.to_owned())
+}
+
+fn url_decode(body: &str, doc_id: &str) -> ftd::p1::Result<Vec<(String, String)>> {
+    let mut pairs = vec![];
+    for pair in body.split('&').filter(|p| !p.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match (percent_decode(name), percent_decode(value)) {
+            (Some(name), Some(value)) => pairs.push((name, value)),
+            _ => return ftd::e2(format!("invalid form data: {}", pair), doc_id, 0),
+        }
+    }
+    Ok(pairs)
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        bytes.push(match b {
+            b'+' => b' ',
+            b'%' => {
+                let hex = [iter.next()?, iter.next()?];
+                u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?
+            }
+            b => b,
+        });
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod test {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Person {
+        name: String,
+        age: i64,
+        subscribed: bool,
+        nickname: Option<String>,
+    }
+
+    #[test]
+    fn submission() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- record person:
+                string name:
+                integer age:
+                boolean subscribed:
+                optional string nickname:
+
+                -- string name: Arpita
+
+                -- ftd.form:
+                id: signup
+                action: /people/
+                method: post
+                record: person
+
+                --- ftd.input:
+                value: $name
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let form = match &doc.main.container.children[0] {
+            ftd::Element::Column(c) => c.common.form.clone(),
+            t => panic!("expected form, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(
+            form,
+            Some(ftd::Form {
+                action: Some("/people/".to_string()),
+                method: ftd::FormMethod::Post,
+                encoding: ftd::FormEncoding::UrlEncoded,
+                message_host: None,
+                record: Some("foo/bar#person".to_string()),
+            })
+        );
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("<form"));
+        assert!(html.contains("onsubmit="));
+
+        let arpita = Person {
+            name: "Arpita Jaiswal".to_string(),
+            age: 28,
+            subscribed: true,
+            nickname: None,
+        };
+        pretty_assertions::assert_eq!(
+            doc.submission::<Person>(
+                "signup",
+                &ftd::FormEncoding::UrlEncoded,
+                "name=Arpita+Jaiswal&age=28&subscribed=true&nickname="
+            )
+            .unwrap(),
+            arpita
+        );
+        pretty_assertions::assert_eq!(
+            doc.submission::<Person>(
+                "signup",
+                &ftd::FormEncoding::Json,
+                r#"{"name": "Arpita Jaiswal", "age": "28", "subscribed": true}"#
+            )
+            .unwrap(),
+            arpita
+        );
+
+        for body in ["name=Arpita&age=old&subscribed=true", "name=Arpita&age=28"] {
+            assert!(
+                doc.submission::<Person>("signup", &ftd::FormEncoding::UrlEncoded, body)
+                    .is_err(),
+                "{}",
+                body
+            );
+        }
+
+        ftd::test::assert_invalid(
+            "",
+            &[
+                "-- ftd.form:",
+                "-- ftd.form:\naction: /a/\nmessage-host: submit",
+                "-- ftd.form:\naction: /a/\nmethod: put",
+                "-- ftd.form:\naction: /a/\nencoding: json",
+                "-- ftd.form:\naction: /a/\nrecord: person",
+                indoc::indoc!(
+                    "
+                    -- ftd.input field:
+                    string v:
+                    value: $v
+
+                    -- ftd.form:
+                    action: /a/
+
+                    --- field:
+                    v: a
+
+                    --- field:
+                    v: b
+                    "
+                ),
+            ],
+        );
+    }
+}
@@ -4,6 +4,16 @@ pub(crate) struct Interpreter<'a> {
     pub p1: Vec<ftd::p1::Section>,
     pub aliases: std::collections::BTreeMap<String, String>,
     pub parsed_libs: Vec<String>,
+    pub profile: ftd::RenderProfile,
+    pub budget: ftd::limits::Budget,
+    pub plugins: ftd::plugin::Plugins,
+    pub embed_providers: ftd::embed::EmbedProviders,
+    pub icon_sets: ftd::icon::IconSets,
+    import_depth: usize,
+    pub lock: ftd::p2::Lock,
+    pub cache: Option<&'a dyn ftd::p2::LibraryCache>,
+    // hash of each library interpreted so far, see `ftd::p2::cache`
+    hashes: std::collections::BTreeMap<String, u64>,
 }
 
 impl<'a> Interpreter<'a> {
@@ -23,13 +33,12 @@ impl<'a> Interpreter<'a> {
         name: &str,
         s: &str,
     ) -> ftd::p1::Result<Vec<ftd::Instruction>> {
-        let mut d_get = std::time::Duration::new(0, 0);
-        let mut d_processor = std::time::Duration::new(0, 0);
-        let v = self
-            .async_interpret_(name, s, true, &mut d_get, &mut d_processor)
-            .await?;
-        // observer::observe_string("time_get", elapsed(d_get).as_str());
-        // observer::observe_string("time_processor", elapsed(d_processor).as_str());
+        let start = std::time::Instant::now();
+        self.plugins = ftd::plugin::Plugins::from_library(self.lib.plugins(), &mut self.bag, name)?;
+        self.embed_providers = ftd::embed::EmbedProviders::from_library(self.lib.embed_providers());
+        self.icon_sets = ftd::icon::IconSets::from_library(self.lib.icon_sets());
+        let v = self.async_interpret_(name, s, true).await?;
+        self.profile.interpret = start.elapsed();
         Ok(v)
     }
 
@@ -39,11 +48,12 @@ impl<'a> Interpreter<'a> {
         name: &str,
         s: &str,
     ) -> ftd::p1::Result<Vec<ftd::Instruction>> {
-        let mut d_get = std::time::Duration::new(0, 0);
-        let mut d_processor = std::time::Duration::new(0, 0);
-        let v = self.interpret_(name, s, true, &mut d_get, &mut d_processor)?;
-        // observer::observe_string("time_get", elapsed(d_get).as_str());
-        // observer::observe_string("time_processor", elapsed(d_processor).as_str());
+        let start = std::time::Instant::now();
+        self.plugins = ftd::plugin::Plugins::from_library(self.lib.plugins(), &mut self.bag, name)?;
+        self.embed_providers = ftd::embed::EmbedProviders::from_library(self.lib.embed_providers());
+        self.icon_sets = ftd::icon::IconSets::from_library(self.lib.icon_sets());
+        let v = self.interpret_(name, s, true)?;
+        self.profile.interpret = start.elapsed();
         Ok(v)
     }
 
@@ -57,49 +67,296 @@ impl<'a> Interpreter<'a> {
         }
     }
 
+    // name to fetch the library with, versioned imports get resolved against
+    // the versions the library has and the lock
     #[cfg(feature = "async")]
-    #[async_recursion::async_recursion(?Send)]
+    async fn library_to_fetch(
+        &mut self,
+        library_name: &str,
+        version: &Option<ftd::p2::VersionReq>,
+        name: &str,
+        aliases: &std::collections::BTreeMap<String, String>,
+        line_number: usize,
+    ) -> ftd::p1::Result<String> {
+        let req = match version {
+            Some(req) => req,
+            None => {
+                self.lock.unversioned(library_name, name, line_number)?;
+                return Ok(library_name.to_string());
+            }
+        };
+        let doc = ftd::p2::TDoc {
+            name,
+            aliases,
+            bag: &self.bag,
+            local_variables: &mut Default::default(),
+        };
+        let available = self.lib.versions(library_name, &doc).await;
+        let version = self
+            .lock
+            .resolve(library_name, req, &available, name, line_number)?;
+        Ok(ftd::p2::versioned_name(library_name, &version))
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn library_to_fetch(
+        &mut self,
+        library_name: &str,
+        version: &Option<ftd::p2::VersionReq>,
+        name: &str,
+        aliases: &std::collections::BTreeMap<String, String>,
+        line_number: usize,
+    ) -> ftd::p1::Result<String> {
+        let req = match version {
+            Some(req) => req,
+            None => {
+                self.lock.unversioned(library_name, name, line_number)?;
+                return Ok(library_name.to_string());
+            }
+        };
+        let doc = ftd::p2::TDoc {
+            name,
+            aliases,
+            bag: &self.bag,
+            local_variables: &mut Default::default(),
+        };
+        let available = self.lib.versions(library_name, &doc);
+        let version = self
+            .lock
+            .resolve(library_name, req, &available, name, line_number)?;
+        Ok(ftd::p2::versioned_name(library_name, &version))
+    }
+
+    // the compiled `name` in the cache, if this version of ftd compiled it
+    fn cached(&self, name: &str) -> Option<ftd::p2::cache::Compiled> {
+        ftd::p2::cache::Compiled::decode(self.cache?.get(name)?.as_slice())
+    }
+
+    // `None` if any of the `libraries` was not cached, so neither is `s`
+    fn hash(&self, s: &str, libraries: &[String]) -> Option<u64> {
+        let imports = libraries
+            .iter()
+            .map(|l| self.hashes.get(l).copied())
+            .collect::<Option<Vec<_>>>()?;
+        Some(ftd::p2::cache::hash(s, &imports))
+    }
+
+    // uses `compiled` for `name` if it was compiled from `s`, and from the
+    // libraries it imports as they are now
+    fn use_compiled(
+        &mut self,
+        name: &str,
+        s: &str,
+        libraries: &[String],
+        aliases: &std::collections::BTreeMap<String, String>,
+        compiled: ftd::p2::cache::Compiled,
+    ) -> Option<Vec<ftd::Instruction>> {
+        let hash = self.hash(s, libraries)?;
+        if hash != compiled.hash || aliases != &compiled.aliases {
+            return None;
+        }
+        self.hashes.insert(name.to_string(), hash);
+        self.bag.extend(compiled.things);
+        Some(compiled.instructions)
+    }
+
+    // stores the library `name` in the cache, `changed` are the keys it added
+    // to, or changed in, the bag
+    fn store_compiled(
+        &mut self,
+        name: &str,
+        s: &str,
+        imports: Vec<(String, Option<String>, usize)>,
+        changed: Option<std::collections::BTreeSet<String>>,
+        aliases: &std::collections::BTreeMap<String, String>,
+        instructions: &[ftd::Instruction],
+    ) {
+        let (cache, changed) = match (self.cache, changed) {
+            (Some(cache), Some(changed)) => (cache, changed),
+            _ => return,
+        };
+        let libraries = imports
+            .iter()
+            .map(|(l, _, _)| l.clone())
+            .collect::<Vec<_>>();
+        let hash = match self.hash(s, &libraries) {
+            Some(hash) => hash,
+            None => return,
+        };
+        self.hashes.insert(name.to_string(), hash);
+
+        let compiled = ftd::p2::cache::Compiled {
+            ftd: env!("CARGO_PKG_VERSION").to_string(),
+            hasher: ftd::p2::cache::HASHER.to_string(),
+            hash,
+            imports: imports.into_iter().map(|(_, c, l)| (c, l)).collect(),
+            aliases: aliases.clone(),
+            things: changed
+                .into_iter()
+                .filter_map(|k| self.bag.get(k.as_str()).map(|v| (k.to_string(), v.clone())))
+                .collect(),
+            instructions: instructions.to_vec(),
+        };
+        if let Some(compiled) = compiled.encode() {
+            cache.set(name, compiled);
+        }
+    }
+
+    // loads the libraries the compiled `name` imports, and then `name` itself
+    // if it is still up to date
+    #[cfg(feature = "async")]
+    async fn load_compiled(
+        &mut self,
+        name: &str,
+        s: &str,
+    ) -> ftd::p1::Result<Option<Vec<ftd::Instruction>>> {
+        let compiled = match self.cached(name) {
+            Some(compiled) => compiled,
+            None => return Ok(None),
+        };
+        let mut aliases = default_aliases();
+        let mut libraries = vec![];
+        for (caption, line_number) in compiled.imports.iter() {
+            let (library_name, alias, version) =
+                ftd::p2::utils::parse_import(caption, name, *line_number)?;
+            aliases.insert(alias, library_name.clone());
+            let library = self
+                .library_to_fetch(&library_name, &version, name, &aliases, *line_number)
+                .await?;
+            let start = std::time::Instant::now();
+            let doc = ftd::p2::TDoc {
+                name,
+                aliases: &aliases,
+                bag: &self.bag,
+                local_variables: &mut Default::default(),
+            };
+            let s = self.lib.get_with_result(library.as_str(), &doc).await?;
+            self.profile.imports.push(ftd::Timing::since(
+                library.as_str(),
+                name,
+                *line_number,
+                start,
+            ));
+            self.async_import_library(library_name.as_str(), s.as_str(), name, *line_number)
+                .await?;
+            libraries.push(library_name);
+        }
+        Ok(self.use_compiled(name, s, &libraries, &aliases, compiled))
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn load_compiled(
+        &mut self,
+        name: &str,
+        s: &str,
+    ) -> ftd::p1::Result<Option<Vec<ftd::Instruction>>> {
+        let compiled = match self.cached(name) {
+            Some(compiled) => compiled,
+            None => return Ok(None),
+        };
+        let mut aliases = default_aliases();
+        let mut libraries = vec![];
+        for (caption, line_number) in compiled.imports.iter() {
+            let (library_name, alias, version) =
+                ftd::p2::utils::parse_import(caption, name, *line_number)?;
+            aliases.insert(alias, library_name.clone());
+            let library =
+                self.library_to_fetch(&library_name, &version, name, &aliases, *line_number)?;
+            let start = std::time::Instant::now();
+            let doc = ftd::p2::TDoc {
+                name,
+                aliases: &aliases,
+                bag: &self.bag,
+                local_variables: &mut Default::default(),
+            };
+            let s = self.lib.get_with_result(library.as_str(), &doc)?;
+            self.profile.imports.push(ftd::Timing::since(
+                library.as_str(),
+                name,
+                *line_number,
+                start,
+            ));
+            self.import_library(library_name.as_str(), s.as_str(), name, *line_number)?;
+            libraries.push(library_name);
+        }
+        Ok(self.use_compiled(name, s, &libraries, &aliases, compiled))
+    }
+
+    #[cfg(feature = "async")]
+    #[async_recursion::async_recursion]
     async fn async_interpret_(
         &mut self,
         name: &str,
         s: &str,
         is_main: bool,
-        d_get: &mut std::time::Duration,
-        d_processor: &mut std::time::Duration,
     ) -> ftd::p1::Result<Vec<ftd::Instruction>> {
+        if !is_main {
+            if let Some(instructions) = self.load_compiled(name, s).await? {
+                return Ok(instructions);
+            }
+        }
+        // keys this library adds to, or changes in, the bag, `None` if it is
+        // not to be cached, as its processors may give something else next time
+        let mut changed = self
+            .cache
+            .filter(|_| !is_main)
+            .map(|_| std::collections::BTreeSet::new());
         let p1 = ftd::p1::parse(s, name)?;
 
         let mut aliases = default_aliases();
+        let mut imports = vec![];
         let mut iteration_index = 0;
+        let mut libraries = vec![];
         while iteration_index < p1.len() && p1[iteration_index].name == "import" {
             if p1[iteration_index].is_commented {
                 iteration_index += 1;
                 continue;
             }
-            let (library_name, alias) = ftd::p2::utils::parse_import(
-                &p1[iteration_index].caption,
-                name,
-                p1[iteration_index].line_number,
-            )?;
+            let line_number = p1[iteration_index].line_number;
+            let (library_name, alias, version) =
+                ftd::p2::utils::parse_import(&p1[iteration_index].caption, name, line_number)?;
             aliases.insert(alias, library_name.clone());
-            let start = std::time::Instant::now();
+            let library = self
+                .library_to_fetch(&library_name, &version, name, &aliases, line_number)
+                .await?;
+            imports.push((
+                library_name.clone(),
+                p1[iteration_index].caption.clone(),
+                line_number,
+            ));
+            libraries.push((library_name, library, line_number));
+            iteration_index += 1;
+        }
+
+        // the leading import block does not depend on anything in the
+        // document, so all the libraries can be fetched at the same time
+        let sources = {
+            let lib = self.lib;
             let doc = ftd::p2::TDoc {
                 name,
                 aliases: &aliases,
                 bag: &self.bag,
                 local_variables: &mut Default::default(),
             };
-            let s = self
-                .lib
-                .get_with_result(library_name.as_str(), &doc)
+            futures::future::join_all(libraries.iter().map(|(_, library, line_number)| {
+                let doc = &doc;
+                async move {
+                    let start = std::time::Instant::now();
+                    let s = lib.get_with_result(library.as_str(), doc).await;
+                    (
+                        s,
+                        ftd::Timing::since(library.as_str(), name, *line_number, start),
+                    )
+                }
+            }))
+            .await
+        };
+
+        for ((library_name, _, line_number), (s, timing)) in libraries.iter().zip(sources) {
+            self.profile.imports.push(timing);
+            let s = s?;
+            self.async_import_library(library_name.as_str(), s.as_str(), name, *line_number)
                 .await?;
-            *d_get = d_get.saturating_add(std::time::Instant::now() - start);
-            if !self.library_in_the_bag(library_name.as_str()) {
-                self.async_interpret_(library_name.as_str(), s.as_str(), false, d_get, d_processor)
-                    .await?;
-                self.add_library_to_bag(library_name.as_str())
-            }
-            iteration_index += 1;
         }
         let (new_p1, var_types) = ftd::p2::utils::reorder(
             &p1[iteration_index..],
@@ -117,11 +374,15 @@ impl<'a> Interpreter<'a> {
             if p1.is_commented {
                 continue;
             }
+            self.budget.check_time(name, p1.line_number)?;
 
             if p1.name == "import" {
-                let (library_name, alias) =
+                let (library_name, alias, version) =
                     ftd::p2::utils::parse_import(&p1.caption, name, p1.line_number)?;
                 aliases.insert(alias, library_name.clone());
+                let library = self
+                    .library_to_fetch(&library_name, &version, name, &aliases, p1.line_number)
+                    .await?;
                 let start = std::time::Instant::now();
                 let doc = ftd::p2::TDoc {
                     name,
@@ -129,22 +390,16 @@ impl<'a> Interpreter<'a> {
                     bag: &self.bag,
                     local_variables: &mut Default::default(),
                 };
-                let s = self
-                    .lib
-                    .get_with_result(library_name.as_str(), &doc)
-                    .await?;
-                *d_get = d_get.saturating_add(std::time::Instant::now() - start);
-                if !self.library_in_the_bag(library_name.as_str()) {
-                    self.async_interpret_(
-                        library_name.as_str(),
-                        s.as_str(),
-                        false,
-                        d_get,
-                        d_processor,
-                    )
+                let s = self.lib.get_with_result(library.as_str(), &doc).await?;
+                self.profile.imports.push(ftd::Timing::since(
+                    library.as_str(),
+                    name,
+                    p1.line_number,
+                    start,
+                ));
+                self.async_import_library(library_name.as_str(), s.as_str(), name, p1.line_number)
                     .await?;
-                    self.add_library_to_bag(library_name.as_str())
-                }
+                imports.push((library_name, p1.caption.clone(), p1.line_number));
                 continue;
             }
 
@@ -239,8 +494,14 @@ impl<'a> Interpreter<'a> {
                 {
                     let name = doc.resolve_name(p1.line_number, &var_data.name)?;
                     let start = std::time::Instant::now();
+                    changed = None;
                     let value = self.lib.process(p1, &doc).await?;
-                    *d_processor = d_processor.saturating_add(std::time::Instant::now() - start);
+                    self.profile.processors.push(ftd::Timing::since(
+                        p1.name.as_str(),
+                        doc.name,
+                        p1.line_number,
+                        start,
+                    ));
                     ftd::Variable {
                         name,
                         value: ftd::PropertyValue::Value { value },
@@ -297,8 +558,14 @@ impl<'a> Interpreter<'a> {
                     .is_some()
                 {
                     let start = std::time::Instant::now();
+                    changed = None;
                     let value = self.lib.process(p1, &doc).await?;
-                    *d_processor = d_processor.saturating_add(std::time::Instant::now() - start);
+                    self.profile.processors.push(ftd::Timing::since(
+                        p1.name.as_str(),
+                        doc.name,
+                        p1.line_number,
+                        start,
+                    ));
                     v.value = ftd::PropertyValue::Value { value };
                 } else {
                     v.update_from_p1(p1, &doc)?;
@@ -325,7 +592,15 @@ impl<'a> Interpreter<'a> {
                                 .str_optional(doc.name, p1.line_number, "$processor$")?
                                 .is_some()
                             {
+                                let start = std::time::Instant::now();
+                                changed = None;
                                 let value = self.lib.process(&p1, &doc).await?;
+                                self.profile.processors.push(ftd::Timing::since(
+                                    p1.name.as_str(),
+                                    doc.name,
+                                    p1.line_number,
+                                    start,
+                                ));
                                 Self::p1_from_processor(&mut p1, value);
                             }
                             p1
@@ -430,12 +705,17 @@ impl<'a> Interpreter<'a> {
                     }
                 };
             }
+            if let Some(changed) = changed.as_mut() {
+                changed.extend(thing.iter().map(|(k, _)| k.to_string()));
+            }
             self.bag.extend(thing);
         }
 
         if is_main {
             self.p1 = p1;
             self.aliases = aliases;
+        } else {
+            self.store_compiled(name, s, imports, changed, &aliases, &instructions);
         }
         Ok(instructions)
     }
@@ -446,25 +726,35 @@ impl<'a> Interpreter<'a> {
         name: &str,
         s: &str,
         is_main: bool,
-        d_get: &mut std::time::Duration,
-        d_processor: &mut std::time::Duration,
     ) -> ftd::p1::Result<Vec<ftd::Instruction>> {
+        if !is_main {
+            if let Some(instructions) = self.load_compiled(name, s)? {
+                return Ok(instructions);
+            }
+        }
+        // keys this library adds to, or changes in, the bag, `None` if it is
+        // not to be cached, as its processors may give something else next time
+        let mut changed = self
+            .cache
+            .filter(|_| !is_main)
+            .map(|_| std::collections::BTreeSet::new());
         let p1 = ftd::p1::parse(s, name)?;
 
         // do all imports and then reorder
         let mut aliases = default_aliases();
+        let mut imports = vec![];
         let mut iteration_index = 0;
         while iteration_index < p1.len() && p1[iteration_index].name == "import" {
             if p1[iteration_index].is_commented {
                 iteration_index += 1;
                 continue;
             }
-            let (library_name, alias) = ftd::p2::utils::parse_import(
-                &p1[iteration_index].caption,
-                name,
-                p1[iteration_index].line_number,
-            )?;
+            let line_number = p1[iteration_index].line_number;
+            let (library_name, alias, version) =
+                ftd::p2::utils::parse_import(&p1[iteration_index].caption, name, line_number)?;
             aliases.insert(alias, library_name.clone());
+            let library =
+                self.library_to_fetch(&library_name, &version, name, &aliases, line_number)?;
             let start = std::time::Instant::now();
             let doc = ftd::p2::TDoc {
                 name,
@@ -472,12 +762,19 @@ impl<'a> Interpreter<'a> {
                 bag: &self.bag,
                 local_variables: &mut Default::default(),
             };
-            let s = self.lib.get_with_result(library_name.as_str(), &doc)?;
-            *d_get = d_get.saturating_add(std::time::Instant::now() - start);
-            if !self.library_in_the_bag(library_name.as_str()) {
-                self.interpret_(library_name.as_str(), s.as_str(), false, d_get, d_processor)?;
-                self.add_library_to_bag(library_name.as_str())
-            }
+            let s = self.lib.get_with_result(library.as_str(), &doc)?;
+            self.profile.imports.push(ftd::Timing::since(
+                library.as_str(),
+                name,
+                line_number,
+                start,
+            ));
+            self.import_library(library_name.as_str(), s.as_str(), name, line_number)?;
+            imports.push((
+                library_name,
+                p1[iteration_index].caption.clone(),
+                line_number,
+            ));
             iteration_index += 1;
         }
         let (new_p1, var_types) = ftd::p2::utils::reorder(
@@ -496,11 +793,14 @@ impl<'a> Interpreter<'a> {
             if p1.is_commented {
                 continue;
             }
+            self.budget.check_time(name, p1.line_number)?;
 
             if p1.name == "import" {
-                let (library_name, alias) =
+                let (library_name, alias, version) =
                     ftd::p2::utils::parse_import(&p1.caption, name, p1.line_number)?;
                 aliases.insert(alias, library_name.clone());
+                let library =
+                    self.library_to_fetch(&library_name, &version, name, &aliases, p1.line_number)?;
                 let start = std::time::Instant::now();
                 let doc = ftd::p2::TDoc {
                     name,
@@ -508,12 +808,15 @@ impl<'a> Interpreter<'a> {
                     bag: &self.bag,
                     local_variables: &mut Default::default(),
                 };
-                let s = self.lib.get_with_result(library_name.as_str(), &doc)?;
-                *d_get = d_get.saturating_add(std::time::Instant::now() - start);
-                if !self.library_in_the_bag(library_name.as_str()) {
-                    self.interpret_(library_name.as_str(), s.as_str(), false, d_get, d_processor)?;
-                    self.add_library_to_bag(library_name.as_str())
-                }
+                let s = self.lib.get_with_result(library.as_str(), &doc)?;
+                self.profile.imports.push(ftd::Timing::since(
+                    library.as_str(),
+                    name,
+                    p1.line_number,
+                    start,
+                ));
+                self.import_library(library_name.as_str(), s.as_str(), name, p1.line_number)?;
+                imports.push((library_name, p1.caption.clone(), p1.line_number));
                 continue;
             }
 
@@ -608,8 +911,14 @@ impl<'a> Interpreter<'a> {
                 {
                     let name = doc.resolve_name(p1.line_number, &var_data.name)?;
                     let start = std::time::Instant::now();
+                    changed = None;
                     let value = self.lib.process(p1, &doc)?;
-                    *d_processor = d_processor.saturating_add(std::time::Instant::now() - start);
+                    self.profile.processors.push(ftd::Timing::since(
+                        p1.name.as_str(),
+                        doc.name,
+                        p1.line_number,
+                        start,
+                    ));
                     ftd::Variable {
                         name,
                         value: ftd::PropertyValue::Value { value },
@@ -666,8 +975,14 @@ impl<'a> Interpreter<'a> {
                     .is_some()
                 {
                     let start = std::time::Instant::now();
+                    changed = None;
                     let value = self.lib.process(p1, &doc)?;
-                    *d_processor = d_processor.saturating_add(std::time::Instant::now() - start);
+                    self.profile.processors.push(ftd::Timing::since(
+                        p1.name.as_str(),
+                        doc.name,
+                        p1.line_number,
+                        start,
+                    ));
                     v.value = ftd::PropertyValue::Value { value };
                 } else {
                     v.update_from_p1(p1, &doc)?;
@@ -694,7 +1009,15 @@ impl<'a> Interpreter<'a> {
                                 .str_optional(doc.name, p1.line_number, "$processor$")?
                                 .is_some()
                             {
+                                let start = std::time::Instant::now();
+                                changed = None;
                                 let value = self.lib.process(&p1, &doc)?;
+                                self.profile.processors.push(ftd::Timing::since(
+                                    p1.name.as_str(),
+                                    doc.name,
+                                    p1.line_number,
+                                    start,
+                                ));
                                 Self::p1_from_processor(&mut p1, value);
                             }
                             p1
@@ -799,16 +1122,69 @@ impl<'a> Interpreter<'a> {
                     }
                 };
             }
+            if let Some(changed) = changed.as_mut() {
+                changed.extend(thing.iter().map(|(k, _)| k.to_string()));
+            }
             self.bag.extend(thing);
         }
 
         if is_main {
             self.p1 = p1;
             self.aliases = aliases;
+        } else {
+            self.store_compiled(name, s, imports, changed, &aliases, &instructions);
         }
         Ok(instructions)
     }
 
+    /// interprets the imported `library` unless it is already in the bag, the
+    /// import depth is restored even if interpreting it fails
+    #[cfg(feature = "async")]
+    async fn async_import_library(
+        &mut self,
+        library: &str,
+        s: &str,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::p1::Result<()> {
+        if self.library_in_the_bag(library) {
+            return Ok(());
+        }
+        self.budget
+            .limits
+            .check_import_depth(self.import_depth + 1, doc_id, line_number)?;
+        self.import_depth += 1;
+        let r = self.async_interpret_(library, s, false).await;
+        self.import_depth -= 1;
+        r?;
+        self.add_library_to_bag(library);
+        Ok(())
+    }
+
+    /// interprets the imported `library` unless it is already in the bag, the
+    /// import depth is restored even if interpreting it fails
+    #[cfg(not(feature = "async"))]
+    fn import_library(
+        &mut self,
+        library: &str,
+        s: &str,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::p1::Result<()> {
+        if self.library_in_the_bag(library) {
+            return Ok(());
+        }
+        self.budget
+            .limits
+            .check_import_depth(self.import_depth + 1, doc_id, line_number)?;
+        self.import_depth += 1;
+        let r = self.interpret_(library, s, false);
+        self.import_depth -= 1;
+        r?;
+        self.add_library_to_bag(library);
+        Ok(())
+    }
+
     pub(crate) fn new(lib: &'a dyn ftd::p2::Library) -> Self {
         Self {
             lib,
@@ -816,6 +1192,15 @@ impl<'a> Interpreter<'a> {
             p1: Default::default(),
             aliases: Default::default(),
             parsed_libs: Default::default(),
+            profile: Default::default(),
+            budget: Default::default(),
+            plugins: Default::default(),
+            embed_providers: Default::default(),
+            icon_sets: Default::default(),
+            import_depth: 0,
+            lock: Default::default(),
+            cache: None,
+            hashes: Default::default(),
         }
     }
 
@@ -855,6 +1240,9 @@ pub fn interpret(
     let mut interpreter = Interpreter::new(lib);
     let instructions = interpreter.interpret(name, source)?;
     let mut rt = ftd::RT::from(name, interpreter.aliases, interpreter.bag, instructions);
+    rt.plugins = interpreter.plugins;
+    rt.embed_providers = interpreter.embed_providers;
+    rt.icon_sets = interpreter.icon_sets;
     let main = rt.render_()?;
     Ok((rt.bag, main))
 }
@@ -881,6 +1269,38 @@ pub fn default_bag() -> std::collections::BTreeMap<String, ftd::p2::Thing> {
             "ftd#column".to_string(),
             ftd::p2::Thing::Component(ftd::p2::element::column_function()),
         ),
+        (
+            "ftd#form".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::form_function()),
+        ),
+        (
+            "ftd#dialog".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::overlay_function("ftd#dialog")),
+        ),
+        (
+            "ftd#popover".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::overlay_function("ftd#popover")),
+        ),
+        (
+            "ftd#tooltip".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::overlay_function("ftd#tooltip")),
+        ),
+        (
+            "ftd#tabs".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::disclosure_function("ftd#tabs")),
+        ),
+        (
+            "ftd#accordion".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::disclosure_function("ftd#accordion")),
+        ),
+        (
+            "ftd#panel".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::disclosure_function("ftd#panel")),
+        ),
+        (
+            "ftd#details".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::disclosure_function("ftd#details")),
+        ),
         /*(
             "ftd#text".to_string(),
             ftd::p2::Thing::Component(ftd::p2::element::text_function(false)),
@@ -901,6 +1321,10 @@ pub fn default_bag() -> std::collections::BTreeMap<String, ftd::p2::Thing> {
             "ftd#iframe".to_string(),
             ftd::p2::Thing::Component(ftd::p2::element::iframe_function()),
         ),
+        (
+            "ftd#toc".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::toc_function()),
+        ),
         (
             "ftd#integer".to_string(),
             ftd::p2::Thing::Component(ftd::p2::element::integer_function()),
@@ -929,6 +1353,42 @@ pub fn default_bag() -> std::collections::BTreeMap<String, ftd::p2::Thing> {
             "ftd#input".to_string(),
             ftd::p2::Thing::Component(ftd::p2::element::input_function()),
         ),
+        (
+            "ftd#checkbox".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::checkbox_function("ftd#checkbox")),
+        ),
+        (
+            "ftd#toggle".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::checkbox_function("ftd#toggle")),
+        ),
+        (
+            "ftd#radio".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::select_function("ftd#radio")),
+        ),
+        (
+            "ftd#select".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::select_function("ftd#select")),
+        ),
+        (
+            "ftd#table".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::table_function()),
+        ),
+        (
+            "ftd#svg".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::svg_function("ftd#svg")),
+        ),
+        (
+            "ftd#icon".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::svg_function("ftd#icon")),
+        ),
+        (
+            "ftd#video".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::media_function("ftd#video")),
+        ),
+        (
+            "ftd#audio".to_string(),
+            ftd::p2::Thing::Component(ftd::p2::element::media_function("ftd#audio")),
+        ),
         (
             "ftd#null".to_string(),
             ftd::p2::Thing::Component(ftd::p2::element::null()),
@@ -1027,6 +1487,59 @@ pub fn default_bag() -> std::collections::BTreeMap<String, ftd::p2::Thing> {
                 order: vec!["light".to_string(), "dark".to_string()],
             }),
         ),
+        (
+            "ftd#track".to_string(),
+            ftd::p2::Thing::Record(ftd::p2::Record {
+                name: "ftd#track".to_string(),
+                fields: std::array::IntoIter::new([
+                    ("src".to_string(), ftd::p2::Kind::caption()),
+                    (
+                        "kind".to_string(),
+                        ftd::p2::Kind::string().set_default(Some("captions".to_string())),
+                    ),
+                    ("lang".to_string(), ftd::p2::Kind::string().into_optional()),
+                    ("label".to_string(), ftd::p2::Kind::string().into_optional()),
+                    (
+                        "default".to_string(),
+                        ftd::p2::Kind::boolean().set_default(Some("false".to_string())),
+                    ),
+                ])
+                .collect(),
+                instances: Default::default(),
+                order: vec![
+                    "src".to_string(),
+                    "kind".to_string(),
+                    "lang".to_string(),
+                    "label".to_string(),
+                    "default".to_string(),
+                ],
+            }),
+        ),
+        (
+            "ftd#table-column".to_string(),
+            ftd::p2::Thing::Record(ftd::p2::Record {
+                name: "ftd#table-column".to_string(),
+                fields: std::array::IntoIter::new([
+                    ("caption".to_string(), ftd::p2::Kind::caption()),
+                    ("field".to_string(), ftd::p2::Kind::string()),
+                    ("width".to_string(), ftd::p2::Kind::string().into_optional()),
+                    ("align".to_string(), ftd::p2::Kind::string().into_optional()),
+                    (
+                        "component".to_string(),
+                        ftd::p2::Kind::string().into_optional(),
+                    ),
+                ])
+                .collect(),
+                instances: Default::default(),
+                order: vec![
+                    "caption".to_string(),
+                    "field".to_string(),
+                    "width".to_string(),
+                    "align".to_string(),
+                    "component".to_string(),
+                ],
+            }),
+        ),
         (
             "ftd#color".to_string(),
             ftd::p2::Thing::Record(ftd::p2::Record {
@@ -1584,6 +2097,7 @@ mod test {
                     )])
                     .collect(),
                     reference: Some(s("foo/bar#name@0")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -2504,6 +3018,7 @@ mod test {
                             common: ftd::Common {
                                 data_id: Some(s("/ChildBuilding/")),
                                 width: Some(ftd::Length::Fill),
+                                components: vec![s("foo/bar#parent")],
                                 ..Default::default()
                             },
                         }),
@@ -2515,6 +3030,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("/Building/")),
                     width: Some(ftd::Length::Fill),
+                    components: vec![s("foo/bar#parent")],
                     ..Default::default()
                 },
             }),
@@ -2587,6 +3103,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("/Building2/")),
                     width: Some(ftd::Length::Fill),
+                    components: vec![s("foo/bar#parent")],
                     ..Default::default()
                 },
             }),
@@ -2612,6 +3129,7 @@ mod test {
                                 common: ftd::Common {
                                     data_id: Some(s("/welcome/")),
                                     width: Some(ftd::Length::Fill),
+                                    components: vec![s("foo/bar#parent")],
                                     ..Default::default()
                                 },
                             })],
@@ -2621,11 +3139,16 @@ mod test {
                             data_id: Some(s("toc_main")),
                             height: Some(ftd::Length::Fill),
                             width: Some(ftd::Length::Px { value: 300 }),
+                            components: vec![s("foo/bar#table-of-content")],
                             ..Default::default()
                         },
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#ft_toc")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -3574,6 +4097,7 @@ mod test {
                             common: ftd::Common {
                                 data_id: Some(s("/ChildBuilding/")),
                                 width: Some(ftd::Length::Fill),
+                                components: vec![s("creating-a-tree#parent")],
                                 ..Default::default()
                             },
                         }),
@@ -3585,6 +4109,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("/Building/")),
                     width: Some(ftd::Length::Fill),
+                    components: vec![s("creating-a-tree#parent")],
                     ..Default::default()
                 },
             }),
@@ -3658,6 +4183,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("/Building2/")),
                     width: Some(ftd::Length::Fill),
+                    components: vec![s("creating-a-tree#parent")],
                     ..Default::default()
                 },
             }),
@@ -3683,6 +4209,7 @@ mod test {
                                 common: ftd::Common {
                                     data_id: Some(s("/welcome/")),
                                     width: Some(ftd::Length::Fill),
+                                    components: vec![s("creating-a-tree#parent")],
                                     ..Default::default()
                                 },
                             })],
@@ -3692,11 +4219,16 @@ mod test {
                             data_id: Some(s("toc_main")),
                             height: Some(ftd::Length::Fill),
                             width: Some(ftd::Length::Px { value: 300 }),
+                            components: vec![s("creating-a-tree#table-of-content")],
                             ..Default::default()
                         },
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("creating-a-tree#ft_toc")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -3909,6 +4441,7 @@ mod test {
                         },
                         reference: Some(s("reference#f3f3f3")),
                     }),
+                    components: vec![s("reference#test-component")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -3954,30 +4487,42 @@ mod test {
                 )])
                 .collect(),
                 invocations: vec![
-                    std::array::IntoIter::new([(
-                        s("name"),
-                        ftd::Value::String {
-                            text: s("hello"),
-                            source: ftd::TextSource::Caption,
-                        },
-                    )])
-                    .collect(),
-                    std::array::IntoIter::new([(
-                        s("name"),
-                        ftd::Value::String {
-                            text: s("world"),
-                            source: ftd::TextSource::Header,
-                        },
-                    )])
-                    .collect(),
-                    std::array::IntoIter::new([(
-                        s("name"),
-                        ftd::Value::String {
-                            text: s("yo yo"),
-                            source: ftd::TextSource::Body,
-                        },
-                    )])
-                    .collect(),
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 5,
+                        arguments: std::array::IntoIter::new([(
+                            s("name"),
+                            ftd::Value::String {
+                                text: s("hello"),
+                                source: ftd::TextSource::Caption,
+                            },
+                        )])
+                        .collect(),
+                    },
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 7,
+                        arguments: std::array::IntoIter::new([(
+                            s("name"),
+                            ftd::Value::String {
+                                text: s("world"),
+                                source: ftd::TextSource::Header,
+                            },
+                        )])
+                        .collect(),
+                    },
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 10,
+                        arguments: std::array::IntoIter::new([(
+                            s("name"),
+                            ftd::Value::String {
+                                text: s("yo yo"),
+                                source: ftd::TextSource::Body,
+                            },
+                        )])
+                        .collect(),
+                    },
                 ],
                 line_number: 1,
                 ..Default::default()
@@ -4034,6 +4579,7 @@ mod test {
                 line: true,
                 common: ftd::Common {
                     reference: Some(s("foo/bar#name@0")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -4045,6 +4591,7 @@ mod test {
                 line: true,
                 common: ftd::Common {
                     reference: Some(s("foo/bar#name@1")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -4056,6 +4603,7 @@ mod test {
                 line: true,
                 common: ftd::Common {
                     reference: Some(s("foo/bar#name@2")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -4582,6 +5130,7 @@ mod test {
                 spacing: None,
                 common: ftd::Common {
                     padding: Some(30),
+                    components: vec![s("foo/bar#white-two-image")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -4796,6 +5345,7 @@ mod test {
                 spacing: None,
                 common: ftd::Common {
                     padding: Some(30),
+                    components: vec![s("foo/bar#white-two-image")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -4813,6 +5363,7 @@ mod test {
                 spacing: None,
                 common: ftd::Common {
                     padding: Some(30),
+                    components: vec![s("foo/bar#white-two-image")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -5155,6 +5706,7 @@ mod test {
                 spacing: None,
                 common: ftd::Common {
                     padding: Some(30),
+                    components: vec![s("foo/bar#white-two-image")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -5172,6 +5724,7 @@ mod test {
                 spacing: None,
                 common: ftd::Common {
                     padding: Some(30),
+                    components: vec![s("foo/bar#white-two-image")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -5189,6 +5742,7 @@ mod test {
                 spacing: None,
                 common: ftd::Common {
                     padding: Some(30),
+                    components: vec![s("foo/bar#white-two-image")],
                     ..Default::default()
                 },
                 container: ftd::Container {
@@ -5667,6 +6221,7 @@ mod test {
                                     value: s("$IsNotNull$"),
                                 }),
                                 reference: Some(s("foo/bar#body@0,1")),
+                                components: vec![s("fifthtry/ft#markdown")],
                                 ..Default::default()
                             },
                             ..Default::default()
@@ -5674,6 +6229,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#h0")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
         main.container
@@ -5701,6 +6260,7 @@ mod test {
                                 }),
                                 reference: Some(s("foo/bar#body@1,1")),
                                 is_not_visible: true,
+                                components: vec![s("fifthtry/ft#markdown")],
                                 ..Default::default()
                             },
                             ..Default::default()
@@ -5708,6 +6268,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#h0")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -5942,6 +6506,10 @@ mod test {
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#image")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
         main.container
@@ -5960,6 +6528,10 @@ mod test {
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#image")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -6074,7 +6646,13 @@ mod test {
         );
 
         let mut main = super::default_column();
-        let mut row: ftd::Row = Default::default();
+        let mut row = ftd::Row {
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         row.container
             .children
             .push(ftd::Element::Decimal(ftd::Text {
@@ -6193,9 +6771,15 @@ mod test {
         );
 
         let mut main = super::default_column();
-        let mut row: ftd::Row = Default::default();
-        row.container
-            .children
+        let mut row = ftd::Row {
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        row.container
+            .children
             .push(ftd::Element::Integer(ftd::Text {
                 text: ftd::markdown_line("11"),
                 line: false,
@@ -6353,7 +6937,13 @@ mod test {
         );
 
         let mut main = super::default_column();
-        let mut row: ftd::Row = Default::default();
+        let mut row = ftd::Row {
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         row.container
             .children
             .push(ftd::Element::Boolean(ftd::Text {
@@ -6457,7 +7047,13 @@ mod test {
                 ..Default::default()
             }));
 
-        let mut column: ftd::Column = Default::default();
+        let mut column = ftd::Column {
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         column
             .container
             .children
@@ -6493,7 +7089,13 @@ mod test {
 
         main.container.children.push(ftd::Element::Column(column));
 
-        let mut column: ftd::Column = Default::default();
+        let mut column = ftd::Column {
+            common: ftd::Common {
+                components: vec![s("foo/bar#bar")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         column
             .container
             .children
@@ -6528,7 +7130,13 @@ mod test {
 
         main.container.children.push(ftd::Element::Column(column));
 
-        let mut column: ftd::Column = Default::default();
+        let mut column = ftd::Column {
+            common: ftd::Common {
+                components: vec![s("foo/bar#bar")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         column
             .container
             .children
@@ -6563,7 +7171,13 @@ mod test {
 
         main.container.children.push(ftd::Element::Column(column));
 
-        let mut column: ftd::Column = Default::default();
+        let mut column = ftd::Column {
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo2")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
         column
             .container
             .children
@@ -6769,6 +7383,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("foo-1")),
                     id: Some(s("foo-1")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -6803,6 +7418,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("foo-2")),
                     id: Some(s("foo-2")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -6929,6 +7545,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("foo-1")),
                     id: Some(s("foo-1")),
+                    components: vec![s("inner_container#foo")],
                     ..Default::default()
                 },
             }));
@@ -6963,6 +7580,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("foo-2")),
                     id: Some(s("foo-2")),
+                    components: vec![s("inner_container#foo")],
                     ..Default::default()
                 },
             }));
@@ -7024,6 +7642,10 @@ mod test {
                     append_at: Some(s("some-child")),
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -7179,6 +7801,7 @@ mod test {
                                                 }),
                                                 data_id: Some(s("some-child")),
                                                 id: Some(s("foo-id:some-child")),
+                                                components: vec![s("foo/bar#mobile-display")],
                                                 ..Default::default()
                                             },
                                         }),
@@ -7202,6 +7825,7 @@ mod test {
                                                 is_not_visible: true,
                                                 data_id: Some(s("some-child")),
                                                 id: Some(s("foo-id:some-child")),
+                                                components: vec![s("foo/bar#desktop-display")],
                                                 ..Default::default()
                                             },
                                         }),
@@ -7218,6 +7842,7 @@ mod test {
                                 common: ftd::Common {
                                     id: Some(s("foo-id")),
                                     data_id: Some(s("foo-id")),
+                                    components: vec![s("foo/bar#foo")],
                                     ..Default::default()
                                 },
                             })],
@@ -7601,6 +8226,7 @@ mod test {
                                         }),
                                         is_not_visible: true,
                                         data_id: Some(s("main-container")),
+                                        components: vec![s("foo/bar#desktop")],
                                         ..Default::default()
                                     },
                                 }),
@@ -7630,6 +8256,7 @@ mod test {
                                             value: s("true"),
                                         }),
                                         data_id: Some(s("main-container")),
+                                        components: vec![s("foo/bar#mobile")],
                                         ..Default::default()
                                     },
                                 }),
@@ -7650,6 +8277,10 @@ mod test {
                     append_at: Some(s("main-container")),
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#page")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -7748,6 +8379,7 @@ mod test {
                                 }),
                                 is_not_visible: true,
                                 data_id: Some(s("main-container")),
+                                components: vec![s("foo/bar#desktop")],
                                 ..Default::default()
                             },
                         }),
@@ -7770,6 +8402,7 @@ mod test {
                                     value: s("true"),
                                 }),
                                 data_id: Some(s("main-container")),
+                                components: vec![s("foo/bar#mobile")],
                                 ..Default::default()
                             },
                         }),
@@ -7783,6 +8416,10 @@ mod test {
                     append_at: Some(s("main-container.foo")),
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#page")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -7859,6 +8496,7 @@ mod test {
                             spacing: None,
                             common: ftd::Common {
                                 data_id: Some(s("foo")),
+                                components: vec![s("foo/bar#ft_container")],
                                 ..Default::default()
                             },
                             ..Default::default()
@@ -7879,6 +8517,10 @@ mod test {
                 append_at: Some(s("desktop-container")),
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#desktop")],
+                ..Default::default()
+            },
             ..Default::default()
         })];
 
@@ -7899,6 +8541,7 @@ mod test {
                                             spacing: None,
                                             common: ftd::Common {
                                                 data_id: Some(s("foo")),
+                                                components: vec![s("foo/bar#ft_container")],
                                                 ..Default::default()
                                             },
                                             ..Default::default()
@@ -7925,6 +8568,7 @@ mod test {
                                     value: s("false"),
                                 }),
                                 data_id: Some(s("main-container")),
+                                components: vec![s("foo/bar#desktop")],
                                 ..Default::default()
                             },
                             ..Default::default()
@@ -7939,6 +8583,7 @@ mod test {
                                             spacing: None,
                                             common: ftd::Common {
                                                 data_id: Some(s("foo")),
+                                                components: vec![s("foo/bar#ft_container_mobile")],
                                                 ..Default::default()
                                             },
                                             ..Default::default()
@@ -7966,6 +8611,7 @@ mod test {
                                 }),
                                 is_not_visible: true,
                                 data_id: Some(s("main-container")),
+                                components: vec![s("foo/bar#mobile")],
                                 ..Default::default()
                             },
                         }),
@@ -7979,6 +8625,10 @@ mod test {
                     append_at: Some(s("main-container.foo")),
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#page")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -8332,6 +8982,10 @@ mod test {
                 ],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -8362,6 +9016,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#people")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -8393,6 +9048,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#people")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -8786,6 +9442,7 @@ mod test {
                     value: s("true"),
                 }),
                 is_not_visible: true,
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
             ..Default::default()
@@ -8822,6 +9479,7 @@ mod test {
                     value: s("true"),
                 }),
                 reference: Some(s("foo/bar#people")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
             ..Default::default()
@@ -9356,6 +10014,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#people")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -9387,6 +10046,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#people")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -9450,40 +10110,48 @@ mod test {
                     },
                 ],
                 invocations: vec![
-                    std::array::IntoIter::new([
-                        (
-                            s("body"),
-                            ftd::Value::String {
-                                text: s("Arpita is developer at Fifthtry"),
-                                source: ftd::TextSource::Body,
-                            },
-                        ),
-                        (
-                            s("name"),
-                            ftd::Value::String {
-                                text: s("Arpita Jaiswal"),
-                                source: ftd::TextSource::Caption,
-                            },
-                        ),
-                    ])
-                    .collect(),
-                    std::array::IntoIter::new([
-                        (
-                            s("body"),
-                            ftd::Value::String {
-                                text: s("Amit is CEO of FifthTry."),
-                                source: ftd::TextSource::Body,
-                            },
-                        ),
-                        (
-                            s("name"),
-                            ftd::Value::String {
-                                text: s("Amit Upadhyay"),
-                                source: ftd::TextSource::Caption,
-                            },
-                        ),
-                    ])
-                    .collect(),
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 25,
+                        arguments: std::array::IntoIter::new([
+                            (
+                                s("body"),
+                                ftd::Value::String {
+                                    text: s("Arpita is developer at Fifthtry"),
+                                    source: ftd::TextSource::Body,
+                                },
+                            ),
+                            (
+                                s("name"),
+                                ftd::Value::String {
+                                    text: s("Arpita Jaiswal"),
+                                    source: ftd::TextSource::Caption,
+                                },
+                            ),
+                        ])
+                        .collect(),
+                    },
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 25,
+                        arguments: std::array::IntoIter::new([
+                            (
+                                s("body"),
+                                ftd::Value::String {
+                                    text: s("Amit is CEO of FifthTry."),
+                                    source: ftd::TextSource::Body,
+                                },
+                            ),
+                            (
+                                s("name"),
+                                ftd::Value::String {
+                                    text: s("Amit Upadhyay"),
+                                    source: ftd::TextSource::Caption,
+                                },
+                            ),
+                        ])
+                        .collect(),
+                    },
                 ],
                 ..Default::default()
             }),
@@ -10076,6 +10744,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10107,6 +10776,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10138,6 +10808,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10169,6 +10840,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10200,6 +10872,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10231,6 +10904,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10262,6 +10936,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10293,6 +10968,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#test")),
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -10663,6 +11339,10 @@ mod test {
                             })],
                             ..Default::default()
                         },
+                        common: ftd::Common {
+                            components: vec![s("foo/bar#toc-item")],
+                            ..Default::default()
+                        },
                         ..Default::default()
                     }),
                     ftd::Element::Column(ftd::Column {
@@ -10680,6 +11360,10 @@ mod test {
                             })],
                             ..Default::default()
                         },
+                        common: ftd::Common {
+                            components: vec![s("foo/bar#toc-item")],
+                            ..Default::default()
+                        },
                         ..Default::default()
                     }),
                 ],
@@ -10687,6 +11371,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#toc")),
+                components: vec![s("foo/bar#toc-item")],
                 ..Default::default()
             },
             ..Default::default()
@@ -10720,6 +11405,10 @@ mod test {
                             })],
                             ..Default::default()
                         },
+                        common: ftd::Common {
+                            components: vec![s("foo/bar#toc-item")],
+                            ..Default::default()
+                        },
                         ..Default::default()
                     }),
                     ftd::Element::Column(ftd::Column {
@@ -10737,6 +11426,10 @@ mod test {
                             })],
                             ..Default::default()
                         },
+                        common: ftd::Common {
+                            components: vec![s("foo/bar#toc-item")],
+                            ..Default::default()
+                        },
                         ..Default::default()
                     }),
                 ],
@@ -10744,6 +11437,7 @@ mod test {
             },
             common: ftd::Common {
                 reference: Some(s("foo/bar#toc")),
+                components: vec![s("foo/bar#toc-item")],
                 ..Default::default()
             },
             ..Default::default()
@@ -10755,6 +11449,10 @@ mod test {
                 children: vec![col1],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -11153,6 +11851,10 @@ mod test {
                 })],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("hello-world#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -11222,6 +11924,7 @@ mod test {
                 line_clamp: Some(10),
                 common: ftd::Common {
                     reference: Some(s("foo/bar#name@0")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -11235,6 +11938,7 @@ mod test {
                 line_clamp: Some(10),
                 common: ftd::Common {
                     reference: Some(s("foo/bar#name@1")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -11248,6 +11952,7 @@ mod test {
                 line_clamp: Some(20),
                 common: ftd::Common {
                     reference: Some(s("foo/bar#name@2")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -11303,39 +12008,51 @@ mod test {
                 ])
                 .collect(),
                 invocations: vec![
-                    std::array::IntoIter::new([
-                        (
-                            s("name"),
-                            ftd::Value::String {
-                                text: s("hello world"),
-                                source: ftd::TextSource::Default,
-                            },
-                        ),
-                        (s("line-clamp"), ftd::Value::Integer { value: 10 }),
-                    ])
-                    .collect(),
-                    std::array::IntoIter::new([
-                        (
-                            s("name"),
-                            ftd::Value::String {
-                                text: s("hello"),
-                                source: ftd::TextSource::Caption,
-                            },
-                        ),
-                        (s("line-clamp"), ftd::Value::Integer { value: 10 }),
-                    ])
-                    .collect(),
-                    std::array::IntoIter::new([
-                        (
-                            s("name"),
-                            ftd::Value::String {
-                                text: s("this is nice"),
-                                source: ftd::TextSource::Caption,
-                            },
-                        ),
-                        (s("line-clamp"), ftd::Value::Integer { value: 20 }),
-                    ])
-                    .collect(),
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 7,
+                        arguments: std::array::IntoIter::new([
+                            (
+                                s("name"),
+                                ftd::Value::String {
+                                    text: s("hello world"),
+                                    source: ftd::TextSource::Default,
+                                },
+                            ),
+                            (s("line-clamp"), ftd::Value::Integer { value: 10 }),
+                        ])
+                        .collect(),
+                    },
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 9,
+                        arguments: std::array::IntoIter::new([
+                            (
+                                s("name"),
+                                ftd::Value::String {
+                                    text: s("hello"),
+                                    source: ftd::TextSource::Caption,
+                                },
+                            ),
+                            (s("line-clamp"), ftd::Value::Integer { value: 10 }),
+                        ])
+                        .collect(),
+                    },
+                    ftd::Invocation {
+                        doc_id: s("foo/bar"),
+                        line_number: 11,
+                        arguments: std::array::IntoIter::new([
+                            (
+                                s("name"),
+                                ftd::Value::String {
+                                    text: s("this is nice"),
+                                    source: ftd::TextSource::Caption,
+                                },
+                            ),
+                            (s("line-clamp"), ftd::Value::Integer { value: 20 }),
+                        ])
+                        .collect(),
+                    },
                 ],
                 line_number: 1,
                 ..Default::default()
@@ -11622,6 +12339,10 @@ mod test {
                 })],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
         main.container.children.push(ftd::Element::Row(ftd::Row {
@@ -11639,6 +12360,10 @@ mod test {
                 })],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -12047,6 +12772,7 @@ mod test {
                                         },
                                         common: ftd::Common {
                                             data_id: Some(s("display-text-id")),
+                                            components: vec![s("foo/bar#display-text")],
                                             ..Default::default()
                                         },
                                     })],
@@ -12058,11 +12784,16 @@ mod test {
                         },
                         common: ftd::Common {
                             data_id: Some(s("inside-page-id")),
+                            components: vec![s("foo/bar#inside-page")],
                             ..Default::default()
                         },
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#page")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -12095,6 +12826,7 @@ mod test {
                                                 id: Some(s(
                                                     "page-id:inside-page-id:display-text-id",
                                                 )),
+                                                components: vec![s("foo/bar#display-text")],
                                                 ..Default::default()
                                             },
                                         })],
@@ -12107,6 +12839,7 @@ mod test {
                             common: ftd::Common {
                                 data_id: Some(s("inside-page-id")),
                                 id: Some(s("page-id:inside-page-id")),
+                                components: vec![s("foo/bar#inside-page")],
                                 ..Default::default()
                             },
                         }),
@@ -12125,6 +12858,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("page-id")),
                     id: Some(s("page-id")),
+                    components: vec![s("foo/bar#page")],
                     ..Default::default()
                 },
             }));
@@ -12201,6 +12935,7 @@ mod test {
                 common: ftd::Common {
                     region: Some(ftd::Region::H3),
                     id: Some(s("heading-31")),
+                    components: vec![s("foo/bar#h3")],
                     ..Default::default()
                 },
             }));
@@ -12260,6 +12995,7 @@ mod test {
                                         common: ftd::Common {
                                             region: Some(ftd::Region::H3),
                                             id: Some(s("heading-32")),
+                                            components: vec![s("foo/bar#h3")],
                                             ..Default::default()
                                         },
                                     }),
@@ -12269,6 +13005,7 @@ mod test {
                             common: ftd::Common {
                                 region: Some(ftd::Region::H2),
                                 id: Some(s("heading-21")),
+                                components: vec![s("foo/bar#h2")],
                                 ..Default::default()
                             },
                         }),
@@ -12290,6 +13027,7 @@ mod test {
                             common: ftd::Common {
                                 region: Some(ftd::Region::H2),
                                 id: Some(s("heading-22")),
+                                components: vec![s("foo/bar#h2")],
                                 ..Default::default()
                             },
                         }),
@@ -12311,6 +13049,7 @@ mod test {
                             common: ftd::Common {
                                 region: Some(ftd::Region::H2),
                                 id: Some(s("heading-23")),
+                                components: vec![s("foo/bar#h2")],
                                 ..Default::default()
                             },
                         }),
@@ -12320,6 +13059,7 @@ mod test {
                 common: ftd::Common {
                     region: Some(ftd::Region::H1),
                     id: Some(s("heading-11")),
+                    components: vec![s("foo/bar#h1")],
                     ..Default::default()
                 },
             }));
@@ -12358,6 +13098,7 @@ mod test {
                             common: ftd::Common {
                                 region: Some(ftd::Region::H3),
                                 id: Some(s("heading-33")),
+                                components: vec![s("foo/bar#h3")],
                                 ..Default::default()
                             },
                         }),
@@ -12379,6 +13120,7 @@ mod test {
                             common: ftd::Common {
                                 region: Some(ftd::Region::H2),
                                 id: Some(s("heading-24")),
+                                components: vec![s("foo/bar#h2")],
                                 ..Default::default()
                             },
                         }),
@@ -12388,6 +13130,7 @@ mod test {
                 common: ftd::Common {
                     region: Some(ftd::Region::H1),
                     id: Some(s("heading-12")),
+                    components: vec![s("foo/bar#h1")],
                     ..Default::default()
                 },
             }));
@@ -12489,6 +13232,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -12560,6 +13307,7 @@ mod test {
                             parameters: Default::default(),
                         },
                     }],
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -12616,17 +13364,21 @@ mod test {
                     },
                 }),
                 kernel: false,
-                invocations: vec![std::array::IntoIter::new([
-                    (
-                        s("name"),
-                        ftd::Value::String {
-                            text: s("Hello"),
-                            source: ftd::TextSource::Caption,
-                        },
-                    ),
-                    (s("open"), ftd::Value::Boolean { value: true }),
-                ])
-                .collect()],
+                invocations: vec![ftd::Invocation {
+                    doc_id: s("foo/bar"),
+                    line_number: 8,
+                    arguments: std::array::IntoIter::new([
+                        (
+                            s("name"),
+                            ftd::Value::String {
+                                text: s("Hello"),
+                                source: ftd::TextSource::Caption,
+                            },
+                        ),
+                        (s("open"), ftd::Value::Boolean { value: true }),
+                    ])
+                    .collect(),
+                }],
                 line_number: 1,
                 ..Default::default()
             }),
@@ -12732,6 +13484,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -12814,6 +13570,7 @@ mod test {
                                     variable: s("foo/bar#open@0"),
                                     value: s("true"),
                                 }),
+                                components: vec![s("foo/bar#toc-item")],
                                 ..Default::default()
                             },
                         }),
@@ -12844,6 +13601,7 @@ mod test {
                                     variable: s("foo/bar#open@0"),
                                     value: s("true"),
                                 }),
+                                components: vec![s("foo/bar#toc-item")],
                                 ..Default::default()
                             },
                         }),
@@ -12852,6 +13610,7 @@ mod test {
                 },
                 common: ftd::Common {
                     reference: Some(s("foo/bar#toc")),
+                    components: vec![s("foo/bar#toc-item")],
                     ..Default::default()
                 },
             }));
@@ -12959,6 +13718,7 @@ mod test {
                                             variable: s("foo/bar#open@0"),
                                             value: s("true"),
                                         }),
+                                        components: vec![s("foo/bar#bar")],
                                         ..Default::default()
                                     },
                                 }),
@@ -12972,6 +13732,10 @@ mod test {
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -13201,6 +13965,10 @@ mod test {
                 })],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -13292,6 +14060,10 @@ mod test {
                                 ],
                                 ..Default::default()
                             },
+                            common: ftd::Common {
+                                components: vec![s("foo/bar#bar")],
+                                ..Default::default()
+                            },
                             ..Default::default()
                         }),
                         ftd::Element::Markup(ftd::Markups {
@@ -13311,6 +14083,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -13612,6 +14388,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -13662,6 +14442,13 @@ mod test {
                 })],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![
+                    s("foo/bar#secondary-button-1"),
+                    s("foo/bar#secondary-button"),
+                ],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -13722,6 +14509,7 @@ mod test {
                         },
                     ],
                     reference: Some(s("foo/bar#src@0")),
+                    components: vec![s("foo/bar#slide")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -13759,6 +14547,7 @@ mod test {
                         },
                     }],
                     reference: Some(s("foo/bar#src@1")),
+                    components: vec![s("foo/bar#slide")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -13840,6 +14629,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -13918,6 +14711,10 @@ mod test {
                             append_at: Some(s("some-child")),
                             ..Default::default()
                         },
+                        common: ftd::Common {
+                            components: vec![s("foo/bar#display-item1")],
+                            ..Default::default()
+                        },
                         ..Default::default()
                     }),
                     ftd::Element::Column(ftd::Column {
@@ -14009,6 +14806,10 @@ mod test {
                                                 append_at: Some(s("some-child")),
                                                 ..Default::default()
                                             },
+                                            common: ftd::Common {
+                                                components: vec![s("foo/bar#display-item1")],
+                                                ..Default::default()
+                                            },
                                             ..Default::default()
                                         })],
                                         ..Default::default()
@@ -14025,6 +14826,10 @@ mod test {
                             append_at: Some(s("some-child")),
                             ..Default::default()
                         },
+                        common: ftd::Common {
+                            components: vec![s("foo/bar#display-item1")],
+                            ..Default::default()
+                        },
                         ..Default::default()
                     }),
                 ],
@@ -14090,6 +14895,7 @@ mod test {
                         common: ftd::Common {
                             data_id: Some(s("beverage")),
                             id: Some(s("beverage")),
+                            components: vec![s("foo/bar#display-item1")],
                             ..Default::default()
                         },
                     })],
@@ -14184,6 +14990,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -14288,6 +15098,7 @@ mod test {
                                     variable: s("foo/bar#body@0"),
                                     value: s("$IsNotNull$"),
                                 }),
+                                components: vec![s("foo/bar#markdown")],
                                 ..Default::default()
                             },
                             ..Default::default()
@@ -14299,6 +15110,7 @@ mod test {
                     region: Some(ftd::Region::H0),
                     id: Some(s("one")),
                     data_id: Some(s("one")),
+                    components: vec![s("foo/bar#h0")],
                     ..Default::default()
                 },
             }));
@@ -14329,6 +15141,7 @@ mod test {
                                     value: s("$IsNotNull$"),
                                 }),
                                 reference: Some(s("foo/bar#body@1,1")),
+                                components: vec![s("foo/bar#markdown")],
                                 ..Default::default()
                             },
                             ..Default::default()
@@ -14339,6 +15152,7 @@ mod test {
                 common: ftd::Common {
                     region: Some(ftd::Region::H0),
                     id: Some(s("heading-01")),
+                    components: vec![s("foo/bar#h0")],
                     ..Default::default()
                 },
             }));
@@ -14401,6 +15215,10 @@ mod test {
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -14424,6 +15242,7 @@ mod test {
                 common: ftd::Common {
                     data_id: Some(s("asd")),
                     id: Some(s("asd")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -14478,6 +15297,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -14496,6 +15319,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
         let (_g_bag, g_col) = ftd::p2::interpreter::interpret(
@@ -14558,6 +15385,7 @@ mod test {
                 },
                 common: ftd::Common {
                     is_not_visible: true,
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -14635,6 +15463,7 @@ mod test {
                 common: ftd::Common {
                     id: Some(s("foo-id")),
                     data_id: Some(s("foo-id")),
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -14699,6 +15528,10 @@ mod test {
                                 })],
                                 ..Default::default()
                             },
+                            common: ftd::Common {
+                                components: vec![s("foo/bar#display-commit")],
+                                ..Default::default()
+                            },
                             ..Default::default()
                         }),
                         ftd::Element::Column(ftd::Column {
@@ -14715,6 +15548,10 @@ mod test {
                                 })],
                                 ..Default::default()
                             },
+                            common: ftd::Common {
+                                components: vec![s("foo/bar#display-commit")],
+                                ..Default::default()
+                            },
                             ..Default::default()
                         }),
                         ftd::Element::Column(ftd::Column {
@@ -14731,6 +15568,10 @@ mod test {
                                 })],
                                 ..Default::default()
                             },
+                            common: ftd::Common {
+                                components: vec![s("foo/bar#display-file")],
+                                ..Default::default()
+                            },
                             ..Default::default()
                         }),
                         ftd::Element::Column(ftd::Column {
@@ -14747,11 +15588,19 @@ mod test {
                                 })],
                                 ..Default::default()
                             },
+                            common: ftd::Common {
+                                components: vec![s("foo/bar#display-file")],
+                                ..Default::default()
+                            },
                             ..Default::default()
                         }),
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#display")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -14854,6 +15703,7 @@ mod test {
                             scale_y: Some(-1.0),
                             rotate: Some(45),
                             position: Some(ftd::Position::Center),
+                            components: vec![s("foo/bar#foo")],
                             ..Default::default()
                         },
                         ..Default::default()
@@ -15065,6 +15915,10 @@ mod test {
                 text: ftd::markdown_line("hello"),
                 line: true,
                 line_clamp: Some(50),
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -15073,6 +15927,10 @@ mod test {
             .push(ftd::Element::Markup(ftd::Markups {
                 text: ftd::markdown_line("hello"),
                 line: true,
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -15098,7 +15956,7 @@ mod test {
     #[test]
     fn inner_container_check() {
         let mut main = super::default_column();
-        let col = ftd::Element::Column(ftd::Column {
+        let col = ftd::Column {
             spacing: None,
             container: ftd::Container {
                 children: vec![ftd::Element::Column(ftd::Column {
@@ -15126,9 +15984,19 @@ mod test {
                 ..Default::default()
             },
             ..Default::default()
-        });
-        main.container.children.push(col.clone());
-        main.container.children.push(col);
+        };
+        main.container
+            .children
+            .push(ftd::Element::Column(col.clone()));
+        main.container
+            .children
+            .push(ftd::Element::Column(ftd::Column {
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
+                ..col
+            }));
 
         let (_g_bag, g_col) = ftd::p2::interpreter::interpret(
             "foo/bar",
@@ -15238,6 +16106,7 @@ mod test {
                             },
                         },
                     ],
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
                 ..Default::default()
@@ -15320,6 +16189,7 @@ mod test {
                                         },
                                     },
                                 ],
+                                components: vec![s("foo/bar#bar")],
                                 ..Default::default()
                             },
                         }),
@@ -15335,6 +16205,7 @@ mod test {
                             parameters: Default::default(),
                         },
                     }],
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -15445,6 +16316,7 @@ mod test {
                         },
                     },
                 ],
+                components: vec![s("foo/bar#foo")],
                 ..Default::default()
             },
         }));
@@ -15509,11 +16381,16 @@ mod test {
                             variable: s("foo/bar#b@0"),
                             value: s("true"),
                         }),
+                        components: vec![s("foo/bar#bar")],
                         ..Default::default()
                     },
                 })],
                 ..Default::default()
             },
+            common: ftd::Common {
+                components: vec![s("foo/bar#foo")],
+                ..Default::default()
+            },
             ..Default::default()
         }));
 
@@ -15634,6 +16511,7 @@ mod test {
                             },
                         },
                     ],
+                    components: vec![s("foo/bar#foo")],
                     ..Default::default()
                 },
             }));
@@ -15802,6 +16680,10 @@ mod test {
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#col")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -15858,6 +16740,10 @@ mod test {
             .push(ftd::Element::TextBlock(ftd::TextBlock {
                 text: ftd::markdown_line("hello"),
                 line: true,
+                common: ftd::Common {
+                    components: vec![s("foo/bar#b")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -15926,6 +16812,7 @@ mod test {
                                     },
                                     reference: Some(s("foo/bar#red")),
                                 }),
+                                components: vec![s("foo/bar#foo"), s("foo/bar#t@0")],
                                 ..Default::default()
                             },
                             line_clamp: Some(30),
@@ -15972,6 +16859,7 @@ mod test {
                                                 },
                                                 reference: Some(s("foo/bar#red")),
                                             }),
+                                            components: vec![s("foo/bar#foo"), s("foo/bar#t@0,2")],
                                             ..Default::default()
                                         },
                                         line_clamp: Some(20),
@@ -15996,6 +16884,7 @@ mod test {
                                                 },
                                                 reference: Some(s("foo/bar#red")),
                                             }),
+                                            components: vec![s("foo/bar#k@0,2")],
                                             ..Default::default()
                                         },
                                         line_clamp: Some(10),
@@ -16005,12 +16894,17 @@ mod test {
                                 ..Default::default()
                             },
                             common: ftd::Common {
+                                components: vec![s("foo/bar#moo"), s("foo/bar#g@0")],
                                 ..Default::default()
                             },
                         }),
                     ],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#bar")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -16174,6 +17068,10 @@ mod test {
                     })],
                     ..Default::default()
                 },
+                common: ftd::Common {
+                    components: vec![s("foo/bar#foo")],
+                    ..Default::default()
+                },
                 ..Default::default()
             }));
 
@@ -16391,6 +17289,8 @@ mod test {
                     ..Default::default()
                 },
                 placeholder: None,
+                value_is_valid: true,
+                ..Default::default()
             }));
 
         p!(
@@ -16409,6 +17309,904 @@ mod test {
         );
     }
 
+    #[test]
+    fn typed_input() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- string email: a@b.c
+
+                -- boolean email-valid: true
+
+                -- ftd.input:
+                type: email
+                value: $email
+                valid: $email-valid
+                required: true
+                max-length: 40
+
+                -- ftd.input:
+                type: textarea
+                value: <hi>
+
+                -- integer count: 3
+
+                -- boolean count-valid: true
+
+                -- ftd.input:
+                type: number
+                value: $count
+                min: 5
+                valid: $count-valid
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let input = match &doc.main.container.children[0] {
+            ftd::Element::Input(i) => i,
+            t => panic!("expected input, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(input.type_, ftd::InputType::Email);
+        pretty_assertions::assert_eq!(input.value, Some(s("a@b.c")));
+        pretty_assertions::assert_eq!(input.common.reference, Some(s("foo/bar#email")));
+        pretty_assertions::assert_eq!(
+            input.common.events,
+            vec![
+                ftd::p2::Event::set_value_event("oninput", "foo/bar#email", "$VALUE", "string"),
+                ftd::p2::Event::set_value_event(
+                    "oninput",
+                    "foo/bar#email-valid",
+                    "$VALID",
+                    "boolean"
+                ),
+            ]
+        );
+
+        // an integer variable, kept an integer as the input changes
+        let input = match &doc.main.container.children[2] {
+            ftd::Element::Input(i) => i,
+            t => panic!("expected input, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(input.value, Some(s("3")));
+        pretty_assertions::assert_eq!(
+            input.common.events[0],
+            ftd::p2::Event::set_value_event("oninput", "foo/bar#count", "$VALUE", "integer")
+        );
+
+        // `valid` starts as whether the initial value is valid
+        assert!(doc.get::<bool>("email-valid").unwrap());
+        assert!(!doc.get::<bool>("count-valid").unwrap());
+
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("type=\"email\""));
+        assert!(html.contains("value=\"a@b.c\""));
+        assert!(html.contains("maxlength=\"40\""));
+        assert!(html.contains("\\u003Chi\\u003E</textarea>"));
+
+        assert_invalid(
+            "",
+            &[
+                "-- ftd.input:\ntype: colour",
+                "-- ftd.input:\nmin: 1",
+                "-- ftd.input:\nvalid: true",
+                "-- integer n: 1\n\n-- ftd.input:\nvalue: $n",
+            ],
+        );
+    }
+
+    #[test]
+    fn choice_inputs() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- boolean dark: true
+
+                -- string size: medium
+
+                -- string list sizes:
+
+                -- sizes: small
+
+                -- sizes: medium
+
+                -- sizes: <large>
+
+                -- ftd.checkbox:
+                checked: $dark
+                label: Dark mode
+
+                -- ftd.toggle:
+                checked: $dark
+
+                -- ftd.radio:
+                options: $sizes
+                value: $size
+                label: Size
+
+                -- ftd.select:
+                options: $sizes
+
+                -- ftd.radio:
+                options: $sizes
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let toggle = ftd::Event {
+            name: s("onchange"),
+            action: ftd::Action {
+                action: s("toggle"),
+                target: s("foo/bar#dark"),
+                parameters: Default::default(),
+            },
+        };
+        let children = &doc.main.container.children;
+        match (&children[0], &children[1]) {
+            (ftd::Element::Checkbox(c), ftd::Element::Toggle(t)) => {
+                assert!(c.checked);
+                pretty_assertions::assert_eq!(c.label, Some(s("Dark mode")));
+                pretty_assertions::assert_eq!(c.common.reference, Some(s("foo/bar#dark")));
+                pretty_assertions::assert_eq!(c.common.events, vec![toggle.clone()]);
+                pretty_assertions::assert_eq!(t.common.events, vec![toggle]);
+            }
+            t => panic!("expected checkbox and toggle, found: {:?}", t),
+        }
+        match (&children[2], &children[3]) {
+            (ftd::Element::Radio(r), ftd::Element::Select(e)) => {
+                pretty_assertions::assert_eq!(
+                    r.options,
+                    vec![s("small"), s("medium"), s("<large>")]
+                );
+                pretty_assertions::assert_eq!(r.value, Some(s("medium")));
+                pretty_assertions::assert_eq!(
+                    r.common.events,
+                    vec![ftd::p2::Event::set_value_event(
+                        "onchange",
+                        "foo/bar#size",
+                        "$VALUE",
+                        "string"
+                    )]
+                );
+                pretty_assertions::assert_eq!(e.options, r.options);
+                pretty_assertions::assert_eq!(e.value, None);
+                assert!(e.common.events.is_empty());
+
+                let radios = r.to_radio_node("foo/bar").children;
+                assert!(!radios[0].children[0].attrs.contains_key("checked"));
+                assert!(radios[1].children[0].attrs.contains_key("checked"));
+                let options = e.to_node("foo/bar").children;
+                pretty_assertions::assert_eq!(options.len(), 4);
+                assert!(options[0].attrs.contains_key("selected"));
+            }
+            t => panic!("expected radio and select, found: {:?}", t),
+        }
+
+        // every radio group has a name of its own, even without an id
+        let name = |e: &ftd::Element| match e {
+            ftd::Element::Radio(r) => r.to_radio_node("foo/bar").children[0].children[0]
+                .attrs
+                .get("name")
+                .cloned(),
+            t => panic!("expected radio, found: {:?}", t),
+        };
+        assert_ne!(name(&children[2]), name(&children[4]));
+
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("role=\"switch\""));
+        assert!(html.contains("aria-label=\"Dark mode\""));
+        assert!(html.contains("role=\"radiogroup\""));
+        assert!(html.contains("\\u003Clarge\\u003E</option>"));
+        assert!(doc
+            .to_rt("main", "foo/bar")
+            .data
+            .get("foo/bar#size")
+            .map_or(false, |d| !d.dependencies.is_empty()));
+    }
+
+    #[test]
+    fn or_type_choice() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- or-type size:
+
+                --- small:
+                caption name:
+
+                --- large:
+                caption name:
+
+                -- size.large s: L
+
+                -- ftd.radio:
+                value: $s
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        // the options are the variants, and picking one sets the variant
+        match &doc.main.container.children[0] {
+            ftd::Element::Radio(r) => {
+                pretty_assertions::assert_eq!(r.options, vec![s("small"), s("large")]);
+                pretty_assertions::assert_eq!(r.value, Some(s("large")));
+                pretty_assertions::assert_eq!(
+                    r.common.events,
+                    vec![ftd::p2::Event::set_value_event(
+                        "onchange",
+                        "foo/bar#s.type",
+                        "$VALUE",
+                        "string"
+                    )]
+                );
+            }
+            t => panic!("expected radio, found: {:?}", t),
+        }
+
+        assert_invalid(
+            "-- string list o:\n\n-- o: S\n\n-- or-type size:\n\n--- small:\n\
+            caption name:\n\n-- size.small s: S\n\n",
+            &["-- ftd.select:\noptions: $o\nvalue: $s", "-- ftd.select:"],
+        );
+    }
+
+    #[test]
+    fn media() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- boolean playing: false
+
+                -- ftd.image-src intro:
+                light: /intro.mp4
+                dark: /intro-dark.mp4
+
+                -- ftd.image-src cover: /cover.png
+                dark: /cover-dark.png
+
+                -- ftd.track list tracks:
+
+                -- tracks: /intro.en.vtt
+                lang: en
+                label: English
+                default: true
+
+                -- tracks: /intro.hi.vtt
+                kind: subtitles
+                lang: hi
+
+                -- ftd.video:
+                src: $intro
+                poster: $cover
+                muted: true
+                loop: true
+                tracks: $tracks
+                $on-play$: toggle $playing
+                $on-ended$: toggle $playing
+
+                -- ftd.image-src theme: /theme.mp3
+                dark: /theme.mp3
+
+                -- ftd.audio:
+                src: $theme
+                controls: false
+                autoplay: true
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let (video, audio) = match (
+            &doc.main.container.children[0],
+            &doc.main.container.children[1],
+        ) {
+            (ftd::Element::Video(v), ftd::Element::Audio(a)) => (v, a),
+            t => panic!("expected video and audio, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(
+            video.src,
+            ftd::ImageSrc {
+                light: s("/intro.mp4"),
+                dark: s("/intro-dark.mp4"),
+            }
+        );
+        pretty_assertions::assert_eq!(video.common.reference, Some(s("foo/bar#intro")));
+        pretty_assertions::assert_eq!(
+            video.poster.as_ref().map(|p| p.light.as_str()),
+            Some("/cover.png")
+        );
+        pretty_assertions::assert_eq!(video.poster_reference, Some(s("foo/bar#cover")));
+        assert!(video.muted && video.loop_ && video.controls && !video.autoplay);
+        pretty_assertions::assert_eq!(
+            video.tracks,
+            vec![
+                ftd::Track {
+                    src: s("/intro.en.vtt"),
+                    kind: s("captions"),
+                    lang: Some(s("en")),
+                    label: Some(s("English")),
+                    default: true,
+                },
+                ftd::Track {
+                    src: s("/intro.hi.vtt"),
+                    kind: s("subtitles"),
+                    lang: Some(s("hi")),
+                    label: None,
+                    default: false,
+                },
+            ]
+        );
+        pretty_assertions::assert_eq!(
+            video
+                .common
+                .events
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["onplay", "onended"]
+        );
+        assert!(audio.poster.is_none() && audio.autoplay && !audio.controls);
+
+        let node = video.to_video_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, s("video"));
+        pretty_assertions::assert_eq!(node.attrs.get("poster"), Some(&s("/cover.png")));
+        pretty_assertions::assert_eq!(node.children.len(), 2);
+        pretty_assertions::assert_eq!(node.children[0].attrs.get("srclang"), Some(&s("en")));
+        assert!(node.children[0].attrs.contains_key("default"));
+        let node = audio.to_audio_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, s("audio"));
+        assert!(node.attrs.contains_key("autoplay") && !node.attrs.contains_key("controls"));
+
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("onplay="));
+        assert!(html.contains("onended="));
+
+        let theme = "-- ftd.image-src a: /a.mp3\ndark: /a.mp3\n\n";
+        assert_invalid(
+            theme,
+            &[
+                "-- ftd.audio:\nsrc: $a\nposter: $a",
+                "-- ftd.track list t:\n\n-- t: /t\nkind: karaoke\n\n\
+                -- ftd.audio:\nsrc: $a\ntracks: $t",
+            ],
+        );
+    }
+
+    #[test]
+    fn responsive_image() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- ftd.image-src hero: /hero.png
+                dark: /hero-dark.png
+
+                -- ftd.image-src hero-mobile: /hero-mobile.png
+                dark: /hero-mobile-dark.png
+
+                -- ftd.image:
+                src: $hero
+                mobile-src: $hero-mobile
+                xl-src: $hero
+                fit: contain
+                aspect-ratio: 16/9
+                loading: eager
+                fetch-priority: high
+                sizes: (max-width: 768px) 100vw, 50vw
+
+                -- ftd.image:
+                src: $hero
+                crop: true
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let (hero, cropped) = match (
+            &doc.main.container.children[0],
+            &doc.main.container.children[1],
+        ) {
+            (ftd::Element::Image(h), ftd::Element::Image(c)) => (h, c),
+            t => panic!("expected images, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(
+            hero.sources,
+            vec![
+                ftd::ImageSource {
+                    media: s("(max-width: 768px)"),
+                    src: ftd::ImageSrc {
+                        light: s("/hero-mobile.png"),
+                        dark: s("/hero-mobile-dark.png"),
+                    },
+                },
+                ftd::ImageSource {
+                    media: s("(min-width: 1441px)"),
+                    src: hero.src.clone(),
+                },
+            ]
+        );
+        pretty_assertions::assert_eq!(hero.fit, Some(ftd::ImageFit::Contain));
+        pretty_assertions::assert_eq!(hero.loading, Some(ftd::Loading::Eager));
+        pretty_assertions::assert_eq!(hero.fetch_priority, Some(ftd::FetchPriority::High));
+
+        let node = hero.to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, s("picture"));
+        pretty_assertions::assert_eq!(
+            node.children
+                .iter()
+                .map(|c| c.node.as_str())
+                .collect::<Vec<_>>(),
+            vec!["source", "source", "img"]
+        );
+        pretty_assertions::assert_eq!(
+            node.children[0].attrs.get("srcset"),
+            Some(&s("/hero-mobile.png"))
+        );
+        pretty_assertions::assert_eq!(
+            node.children[0].attrs.get("data-dark"),
+            Some(&s("/hero-mobile-dark.png"))
+        );
+        pretty_assertions::assert_eq!(
+            node.children[1].attrs.get("sizes"),
+            Some(&s("(max-width: 768px) 100vw, 50vw"))
+        );
+        let img = &node.children[2];
+        pretty_assertions::assert_eq!(img.attrs.get("src"), Some(&s("/hero.png")));
+        pretty_assertions::assert_eq!(img.attrs.get("fetchpriority"), Some(&s("high")));
+        pretty_assertions::assert_eq!(img.attrs.get("loading"), Some(&s("eager")));
+        pretty_assertions::assert_eq!(
+            img.attrs.get("sizes"),
+            Some(&s("(max-width: 768px) 100vw, 50vw"))
+        );
+        pretty_assertions::assert_eq!(img.style.get("object-fit"), Some(&s("contain")));
+        pretty_assertions::assert_eq!(img.style.get("aspect-ratio"), Some(&s("16/9")));
+
+        let node = cropped.to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, s("img"));
+        assert!(!node.attrs.contains_key("loading") && !node.attrs.contains_key("sizes"));
+        pretty_assertions::assert_eq!(node.style.get("object-fit"), Some(&s("cover")));
+
+        let image = "-- ftd.image-src a: /a.png\ndark: /a.png\n\n-- ftd.image:\nsrc: $a\n";
+        for property in ["fit: stretch", "aspect-ratio: wide", "aspect-ratio: 16/0"] {
+            let source = format!("{}{}", image, property);
+            assert!(
+                ftd::p2::Document::from("foo/bar", source.as_str(), &ftd::p2::TestLibrary {})
+                    .is_err(),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn table() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- record person:
+                caption name:
+                integer age:
+                optional string city:
+
+                -- person list people:
+
+                -- people: Amit
+                age: 40
+                city: Bangalore
+
+                -- people: Arpita
+                age: 32
+
+                -- ftd.table-column list columns:
+
+                -- columns: Name
+                field: name
+                width: 200
+
+                -- columns: Age
+                field: age
+                align: right
+                component: age-cell
+
+                -- columns: City
+                field: city
+
+                -- ftd.color stripe: #f5f5f5
+                dark: #222222
+
+                -- ftd.integer age-cell:
+                integer value:
+                value: $value
+
+                -- ftd.table:
+                data: $people
+                columns: $columns
+                sortable: true
+                sticky-header: true
+                stripe-color: $stripe
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let table = match &doc.main.container.children[0] {
+            ftd::Element::Table(t) => t,
+            t => panic!("expected table, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(
+            table
+                .columns
+                .iter()
+                .map(|c| (c.caption.as_str(), c.field.as_str(), c.component.is_some()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Name", "name", false),
+                ("Age", "age", true),
+                ("City", "city", false)
+            ]
+        );
+        pretty_assertions::assert_eq!(table.columns[0].width, Some(ftd::Length::Px { value: 200 }));
+        pretty_assertions::assert_eq!(table.columns[1].align, Some(ftd::TextAlign::Right));
+        pretty_assertions::assert_eq!(
+            table
+                .rows
+                .iter()
+                .map(|r| r.iter().map(|c| c.text.as_str()).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec!["Amit", "40", "Bangalore"], vec!["Arpita", "32", ""]]
+        );
+        match table.rows[1][1].element {
+            Some(ftd::Element::Integer(ref i)) => {
+                pretty_assertions::assert_eq!(i.text.original, "32")
+            }
+            ref t => panic!("expected integer, found: {:?}", t),
+        }
+        assert!(table.sortable && table.sticky_header && table.stripe_color.is_some());
+
+        let node = table.to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, s("table"));
+        assert!(node.classes.contains(&s("ft_table_sticky")));
+        assert!(node.style.contains_key("--ft-table-stripe-dark"));
+        let th = &node.children[0].children[0].children[1];
+        pretty_assertions::assert_eq!(th.attrs.get("aria-sort"), Some(&s("none")));
+        pretty_assertions::assert_eq!(th.children[0].events[0].action.action, s("sort-table"));
+        let td = &node.children[1].children[0].children[1];
+        pretty_assertions::assert_eq!(td.attrs.get("data-sort"), Some(&s("40")));
+        pretty_assertions::assert_eq!(td.children.len(), 1);
+
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("<th"));
+        assert!(html.contains("sort-table"));
+
+        let data = "-- record r:\nstring a:\n\n-- r list rs:\n\n-- rs:\na: x\n\n";
+        assert_invalid(
+            data,
+            &[
+                "-- ftd.table-column list c:\n\n-- c: B\nfield: b\n\n\
+                -- ftd.table:\ndata: $rs\ncolumns: $c",
+                "-- ftd.table-column list c:\n\n-- c: A\nfield: a\ncomponent: ftd.text\n\n\
+                -- ftd.table:\ndata: $rs\ncolumns: $c",
+                "-- ftd.table-column list c:\n\n-- c: A\nfield: a\nalign: top\n\n\
+                -- ftd.table:\ndata: $rs\ncolumns: $c",
+                "-- string list ss:\n\n-- ss: x\n\n\
+                -- ftd.table-column list c:\n\n-- c: A\nfield: a\n\n\
+                -- ftd.table:\ndata: $ss\ncolumns: $c",
+            ],
+        );
+    }
+
+    #[test]
+    fn overlays() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- boolean show: false
+
+                -- boolean menu: true
+
+                -- ftd.text: Delete
+                id: delete
+                $on-click$: toggle $show
+
+                -- ftd.dialog:
+                open: $show
+                label: Delete the page
+
+                --- ftd.text: Are you sure?
+
+                -- ftd.popover:
+                open: $menu
+                anchor-id: delete
+                placement: top
+                dismissible: false
+
+                --- ftd.text: More actions
+
+                -- ftd.tooltip:
+                anchor-id: delete
+
+                --- ftd.text: Deletes the page
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let columns = doc.main.container.children[1..]
+            .iter()
+            .map(|c| match c {
+                ftd::Element::Column(c) => c,
+                t => panic!("expected column, found: {:?}", t),
+            })
+            .collect::<Vec<_>>();
+        let overlay = |i: usize| columns[i].common.overlay.clone().unwrap();
+        pretty_assertions::assert_eq!(
+            overlay(0),
+            ftd::Overlay {
+                kind: ftd::OverlayKind::Dialog,
+                open: Some(s("foo/bar#show")),
+                anchor: None,
+                placement: ftd::Placement::Bottom,
+                dismissible: true,
+                label: Some(s("Delete the page")),
+            }
+        );
+        pretty_assertions::assert_eq!(
+            columns[0].common.condition,
+            Some(ftd::Condition {
+                variable: s("foo/bar#show"),
+                value: s("true"),
+            })
+        );
+        assert!(columns[0].common.is_not_visible);
+        pretty_assertions::assert_eq!(overlay(1).anchor, Some(s("delete")));
+        pretty_assertions::assert_eq!(overlay(1).placement, ftd::Placement::Top);
+        assert!(!overlay(1).dismissible && !columns[1].common.is_not_visible);
+        pretty_assertions::assert_eq!(overlay(2).kind, ftd::OverlayKind::Tooltip);
+        assert!(overlay(2).open.is_none() && columns[2].common.is_not_visible);
+
+        let node = columns[0].to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.attrs.get("role"), Some(&s("dialog")));
+        pretty_assertions::assert_eq!(node.attrs.get("aria-modal"), Some(&s("true")));
+        pretty_assertions::assert_eq!(node.attrs.get("aria-label"), Some(&s("Delete the page")));
+        pretty_assertions::assert_eq!(node.style.get("position"), Some(&s("fixed")));
+        assert!(node.classes.contains(&s("ft_dialog")));
+        let node = columns[2].to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.attrs.get("role"), Some(&s("tooltip")));
+
+        let events = doc.body_events("main");
+        assert!(events.contains("window.ftd.overlays(\"main\", "));
+        assert!(events.contains(r#""kind":"popover""#));
+        assert!(events.contains(r#""open":"foo/bar#show""#));
+
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            "-- boolean a: false\n\n-- ftd.dialog:\nopen: $a\n\n-- ftd.dialog:\nopen: $a",
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+        let events = doc.body_events("main");
+        let ids = events
+            .split(r#""data_id":""#)
+            .skip(1)
+            .map(|s| s.split('"').next().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert!(ids.len() == 2 && ids[0] != ids[1], "{:?}", ids);
+
+        assert_invalid(
+            "-- boolean s: true\n\n",
+            &[
+                "-- ftd.dialog:\nopen: true",
+                "-- ftd.dialog:\nopen: $s\nif: $s",
+                "-- ftd.popover:\nopen: $s\nanchor-id: a\nplacement: middle",
+                "-- ftd.tooltip:\nopen: $s\nanchor-id: a",
+                "-- ftd.tooltip:",
+            ],
+        );
+    }
+
+    #[test]
+    fn disclosures() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- integer tab: 1
+
+                -- boolean more: true
+
+                -- ftd.panel section:
+                caption name:
+                string body:
+                title: $name
+
+                --- ftd.text: $body
+
+                -- ftd.tabs:
+                selected: $tab
+
+                --- section: Overview
+                body: First
+
+                --- section: Usage
+                body: Second
+
+                -- ftd.accordion:
+
+                --- section: Question
+                body: Answer
+
+                -- ftd.details: More
+                open: $more
+
+                --- ftd.text: Hidden
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let columns = doc
+            .main
+            .container
+            .children
+            .iter()
+            .map(|c| match c {
+                ftd::Element::Column(c) => c,
+                t => panic!("expected column, found: {:?}", t),
+            })
+            .collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(
+            columns[0].common.disclosure,
+            Some(ftd::Disclosure {
+                kind: ftd::DisclosureKind::Tabs,
+                variable: Some(s("foo/bar#tab")),
+                selected: Some(1),
+                variants: vec![],
+                open: false,
+                title: None,
+            })
+        );
+        let panels = columns[0]
+            .container
+            .children
+            .iter()
+            .map(|c| c.get_common().unwrap())
+            .collect::<Vec<_>>();
+        assert!(panels[0].is_not_visible && !panels[1].is_not_visible);
+        pretty_assertions::assert_eq!(
+            panels[1].condition,
+            Some(ftd::Condition {
+                variable: s("foo/bar#tab"),
+                value: s("1"),
+            })
+        );
+        pretty_assertions::assert_eq!(
+            panels[1].disclosure.as_ref().and_then(|d| d.title.clone()),
+            Some(s("Usage"))
+        );
+
+        let node = columns[0].to_node("foo/bar");
+        let tabs = &node.children[0].children;
+        pretty_assertions::assert_eq!(node.children[0].attrs.get("role"), Some(&s("tablist")));
+        pretty_assertions::assert_eq!(tabs[1].attrs.get("aria-selected"), Some(&s("true")));
+        pretty_assertions::assert_eq!(tabs[0].attrs.get("tabindex"), Some(&s("-1")));
+        pretty_assertions::assert_eq!(
+            tabs[1].attrs.get("aria-controls"),
+            node.children[2].attrs.get("id")
+        );
+        assert!(node.children[1].attrs.get("id") != node.children[2].attrs.get("id"));
+        assert!(tabs[0].attrs.get("id") != tabs[1].attrs.get("id"));
+        pretty_assertions::assert_eq!(node.children[2].attrs.get("role"), Some(&s("tabpanel")));
+        pretty_assertions::assert_eq!(node.children[1].style.get("display"), Some(&s("none")));
+
+        // no panel of an accordion is open unless it is selected
+        assert!(
+            columns[1].container.children[0]
+                .get_common()
+                .unwrap()
+                .is_not_visible
+        );
+        let node = columns[1].to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.children[0].node, s("h3"));
+        pretty_assertions::assert_eq!(
+            node.children[0].children[0].attrs.get("aria-expanded"),
+            Some(&s("false"))
+        );
+
+        let node = columns[2].to_node("foo/bar");
+        pretty_assertions::assert_eq!(node.node, s("details"));
+        pretty_assertions::assert_eq!(node.children[0].text, Some(s("More")));
+        assert!(node.attrs.contains_key("open"));
+        assert!(node
+            .events
+            .iter()
+            .any(|e| e.action.action == "toggle-details" && e.action.target == "foo/bar#more"));
+    }
+
+    #[test]
+    fn or_type_disclosure() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- or-type section:
+
+                --- overview:
+
+                --- usage:
+
+                -- section.usage tab:
+
+                -- ftd.panel part:
+                caption name:
+                title: $name
+
+                --- ftd.text: $name
+
+                -- ftd.tabs:
+                selected: $tab
+
+                --- part: Overview
+
+                --- part: Usage
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let column = match &doc.main.container.children[0] {
+            ftd::Element::Column(c) => c,
+            t => panic!("expected column, found: {:?}", t),
+        };
+        pretty_assertions::assert_eq!(
+            column.common.disclosure,
+            Some(ftd::Disclosure {
+                kind: ftd::DisclosureKind::Tabs,
+                variable: Some(s("foo/bar#tab.type")),
+                selected: Some(1),
+                variants: vec![s("overview"), s("usage")],
+                open: false,
+                title: None,
+            })
+        );
+        let panels = column
+            .container
+            .children
+            .iter()
+            .map(|c| c.get_common().unwrap())
+            .collect::<Vec<_>>();
+        assert!(panels[0].is_not_visible && !panels[1].is_not_visible);
+        assert!(panels[1].condition.is_none());
+
+        let node = column.to_node("foo/bar");
+        let tabs = &node.children[0].children;
+        pretty_assertions::assert_eq!(tabs[1].attrs.get("aria-selected"), Some(&s("true")));
+        pretty_assertions::assert_eq!(
+            tabs[0].attrs.get("data-panel-variant"),
+            Some(&s("overview"))
+        );
+        assert!(tabs[1]
+            .events
+            .iter()
+            .any(|e| e.action.action == "select-panel" && e.action.target == "foo/bar#tab.type"));
+    }
+
     #[test]
     fn component_processor() {
         let mut main = super::default_column();
@@ -17311,6 +19109,7 @@ mod test {
                         variable: s("foo/bar#active"),
                         value: s("true"),
                     }),
+                    components: vec![s("foo/bar#bar")],
                     ..Default::default()
                 },
             }));
@@ -17378,6 +19177,7 @@ mod test {
                         reference: Some(s("foo/bar#green")),
                     }),
                     border_width: 1,
+                    components: vec![s("foo/bar#bar")],
                     ..Default::default()
                 },
             }));
@@ -8,6 +8,25 @@ pub trait Library: Sync {
             None => ftd::e2(format!("library not found: {}", name), "", 0),
         }
     }
+    /// versions of `name` that `get` can serve as `ftd::p2::versioned_name`
+    async fn versions(&self, _name: &str, _doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        vec![]
+    }
+    /// native components available as `ftd.<name>` in the documents
+    /// interpreted with this library
+    fn plugins(&self) -> Vec<std::sync::Arc<dyn ftd::Plugin>> {
+        vec![]
+    }
+    /// asked before the built in providers for the players of `ftd.iframe`'s
+    /// `embed` urls
+    fn embed_providers(&self) -> Vec<std::sync::Arc<dyn ftd::EmbedProvider>> {
+        vec![]
+    }
+    /// icon sets `ftd.icon` can show in the documents interpreted with this
+    /// library
+    fn icon_sets(&self) -> Vec<std::sync::Arc<dyn ftd::IconSet>> {
+        vec![]
+    }
     async fn process(
         &self,
         section: &ftd::p1::Section,
@@ -30,6 +49,25 @@ pub trait Library {
             None => ftd::e2(format!("library not found: {}", name), "", 0),
         }
     }
+    /// versions of `name` that `get` can serve as `ftd::p2::versioned_name`
+    fn versions(&self, _name: &str, _doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        vec![]
+    }
+    /// native components available as `ftd.<name>` in the documents
+    /// interpreted with this library
+    fn plugins(&self) -> Vec<std::sync::Arc<dyn ftd::Plugin>> {
+        vec![]
+    }
+    /// asked before the built in providers for the players of `ftd.iframe`'s
+    /// `embed` urls
+    fn embed_providers(&self) -> Vec<std::sync::Arc<dyn ftd::EmbedProvider>> {
+        vec![]
+    }
+    /// icon sets `ftd.icon` can show in the documents interpreted with this
+    /// library
+    fn icon_sets(&self) -> Vec<std::sync::Arc<dyn ftd::IconSet>> {
+        vec![]
+    }
     fn process(
         &self,
         section: &ftd::p1::Section,
@@ -215,6 +253,10 @@ impl Library for TestLibrary {
         std::fs::read_to_string(format!("./tests/{}.ftd", name)).ok()
     }
 
+    async fn versions(&self, name: &str, _doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        ftd::p2::versions_in_store(std::path::Path::new("./tests"), name)
+    }
+
     async fn process(
         &self,
         section: &ftd::p1::Section,
@@ -243,6 +285,10 @@ impl Library for TestLibrary {
         std::fs::read_to_string(format!("./tests/{}.ftd", name)).ok()
     }
 
+    fn versions(&self, name: &str, _doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        ftd::p2::versions_in_store(std::path::Path::new("./tests"), name)
+    }
+
     fn process(
         &self,
         section: &ftd::p1::Section,
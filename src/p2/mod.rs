@@ -1,19 +1,30 @@
+pub(crate) mod cache;
 pub(crate) mod document;
+pub(crate) mod edit;
 pub(crate) mod element;
 pub(crate) mod event;
 pub(crate) mod expression;
+pub(crate) mod form;
 pub(crate) mod interpreter;
 pub(crate) mod kind;
 pub(crate) mod library;
+pub(crate) mod package;
 pub(crate) mod record;
+pub(crate) mod schema;
+pub(crate) mod snapshot;
 pub(crate) mod tdoc;
+pub(crate) mod typed;
 pub(crate) mod utils;
 
-pub use document::Document;
+pub use cache::{LibraryCache, MemoryCache};
+pub use document::{Document, Options};
 pub use event::{Action, ActionKind, Event, EventName};
 pub use expression::Boolean;
 pub use interpreter::{default_column, interpret, Thing};
 pub use kind::Kind;
 pub use library::{Library, TestLibrary};
+pub use package::{versioned_name, versions_in_store, Lock, Version, VersionReq, LOCK_FILE};
 pub use record::Record;
+pub use snapshot::SNAPSHOT_VERSION;
 pub use tdoc::TDoc;
+pub use typed::{Declarations, FtdRecord, TypedLibrary};
@@ -0,0 +1,364 @@
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(s: &str, doc_id: &str, line_number: usize) -> ftd::p1::Result<Version> {
+        let parts = s
+            .trim()
+            .split('.')
+            .map(|p| p.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>();
+        let (major, minor, patch) = match parts.as_ref().map(|p| p.as_slice()) {
+            Ok([major]) => (*major, 0, 0),
+            Ok([major, minor]) => (*major, *minor, 0),
+            Ok([major, minor, patch]) => (*major, *minor, *patch),
+            _ => return ftd::e2(format!("invalid version: {}", s), doc_id, line_number),
+        };
+        Ok(Version {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// `1.2` means any version compatible with `1.2.0`, same as cargo's default
+/// caret requirement, `=1.2.3` means exactly that version.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VersionReq {
+    pub version: Version,
+    pub exact: bool,
+}
+
+impl VersionReq {
+    pub fn parse(s: &str, doc_id: &str, line_number: usize) -> ftd::p1::Result<VersionReq> {
+        let s = s.trim();
+        match s.strip_prefix('=') {
+            Some(v) => Ok(VersionReq {
+                version: Version::parse(v, doc_id, line_number)?,
+                exact: true,
+            }),
+            None => Ok(VersionReq {
+                version: Version::parse(s, doc_id, line_number)?,
+                exact: false,
+            }),
+        }
+    }
+
+    pub fn matches(&self, v: &Version) -> bool {
+        if self.exact {
+            return &self.version == v;
+        }
+        if v < &self.version {
+            return false;
+        }
+        match (self.version.major, self.version.minor) {
+            (0, 0) => v.major == 0 && v.minor == 0 && v.patch == self.version.patch,
+            (0, minor) => v.major == 0 && v.minor == minor,
+            (major, _) => v.major == major,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exact {
+            write!(f, "={}", self.version)
+        } else {
+            write!(f, "{}", self.version)
+        }
+    }
+}
+
+/// The versions every package in an import graph got resolved to, this is
+/// what gets stored in `ftd.lock`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Lock {
+    pub packages: std::collections::BTreeMap<String, Version>,
+    // packages resolved while interpreting the current document, with the
+    // requirement that selected them and where it came from
+    resolved: std::collections::BTreeMap<String, (VersionReq, String, usize)>,
+    // packages imported without a version in the current document, and where
+    unversioned: std::collections::BTreeMap<String, (String, usize)>,
+}
+
+pub const LOCK_FILE: &str = "ftd.lock";
+
+impl Lock {
+    pub fn parse(s: &str) -> ftd::p1::Result<Lock> {
+        let mut lock: Lock = Default::default();
+        for section in ftd::p1::parse(s, LOCK_FILE)? {
+            if section.is_commented {
+                continue;
+            }
+            if section.name != "package" {
+                return ftd::e2(
+                    format!("unknown section in lock file: {}", section.name),
+                    LOCK_FILE,
+                    section.line_number,
+                );
+            }
+            let name = match section.caption {
+                Some(ref c) => c.trim().to_string(),
+                None => return ftd::e2("package name is missing", LOCK_FILE, section.line_number),
+            };
+            let version = Version::parse(
+                section
+                    .header
+                    .str(LOCK_FILE, section.line_number, "version")?,
+                LOCK_FILE,
+                section.line_number,
+            )?;
+            lock.packages.insert(name, version);
+        }
+        Ok(lock)
+    }
+
+    pub fn read(dir: &std::path::Path) -> ftd::p1::Result<Lock> {
+        let path = dir.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(s) => Lock::parse(s.as_str()),
+            Err(e) => ftd::e2(e.to_string(), LOCK_FILE, 0),
+        }
+    }
+
+    pub fn write(&self, dir: &std::path::Path) -> ftd::p1::Result<()> {
+        std::fs::write(dir.join(LOCK_FILE), self.to_string())
+            .or_else(|e| ftd::e2(e.to_string(), LOCK_FILE, 0))
+    }
+
+    // a fresh graph for the next document, keeping what is locked
+    pub(crate) fn for_document(&self) -> Lock {
+        Lock {
+            packages: self.packages.clone(),
+            resolved: Default::default(),
+            unversioned: Default::default(),
+        }
+    }
+
+    // what is left to lock once the graph is resolved, packages that are no
+    // longer imported with a version are dropped
+    pub(crate) fn pruned(&self) -> Lock {
+        Lock {
+            packages: self
+                .packages
+                .iter()
+                .filter(|(name, _)| self.resolved.contains_key(name.as_str()))
+                .map(|(name, version)| (name.to_string(), version.clone()))
+                .collect(),
+            resolved: Default::default(),
+            unversioned: Default::default(),
+        }
+    }
+
+    /// `name` is imported without a version, which is an error if the same
+    /// import graph imports it with one.
+    pub(crate) fn unversioned(
+        &mut self,
+        name: &str,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::p1::Result<()> {
+        if let Some((req, first_doc_id, first_line_number)) = self.resolved.get(name) {
+            return ftd::e2(
+                format!(
+                    "conflicting versions of {}: it is imported without a version here, but \
+                    {}:{} requires {}",
+                    name, first_doc_id, first_line_number, req
+                ),
+                doc_id,
+                line_number,
+            );
+        }
+        self.unversioned
+            .entry(name.to_string())
+            .or_insert_with(|| (doc_id.to_string(), line_number));
+        Ok(())
+    }
+
+    /// Picks the version of `name` to use for `req`. A version picked earlier
+    /// in the same import graph wins, and has to satisfy `req`. Otherwise the
+    /// locked version is used if it still satisfies `req`, and the newest
+    /// matching version from `available` if it does not.
+    pub(crate) fn resolve(
+        &mut self,
+        name: &str,
+        req: &VersionReq,
+        available: &[Version],
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::p1::Result<Version> {
+        if let Some((first_doc_id, first_line_number)) = self.unversioned.get(name) {
+            return ftd::e2(
+                format!(
+                    "conflicting versions of {}: {} is required here, but {}:{} imports it \
+                    without a version",
+                    name, req, first_doc_id, first_line_number
+                ),
+                doc_id,
+                line_number,
+            );
+        }
+        if let Some((first, first_doc_id, first_line_number)) = self.resolved.get(name) {
+            let version = &self.packages[name];
+            if req.matches(version) {
+                return Ok(version.clone());
+            }
+            return ftd::e2(
+                format!(
+                    "conflicting versions of {}: {} is required here, but {}:{} requires {} \
+                    which resolved to {}",
+                    name, req, first_doc_id, first_line_number, first, version
+                ),
+                doc_id,
+                line_number,
+            );
+        }
+
+        let version = match self.packages.get(name) {
+            Some(v) if req.matches(v) && available.contains(v) => v.clone(),
+            _ => match available.iter().filter(|v| req.matches(v)).max() {
+                Some(v) => v.clone(),
+                None => {
+                    return ftd::e2(
+                        format!("no version of {} matches {}", name, req),
+                        doc_id,
+                        line_number,
+                    )
+                }
+            },
+        };
+
+        self.packages.insert(name.to_string(), version.clone());
+        self.resolved.insert(
+            name.to_string(),
+            (req.clone(), doc_id.to_string(), line_number),
+        );
+        Ok(version)
+    }
+}
+
+impl ToString for Lock {
+    fn to_string(&self) -> String {
+        let sections = self
+            .packages
+            .iter()
+            .map(|(name, version)| ftd::p1::Section {
+                name: "package".to_string(),
+                caption: Some(name.to_string()),
+                header: ftd::p1::Header(vec![(0, "version".to_string(), version.to_string())]),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        format!("{}\n", ftd::p1::to_string(&sections))
+    }
+}
+
+/// name under which a specific version of a library is fetched from a
+/// `Library`, eg `fifthtry/ui@1.2.3`
+pub fn versioned_name(name: &str, version: &Version) -> String {
+    format!("{}@{}", name, version)
+}
+
+/// versions of `name` in a package store laid out as `<root>/<name>@<version>.ftd`
+pub fn versions_in_store(root: &std::path::Path, name: &str) -> Vec<Version> {
+    let (dir, file) = match name.rsplit_once('/') {
+        Some((dir, file)) => (root.join(dir), file),
+        None => (root.to_path_buf(), name),
+    };
+    let prefix = format!("{}@", file);
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let mut versions = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let file_name = e.file_name().to_string_lossy().to_string();
+            let version = file_name
+                .strip_prefix(prefix.as_str())?
+                .strip_suffix(".ftd")?;
+            Version::parse(version, "", 0).ok()
+        })
+        .collect::<Vec<_>>();
+    versions.sort();
+    versions
+}
+
+#[cfg(test)]
+mod test {
+    fn v(s: &str) -> super::Version {
+        super::Version::parse(s, "", 0).unwrap()
+    }
+
+    fn r(s: &str) -> super::VersionReq {
+        super::VersionReq::parse(s, "", 0).unwrap()
+    }
+
+    #[test]
+    fn version_req() {
+        assert!(r("1.2").matches(&v("1.2.0")));
+        assert!(r("1.2").matches(&v("1.9.3")));
+        assert!(!r("1.2").matches(&v("1.1.9")));
+        assert!(!r("1.2").matches(&v("2.0.0")));
+        assert!(r("0.3").matches(&v("0.3.7")));
+        assert!(!r("0.3").matches(&v("0.4.0")));
+        assert!(r("=1.2.3").matches(&v("1.2.3")));
+        assert!(!r("=1.2.3").matches(&v("1.2.4")));
+        assert!(super::VersionReq::parse("1.x", "", 0).is_err());
+    }
+
+    #[test]
+    fn resolve() {
+        let available = vec![v("1.1.0"), v("1.2.0"), v("1.4.1"), v("2.0.0")];
+
+        let mut lock: super::Lock = Default::default();
+        pretty_assertions::assert_eq!(
+            lock.resolve("a/ui", &r("1.2"), &available, "foo", 1)
+                .unwrap(),
+            v("1.4.1")
+        );
+        pretty_assertions::assert_eq!(
+            lock.resolve("a/ui", &r("1.4"), &available, "bar", 1)
+                .unwrap(),
+            v("1.4.1")
+        );
+        assert!(lock.resolve("a/ui", &r("2"), &available, "bar", 2).is_err());
+
+        let mut lock = super::Lock::parse(&lock.to_string()).unwrap();
+        lock.packages.insert("a/ui".to_string(), v("1.2.0"));
+        lock.packages.insert("a/old".to_string(), v("1.0.0"));
+        pretty_assertions::assert_eq!(
+            lock.resolve("a/ui", &r("1.1"), &available, "foo", 1)
+                .unwrap(),
+            v("1.2.0")
+        );
+        pretty_assertions::assert_eq!(
+            lock.pruned().to_string(),
+            "-- package: a/ui\nversion: 1.2.0\n"
+        );
+
+        // the same package can not be imported with and without a version
+        assert!(lock.unversioned("a/ui", "bar", 3).is_err());
+        let mut lock = lock.for_document();
+        lock.unversioned("a/ui", "bar", 3).unwrap();
+        assert!(lock
+            .resolve("a/ui", &r("1.1"), &available, "foo", 1)
+            .is_err());
+    }
+}
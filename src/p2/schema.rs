@@ -0,0 +1,498 @@
+//! JSON Schema (draft 7) for the data `TDoc::from_json()` accepts.
+//!
+//! Records are objects in which every field is required, optional fields can
+//! be `null` instead, or-type values are objects with the variant in `type`,
+//! same as `Document::get()` returns them. Kinds that can not be passed as
+//! data (`object`, `element`, `ftd.ui`, messages) are an error, except for
+//! component arguments, which are left out of the schema.
+
+const DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+impl<'a> ftd::p2::TDoc<'a> {
+    /// schema for values of the record, or-type or variable `name`, or for the
+    /// arguments of the component `name`
+    pub fn json_schema(&self, name: &str) -> ftd::p1::Result<serde_json::Value> {
+        let mut definitions = serde_json::Map::new();
+        let mut schema = match self.get_thing(0, name)? {
+            ftd::p2::Thing::Record(r) => {
+                let schema = record_schema(self, &r, &mut definitions)?;
+                inline(self, r.fields.values(), r.name.as_str(), &mut definitions);
+                schema
+            }
+            ftd::p2::Thing::OrType(e) => {
+                let schema = or_type_schema(self, &e, &mut definitions)?;
+                let kinds = e.variants.iter().flat_map(|v| v.fields.values());
+                inline(self, kinds, e.name.as_str(), &mut definitions);
+                schema
+            }
+            ftd::p2::Thing::OrTypeWithVariant { e, variant } => {
+                variant_schema(self, &e, variant.as_str(), &mut definitions)?
+            }
+            ftd::p2::Thing::Component(c) => arguments_schema(self, &c.arguments, &mut definitions)?,
+            ftd::p2::Thing::Variable(v) => kind_schema(self, &v.value.kind(), &mut definitions)?,
+        };
+
+        let object = schema.as_object_mut().expect("schemas are objects");
+        object.insert("$schema".to_string(), serde_json::json!(DRAFT));
+        object.insert("title".to_string(), serde_json::json!(name));
+        if !definitions.is_empty() {
+            object.insert(
+                "definitions".to_string(),
+                serde_json::Value::Object(definitions),
+            );
+        }
+        Ok(schema)
+    }
+}
+
+impl ftd::p2::Document {
+    /// see `TDoc::json_schema()`
+    pub fn json_schema(&self, name: &str) -> ftd::p1::Result<serde_json::Value> {
+        let doc = ftd::p2::TDoc {
+            name: self.name.as_str(),
+            aliases: &self.aliases,
+            bag: &self.data,
+            local_variables: &mut Default::default(),
+        };
+        doc.json_schema(name)
+    }
+}
+
+// `foo/bar#person` can not be used in a `$ref` as is
+fn definition_name(full_name: &str) -> String {
+    full_name.replace('/', ".").replace('#', ".")
+}
+
+fn reference(full_name: &str) -> serde_json::Value {
+    serde_json::json!({ "$ref": format!("#/definitions/{}", definition_name(full_name)) })
+}
+
+// the schema asked for is returned inline, it stays in `definitions` only if
+// one of its fields, `kinds`, refers to it
+fn inline<'k, I>(
+    doc: &ftd::p2::TDoc,
+    mut kinds: I,
+    full_name: &str,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) where
+    I: Iterator<Item = &'k ftd::p2::Kind>,
+{
+    let mut seen = Default::default();
+    if !kinds.any(|k| refers_to(doc, k, full_name, &mut seen)) {
+        definitions.remove(definition_name(full_name).as_str());
+    }
+}
+
+// whether `kind` is the record or or-type `full_name`, or uses it through the
+// fields of the records and or-types it is made of
+fn refers_to(
+    doc: &ftd::p2::TDoc,
+    kind: &ftd::p2::Kind,
+    full_name: &str,
+    seen: &mut std::collections::BTreeSet<String>,
+) -> bool {
+    match kind {
+        ftd::p2::Kind::Record { name, .. }
+        | ftd::p2::Kind::OrType { name }
+        | ftd::p2::Kind::OrTypeWithVariant { name, .. } => {
+            if name == full_name {
+                return true;
+            }
+            if !seen.insert(name.to_string()) {
+                return false;
+            }
+            let kinds = match doc.get_thing(0, name.as_str()) {
+                Ok(ftd::p2::Thing::Record(r)) => r.fields.into_iter().map(|(_, k)| k).collect(),
+                Ok(ftd::p2::Thing::OrType(e)) => e
+                    .variants
+                    .into_iter()
+                    .flat_map(|v| v.fields.into_iter().map(|(_, k)| k))
+                    .collect(),
+                _ => vec![],
+            };
+            kinds.iter().any(|k| refers_to(doc, k, full_name, seen))
+        }
+        ftd::p2::Kind::Map { kind }
+        | ftd::p2::Kind::List { kind, .. }
+        | ftd::p2::Kind::Optional { kind } => refers_to(doc, kind, full_name, seen),
+        _ => false,
+    }
+}
+
+fn kind_schema(
+    doc: &ftd::p2::TDoc,
+    kind: &ftd::p2::Kind,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) -> ftd::p1::Result<serde_json::Value> {
+    Ok(match kind {
+        ftd::p2::Kind::String { default, .. } => {
+            with_default(serde_json::json!({"type": "string"}), default, |d| {
+                Some(serde_json::json!(d))
+            })
+        }
+        ftd::p2::Kind::Integer { default } => {
+            with_default(serde_json::json!({"type": "integer"}), default, |d| {
+                d.parse::<i64>().ok().map(|d| serde_json::json!(d))
+            })
+        }
+        ftd::p2::Kind::Decimal { default } => {
+            with_default(serde_json::json!({"type": "number"}), default, |d| {
+                d.parse::<f64>().ok().map(|d| serde_json::json!(d))
+            })
+        }
+        ftd::p2::Kind::Boolean { default } => {
+            with_default(serde_json::json!({"type": "boolean"}), default, |d| {
+                d.parse::<bool>().ok().map(|d| serde_json::json!(d))
+            })
+        }
+        ftd::p2::Kind::Record { name, .. } => {
+            let definition = definition_name(name.as_str());
+            if !definitions.contains_key(definition.as_str()) {
+                let record = doc.get_record(0, name.as_str())?;
+                record_schema(doc, &record, definitions)?;
+            }
+            reference(name.as_str())
+        }
+        ftd::p2::Kind::OrType { name } => {
+            let definition = definition_name(name.as_str());
+            if !definitions.contains_key(definition.as_str()) {
+                let e = doc.get_or_type(0, name.as_str())?;
+                or_type_schema(doc, &e, definitions)?;
+            }
+            reference(name.as_str())
+        }
+        ftd::p2::Kind::OrTypeWithVariant { name, variant } => {
+            let e = doc.get_or_type(0, name.as_str())?;
+            variant_schema(doc, &e, variant.as_str(), definitions)?
+        }
+        ftd::p2::Kind::Map { kind } => serde_json::json!({
+            "type": "object",
+            "additionalProperties": kind_schema(doc, kind, definitions)?,
+        }),
+        ftd::p2::Kind::List { kind, .. } => serde_json::json!({
+            "type": "array",
+            "items": kind_schema(doc, kind, definitions)?,
+        }),
+        ftd::p2::Kind::Optional { kind } => serde_json::json!({
+            "anyOf": [kind_schema(doc, kind, definitions)?, {"type": "null"}],
+        }),
+        ftd::p2::Kind::Object { .. }
+        | ftd::p2::Kind::Element
+        | ftd::p2::Kind::Elements
+        | ftd::p2::Kind::Message
+        | ftd::p2::Kind::StringMessage
+        | ftd::p2::Kind::IntMessage
+        | ftd::p2::Kind::UI { .. } => {
+            return ftd::e2(format!("{:?} can not be passed as data", kind), doc.name, 0)
+        }
+    })
+}
+
+// whether `TDoc::from_json()` can read values of `kind`
+fn is_data(kind: &ftd::p2::Kind) -> bool {
+    match kind {
+        ftd::p2::Kind::Map { kind }
+        | ftd::p2::Kind::List { kind, .. }
+        | ftd::p2::Kind::Optional { kind } => is_data(kind),
+        ftd::p2::Kind::Object { .. }
+        | ftd::p2::Kind::Element
+        | ftd::p2::Kind::Elements
+        | ftd::p2::Kind::Message
+        | ftd::p2::Kind::StringMessage
+        | ftd::p2::Kind::IntMessage
+        | ftd::p2::Kind::UI { .. } => false,
+        _ => true,
+    }
+}
+
+// defaults that refer to other variables (`$foo`) are left out
+fn with_default<F>(
+    mut schema: serde_json::Value,
+    default: &Option<String>,
+    parse: F,
+) -> serde_json::Value
+where
+    F: FnOnce(&str) -> Option<serde_json::Value>,
+{
+    if let Some(value) = default
+        .as_deref()
+        .filter(|d| !d.starts_with('$'))
+        .and_then(parse)
+    {
+        schema["default"] = value;
+    }
+    schema
+}
+
+// adds the record to `definitions` (before its fields, so recursive records
+// terminate) and returns its schema
+fn record_schema(
+    doc: &ftd::p2::TDoc,
+    record: &ftd::p2::Record,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) -> ftd::p1::Result<serde_json::Value> {
+    let definition = definition_name(record.name.as_str());
+    definitions.insert(definition.clone(), serde_json::json!({}));
+    let schema = fields_schema(doc, record, None, definitions)?;
+    definitions.insert(definition, schema.clone());
+    Ok(schema)
+}
+
+fn fields_schema(
+    doc: &ftd::p2::TDoc,
+    record: &ftd::p2::Record,
+    variant: Option<&str>,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) -> ftd::p1::Result<serde_json::Value> {
+    let mut properties = serde_json::Map::new();
+    let mut required = vec![];
+    if let Some(variant) = variant {
+        properties.insert("type".to_string(), serde_json::json!({ "const": variant }));
+        required.push("type".to_string());
+    }
+    for name in record.order.iter() {
+        let kind = match record.fields.get(name) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        properties.insert(name.to_string(), kind_schema(doc, kind, definitions)?);
+        required.push(name.to_string());
+    }
+    Ok(serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    }))
+}
+
+fn or_type_schema(
+    doc: &ftd::p2::TDoc,
+    e: &ftd::OrType,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) -> ftd::p1::Result<serde_json::Value> {
+    let definition = definition_name(e.name.as_str());
+    definitions.insert(definition.clone(), serde_json::json!({}));
+    let mut variants = vec![];
+    for v in e.variants.iter() {
+        let variant = v.variant_name().unwrap_or(v.name.as_str());
+        variants.push(fields_schema(doc, v, Some(variant), definitions)?);
+    }
+    let schema = serde_json::json!({ "oneOf": variants });
+    definitions.insert(definition, schema.clone());
+    Ok(schema)
+}
+
+fn variant_schema(
+    doc: &ftd::p2::TDoc,
+    e: &ftd::OrType,
+    variant: &str,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) -> ftd::p1::Result<serde_json::Value> {
+    match e
+        .variants
+        .iter()
+        .find(|v| v.variant_name() == Some(variant))
+    {
+        Some(v) => fields_schema(doc, v, Some(variant), definitions),
+        None => ftd::e2(
+            format!("{} has no variant {}", e.name, variant),
+            doc.name,
+            0,
+        ),
+    }
+}
+
+// arguments without a default value, that are not optional, are required
+fn arguments_schema(
+    doc: &ftd::p2::TDoc,
+    arguments: &std::collections::BTreeMap<String, ftd::p2::Kind>,
+    definitions: &mut serde_json::Map<String, serde_json::Value>,
+) -> ftd::p1::Result<serde_json::Value> {
+    let mut properties = serde_json::Map::new();
+    let mut required = vec![];
+    for (name, kind) in arguments.iter().filter(|(_, kind)| is_data(kind)) {
+        properties.insert(name.to_string(), kind_schema(doc, kind, definitions)?);
+        if !kind.is_optional() && kind.get_default_value_str().is_none() {
+            required.push(name.to_string());
+        }
+    }
+    Ok(serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn json_schema() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- or-type lead:
+
+                --- individual:
+                caption name:
+
+                --- company:
+                caption name:
+                integer employees: 50
+
+                -- record person:
+                caption name:
+                optional body bio:
+                string list tags:
+                lead source:
+
+                -- ftd.text card:
+                caption title:
+                optional integer size:
+                text: $title
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        pretty_assertions::assert_eq!(
+            doc.json_schema("person").unwrap(),
+            serde_json::json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "person",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "bio": {"anyOf": [{"type": "string"}, {"type": "null"}]},
+                    "tags": {"type": "array", "items": {"type": "string"}},
+                    "source": {"$ref": "#/definitions/foo.bar.lead"},
+                },
+                "required": ["name", "bio", "tags", "source"],
+                "definitions": {
+                    "foo.bar.lead": {
+                        "oneOf": [
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "type": {"const": "individual"},
+                                    "name": {"type": "string"},
+                                },
+                                "required": ["type", "name"],
+                            },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "type": {"const": "company"},
+                                    "name": {"type": "string"},
+                                    "employees": {"type": "integer", "default": 50},
+                                },
+                                "required": ["type", "name", "employees"],
+                            },
+                        ],
+                    },
+                },
+            })
+        );
+
+        let card = doc.json_schema("card").unwrap();
+        pretty_assertions::assert_eq!(
+            card["properties"]["title"],
+            serde_json::json!({"type": "string"})
+        );
+        pretty_assertions::assert_eq!(
+            card["properties"]["size"],
+            serde_json::json!({"anyOf": [{"type": "integer"}, {"type": "null"}]})
+        );
+        pretty_assertions::assert_eq!(card["required"], serde_json::json!(["title"]));
+    }
+
+    #[test]
+    fn from_json() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- or-type lead:
+
+                --- individual:
+                caption name:
+
+                -- record person:
+                caption name:
+                lead source:
+                person list friends:
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        // the record refers to itself, so it is kept in the definitions
+        let schema = doc.json_schema("person").unwrap();
+        pretty_assertions::assert_eq!(
+            schema["properties"]["friends"]["items"],
+            serde_json::json!({"$ref": "#/definitions/foo.bar.person"})
+        );
+        assert!(schema["definitions"]["foo.bar.person"].is_object());
+
+        let tdoc = ftd::p2::TDoc {
+            name: "foo/bar",
+            aliases: &doc.aliases,
+            bag: &doc.data,
+            local_variables: &mut Default::default(),
+        };
+        let kind = ftd::p2::Kind::Map {
+            kind: Box::new(ftd::p2::Kind::Record {
+                name: "foo/bar#person".to_string(),
+                default: None,
+            }),
+        };
+        let person = |source| {
+            serde_json::json!({
+                "amit": {"name": "Amit", "source": source, "friends": []},
+            })
+        };
+
+        let value = tdoc
+            .from_json_(
+                0,
+                &person(serde_json::json!({"type": "individual", "name": "Amit"})),
+                kind.clone(),
+            )
+            .unwrap();
+        match value {
+            ftd::Value::Map { data, .. } => match &data["amit"] {
+                ftd::Value::Record { fields, .. } => pretty_assertions::assert_eq!(
+                    fields["source"],
+                    ftd::PropertyValue::Value {
+                        value: ftd::Value::OrType {
+                            name: "foo/bar#lead".to_string(),
+                            variant: "individual".to_string(),
+                            fields: std::array::IntoIter::new([(
+                                "name".to_string(),
+                                ftd::PropertyValue::Value {
+                                    value: ftd::Value::String {
+                                        text: "Amit".to_string(),
+                                        source: ftd::TextSource::Header,
+                                    },
+                                },
+                            )])
+                            .collect(),
+                        },
+                    }
+                ),
+                t => panic!("expected record, found: {:?}", t),
+            },
+            t => panic!("expected map, found: {:?}", t),
+        }
+
+        assert!(tdoc
+            .from_json_(
+                0,
+                &person(serde_json::json!({"type": "company", "name": "Amit"})),
+                kind
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! A versioned JSON form of an interpreted `Document`, so it can be written to
+//! disk and loaded by a later process, that can `rerender()` or `to_rt()` it
+//! without interpreting the source again.
+//!
+//! ```json
+//! {"version": 2, "ftd": "0.1.18", "document": {...}}
+//! ```
+//!
+//! `version` is `SNAPSHOT_VERSION`, `ftd` is the version of this crate that
+//! wrote the snapshot, and `document` is the serde form of `Document` (bag,
+//! instructions, rendered `main` and source sections). `SNAPSHOT_VERSION` is
+//! bumped whenever that form changes, and as the form follows the types of
+//! this crate, snapshots are only loaded by the `ftd` release that wrote
+//! them. Other snapshots have to be made again from the source.
+
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+#[derive(serde::Serialize)]
+struct Snapshot<'a> {
+    version: u32,
+    ftd: &'static str,
+    document: &'a ftd::p2::Document,
+}
+
+#[derive(serde::Deserialize)]
+struct Header {
+    version: u32,
+    ftd: String,
+}
+
+#[derive(serde::Deserialize)]
+struct Stored {
+    document: ftd::p2::Document,
+}
+
+impl ftd::p2::Document {
+    pub fn to_snapshot(&self) -> ftd::p1::Result<String> {
+        Ok(serde_json::to_string(&Snapshot {
+            version: SNAPSHOT_VERSION,
+            ftd: env!("CARGO_PKG_VERSION"),
+            document: self,
+        })?)
+    }
+
+    pub fn from_snapshot(snapshot: &str) -> ftd::p1::Result<ftd::p2::Document> {
+        let header: Header = serde_json::from_str(snapshot)?;
+        if header.version != SNAPSHOT_VERSION || header.ftd != env!("CARGO_PKG_VERSION") {
+            return ftd::e2(
+                format!(
+                    "snapshot version {} (written by ftd {}) is not supported, expected {} \
+                    (written by ftd {})",
+                    header.version,
+                    header.ftd,
+                    SNAPSHOT_VERSION,
+                    env!("CARGO_PKG_VERSION")
+                ),
+                "",
+                0,
+            );
+        }
+        let stored: Stored = serde_json::from_str(snapshot)?;
+        Ok(stored.document)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn snapshot() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- record person:
+                caption name:
+                integer age:
+
+                -- person list people:
+
+                -- people: Amit
+                age: 30
+
+                -- boolean show: true
+
+                -- ftd.column card:
+                caption title:
+
+                --- ftd.text: $title
+                if: $show
+                region: h1
+
+                -- card: Hello
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let snapshot = doc.to_snapshot().unwrap();
+        let loaded = ftd::p2::Document::from_snapshot(snapshot.as_str()).unwrap();
+        pretty_assertions::assert_eq!(loaded, doc);
+        pretty_assertions::assert_eq!(
+            loaded.to_rt("main", "foo/bar"),
+            doc.to_rt("main", "foo/bar")
+        );
+
+        let old = snapshot.replacen(
+            format!("\"version\":{}", super::SNAPSHOT_VERSION).as_str(),
+            "\"version\":0",
+            1,
+        );
+        assert!(ftd::p2::Document::from_snapshot(old.as_str()).is_err());
+
+        let other = snapshot.replacen(
+            format!("\"ftd\":\"{}\"", env!("CARGO_PKG_VERSION")).as_str(),
+            "\"ftd\":\"0.0.1\"",
+            1,
+        );
+        assert!(ftd::p2::Document::from_snapshot(other.as_str()).is_err());
+    }
+}
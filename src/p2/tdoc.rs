@@ -455,7 +455,7 @@ impl<'a> TDoc<'a> {
         )
     }
 
-    fn from_json_(
+    pub(crate) fn from_json_(
         &self,
         line_number: usize,
         json: &serde_json::Value,
@@ -501,35 +501,89 @@ impl<'a> TDoc<'a> {
             },
             ftd::p2::Kind::Record { name, .. } => {
                 let rec_fields = self.get_record(line_number, &name)?.fields;
-                let mut fields: std::collections::BTreeMap<String, ftd::PropertyValue> =
-                    Default::default();
+                let fields = if let serde_json::Value::Object(o) = json {
+                    self.from_json_fields(line_number, o, rec_fields)?
+                } else {
+                    return ftd::e2(
+                        format!("expected object of record type, found: {}", json),
+                        self.name,
+                        line_number,
+                    );
+                };
+                ftd::Value::Record { name, fields }
+            }
+            ftd::p2::Kind::OrType { ref name }
+            | ftd::p2::Kind::OrTypeWithVariant { ref name, .. } => {
+                let o = match json {
+                    serde_json::Value::Object(o) => o,
+                    _ => {
+                        return ftd::e2(
+                            format!("expected object of or-type, found: {}", json),
+                            self.name,
+                            line_number,
+                        )
+                    }
+                };
+                let variant = match o.get("type") {
+                    Some(serde_json::Value::String(variant)) => variant,
+                    _ => {
+                        return ftd::e2(
+                            format!("variant is missing in `type`, found: {}", json),
+                            self.name,
+                            line_number,
+                        )
+                    }
+                };
+                if let ftd::p2::Kind::OrTypeWithVariant {
+                    variant: expected, ..
+                } = &kind
+                {
+                    if expected != variant {
+                        return ftd::e2(
+                            format!("expected variant {}, found: {}", expected, variant),
+                            self.name,
+                            line_number,
+                        );
+                    }
+                }
+                let e = self.get_or_type(line_number, name)?;
+                let v = match e
+                    .variants
+                    .into_iter()
+                    .find(|v| v.variant_name() == Some(variant))
+                {
+                    Some(v) => v,
+                    None => {
+                        return ftd::e2(
+                            format!("{} has no variant {}", name, variant),
+                            self.name,
+                            line_number,
+                        )
+                    }
+                };
+                ftd::Value::OrType {
+                    name: name.to_string(),
+                    variant: variant.to_string(),
+                    fields: self.from_json_fields(line_number, o, v.fields)?,
+                }
+            }
+            ftd::p2::Kind::Map { kind } => {
+                let mut data: std::collections::BTreeMap<String, ftd::Value> = Default::default();
                 if let serde_json::Value::Object(o) = json {
-                    for (key, kind) in rec_fields {
-                        let val = match o.get(&key) {
-                            Some(v) => v,
-                            None => {
-                                return ftd::e2(
-                                    format!("key not found: {}", key.as_str()),
-                                    self.name,
-                                    line_number,
-                                )
-                            }
-                        };
-                        fields.insert(
-                            key,
-                            ftd::PropertyValue::Value {
-                                value: self.from_json_(line_number, val, kind)?,
-                            },
+                    for (key, item) in o {
+                        data.insert(
+                            key.to_string(),
+                            self.from_json_(line_number, item, kind.as_ref().to_owned())?,
                         );
                     }
                 } else {
                     return ftd::e2(
-                        format!("expected object of record type, found: {}", json),
+                        format!("expected object of map type, found: {}", json),
                         self.name,
                         line_number,
                     );
                 }
-                ftd::Value::Record { name, fields }
+                ftd::Value::Map { data, kind: *kind }
             }
             ftd::p2::Kind::List { kind, .. } => {
                 let kind = kind.as_ref();
@@ -562,15 +616,45 @@ impl<'a> TDoc<'a> {
                     _ => self.from_json_(line_number, json, kind)?,
                 }
             }
-            t => unimplemented!(
-                "{:?} not yet implemented, line number: {}, doc: {}",
-                t,
-                line_number,
-                self.name.to_string()
-            ),
+            t => {
+                return ftd::e2(
+                    format!("{:?} can not be read from json", t),
+                    self.name,
+                    line_number,
+                )
+            }
         })
     }
 
+    // values of `rec_fields` in the json object `o`, every field is required
+    fn from_json_fields(
+        &self,
+        line_number: usize,
+        o: &serde_json::Map<String, serde_json::Value>,
+        rec_fields: std::collections::BTreeMap<String, ftd::p2::Kind>,
+    ) -> ftd::p1::Result<std::collections::BTreeMap<String, ftd::PropertyValue>> {
+        let mut fields: std::collections::BTreeMap<String, ftd::PropertyValue> = Default::default();
+        for (key, kind) in rec_fields {
+            let val = match o.get(&key) {
+                Some(v) => v,
+                None => {
+                    return ftd::e2(
+                        format!("key not found: {}", key.as_str()),
+                        self.name,
+                        line_number,
+                    )
+                }
+            };
+            fields.insert(
+                key,
+                ftd::PropertyValue::Value {
+                    value: self.from_json_(line_number, val, kind)?,
+                },
+            );
+        }
+        Ok(fields)
+    }
+
     pub fn from_json_rows(
         &self,
         section: &ftd::p1::Section,
@@ -606,12 +690,13 @@ impl<'a> TDoc<'a> {
                         kind: kind.to_owned(),
                     }
                 }
-                t => unimplemented!(
-                    "{:?} not yet implemented, line number: {}, doc: {}",
-                    t,
-                    line_number,
-                    doc.name.to_string()
-                ),
+                t => {
+                    return ftd::e2(
+                        format!("{:?} can not be read from json rows", t),
+                        doc.name,
+                        line_number,
+                    )
+                }
             })
         }
     }
@@ -712,12 +797,13 @@ impl<'a> TDoc<'a> {
                     },
                 )?,
             },
-            t => unimplemented!(
-                "{:?} not yet implemented, line number: {}, doc: {}",
-                t,
-                line_number,
-                self.name.to_string()
-            ),
+            t => {
+                return ftd::e2(
+                    format!("{:?} can not be read from a json row", t),
+                    self.name,
+                    line_number,
+                )
+            }
         })
     }
 
@@ -746,7 +832,14 @@ impl<'a> TDoc<'a> {
                     )
                 }
             },
-            (_, _, Some(_)) => unimplemented!(),
+            (_, _, Some(_)) => {
+                return self.err(
+                    "unexpected name",
+                    name,
+                    "resolve_name_without_full_path",
+                    line_number,
+                )
+            }
             (None, v, None) => v.to_string(),
         })
     }
@@ -798,7 +891,14 @@ impl<'a> TDoc<'a> {
                 },
             },
             (None, v, None) => v.to_string(),
-            _ => unimplemented!(),
+            _ => {
+                return self.err(
+                    "unexpected name",
+                    name,
+                    "resolve_name_with_instruction",
+                    line_number,
+                )
+            }
         })
     }
 
@@ -856,7 +956,7 @@ impl<'a> TDoc<'a> {
                 None => format!("{}#{}.{}", self.name, v, c),
             },
             (None, v, None) => format!("{}#{}", self.name, v),
-            _ => unimplemented!(),
+            _ => return self.err("unexpected name", name, "resolve_name", line_number),
         })
     }
 
@@ -1036,10 +1136,13 @@ impl<'a> TDoc<'a> {
                 Some(a) => Some((a, remaining_value)),
                 None => match root_name {
                     Some(doc_name) => match doc.aliases.get(doc_name) {
-                        Some(g) => doc
-                            .bag
-                            .get(format!("{}#{}", g, name).as_str())
-                            .map(|v| (v.clone(), remaining_value)),
+                        Some(g) => {
+                            let full_name = format!("{}#{}", g, name);
+                            doc.bag
+                                .get(full_name.as_str())
+                                .cloned()
+                                .map(|v| (v, remaining_value))
+                        }
                         None => None,
                     },
                     None => None,
@@ -0,0 +1,200 @@
+/// A rust type with a matching `-- record` (or `-- or-type`) declaration,
+/// usually implemented with `#[derive(ftd::FtdRecord)]` (needs the `derive`
+/// feature).
+pub trait FtdRecord {
+    /// name of the record in ftd, eg `toc-item`
+    fn ftd_name() -> String;
+
+    /// adds the declaration of this type to `declarations`, after the
+    /// declarations of the records and or-types it uses
+    fn ftd_declarations(declarations: &mut Declarations);
+
+    fn ftd_source() -> String {
+        let mut declarations: Declarations = Default::default();
+        Self::ftd_declarations(&mut declarations);
+        declarations.to_source()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Declarations {
+    started: std::collections::BTreeSet<String>,
+    sources: Vec<String>,
+}
+
+impl Declarations {
+    /// `false` if `name` is already declared, or is being declared, which is
+    /// the case for records that refer to themselves
+    pub fn start(&mut self, name: &str) -> bool {
+        self.started.insert(name.to_string())
+    }
+
+    pub fn finish(&mut self, source: String) {
+        self.sources.push(source);
+    }
+
+    pub fn to_source(&self) -> String {
+        format!("{}\n", self.sources.join("\n\n\n"))
+    }
+}
+
+/// A `Library` that serves the declarations of registered `FtdRecord` types as
+/// modules, and everything else from the wrapped library.
+pub struct TypedLibrary<'a> {
+    lib: &'a dyn ftd::p2::Library,
+    modules: std::collections::BTreeMap<String, Declarations>,
+}
+
+impl<'a> TypedLibrary<'a> {
+    pub fn new(lib: &'a dyn ftd::p2::Library) -> Self {
+        TypedLibrary {
+            lib,
+            modules: Default::default(),
+        }
+    }
+
+    /// makes `T`, and the types it uses, available as `-- import: <module>`
+    pub fn register<T: FtdRecord>(&mut self, module: &str) -> &mut Self {
+        T::ftd_declarations(self.modules.entry(module.to_string()).or_default());
+        self
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl ftd::p2::Library for TypedLibrary<'_> {
+    async fn get(&self, name: &str, doc: &ftd::p2::TDoc) -> Option<String> {
+        match self.modules.get(name) {
+            Some(declarations) => Some(declarations.to_source()),
+            None => self.lib.get(name, doc).await,
+        }
+    }
+
+    async fn versions(&self, name: &str, doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        self.lib.versions(name, doc).await
+    }
+
+    async fn process(
+        &self,
+        section: &ftd::p1::Section,
+        doc: &ftd::p2::TDoc,
+    ) -> ftd::p1::Result<ftd::Value> {
+        self.lib.process(section, doc).await
+    }
+}
+
+#[cfg(not(feature = "async"))]
+impl ftd::p2::Library for TypedLibrary<'_> {
+    fn get(&self, name: &str, doc: &ftd::p2::TDoc) -> Option<String> {
+        match self.modules.get(name) {
+            Some(declarations) => Some(declarations.to_source()),
+            None => self.lib.get(name, doc),
+        }
+    }
+
+    fn versions(&self, name: &str, doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        self.lib.versions(name, doc)
+    }
+
+    fn process(
+        &self,
+        section: &ftd::p1::Section,
+        doc: &ftd::p2::TDoc,
+    ) -> ftd::p1::Result<ftd::Value> {
+        self.lib.process(section, doc)
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod test {
+    #[derive(ftd::FtdRecord, serde::Deserialize, Debug, PartialEq)]
+    #[serde(tag = "type", rename_all = "kebab-case")]
+    enum Shape {
+        Dot,
+        Circle { radius: f64 },
+    }
+
+    #[derive(ftd::FtdRecord, serde::Deserialize, Debug, PartialEq)]
+    struct Person {
+        #[ftd(caption)]
+        name: String,
+        #[ftd(body)]
+        bio: Option<String>,
+        age: i64,
+        tags: Vec<String>,
+        shape: Option<Shape>,
+    }
+
+    #[test]
+    fn ftd_source() {
+        pretty_assertions::assert_eq!(
+            <Person as super::FtdRecord>::ftd_source(),
+            indoc::indoc!(
+                "
+                -- or-type shape:
+
+                --- dot:
+
+                --- circle:
+                decimal radius:
+
+
+                -- record person:
+                caption name:
+                optional body bio:
+                integer age:
+                string list tags:
+                optional shape shape:
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn typed_instances() {
+        let lib = ftd::p2::TestLibrary {};
+        let mut typed = super::TypedLibrary::new(&lib);
+        typed.register::<Person>("types");
+
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- import: types
+
+                -- types.person: Amit
+                age: 30
+                tags: rust
+                tags: ftd
+
+                Amit is building ftd.
+
+                -- types.person: Arpita
+                age: 25
+                "
+            ),
+            &typed,
+        )
+        .expect("found error");
+
+        pretty_assertions::assert_eq!(
+            doc.typed_instances::<Person>().unwrap(),
+            vec![
+                Person {
+                    name: "Amit".to_string(),
+                    bio: Some("Amit is building ftd.".to_string()),
+                    age: 30,
+                    tags: vec!["rust".to_string(), "ftd".to_string()],
+                    shape: None,
+                },
+                Person {
+                    name: "Arpita".to_string(),
+                    bio: None,
+                    age: 25,
+                    tags: vec![],
+                    shape: None,
+                },
+            ]
+        );
+    }
+}
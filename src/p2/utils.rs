@@ -2,7 +2,7 @@ pub fn parse_import(
     c: &Option<String>,
     doc_id: &str,
     line_number: usize,
-) -> ftd::p1::Result<(String, String)> {
+) -> ftd::p1::Result<(String, String, Option<ftd::p2::VersionReq>)> {
     let v = match c {
         Some(v) => v.trim(),
         None => {
@@ -14,31 +14,48 @@ pub fn parse_import(
         }
     };
 
-    if v.contains(" as ") {
+    let (v, alias) = if v.contains(" as ") {
         let mut parts = v.splitn(2, " as ");
-        return match (parts.next(), parts.next()) {
-            (Some(n), Some(a)) => Ok((n.to_string(), a.to_string())),
-            _ => ftd::e2(
-                "invalid use of keyword as in import statement",
-                doc_id,
-                line_number,
-            ),
-        };
+        match (parts.next(), parts.next()) {
+            (Some(n), Some(a)) => (n.trim(), Some(a)),
+            _ => {
+                return ftd::e2(
+                    "invalid use of keyword as in import statement",
+                    doc_id,
+                    line_number,
+                )
+            }
+        }
+    } else {
+        (v, None)
+    };
+
+    // `-- import: fifthtry/ui@1.2`
+    let (v, version) = match v.split_once('@') {
+        Some((n, version)) => (
+            n.trim(),
+            Some(ftd::p2::VersionReq::parse(version, doc_id, line_number)?),
+        ),
+        None => (v, None),
+    };
+
+    if let Some(a) = alias {
+        return Ok((v.to_string(), a.to_string(), version));
     }
 
     if v.contains('/') {
         let mut parts = v.rsplitn(2, '/');
         return match (parts.next(), parts.next()) {
-            (Some(t), Some(_)) => Ok((v.to_string(), t.to_string())),
+            (Some(t), Some(_)) => Ok((v.to_string(), t.to_string(), version)),
             _ => ftd::e2("doc id must contain /", doc_id, line_number),
         };
     }
 
     if let Some((t, _)) = v.split_once('.') {
-        return Ok((v.to_string(), t.to_string()));
+        return Ok((v.to_string(), t.to_string(), version));
     }
 
-    Ok((v.to_string(), v.to_string()))
+    Ok((v.to_string(), v.to_string(), version))
 }
 
 pub fn boolean_and_ref(
@@ -994,10 +1011,17 @@ pub fn bool_optional(
 mod test {
     macro_rules! p {
         ($s:expr, $id: expr, $alias: expr) => {
+            p!($s, $id, $alias, None)
+        };
+        ($s:expr, $id: expr, $alias: expr, $version: expr) => {
             assert_eq!(
                 super::parse_import(&Some($s.to_string()), $id, 0)
                     .unwrap_or_else(|e| panic!("{}", e)),
-                ($id.to_string(), $alias.to_string())
+                (
+                    $id.to_string(),
+                    $alias.to_string(),
+                    $version.map(|v| ftd::p2::VersionReq::parse(v, "", 0).unwrap())
+                )
             )
         };
     }
@@ -1010,6 +1034,10 @@ mod test {
         p!("a/b", "a/b", "b");
         p!("a", "a", "a");
         p!("a as b", "a", "b");
+        p!("a/b/c@1.2", "a/b/c", "c", Some("1.2"));
+        p!("a/b@=1.2.3 as foo", "a/b", "foo", Some("=1.2.3"));
+        p!("a@0.1", "a", "a", Some("0.1"));
+        assert!(super::parse_import(&Some("a/b@latest".to_string()), "a/b", 0).is_err());
     }
 }
 
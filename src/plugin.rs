@@ -0,0 +1,157 @@
+/// A native component implemented in Rust, available as `ftd.<name>` in the
+/// documents interpreted with a `Library` that returns it from `plugins()`.
+///
+/// Along with `arguments()` the component accepts all the common arguments
+/// (`padding`, `width`, `if`, events etc), those are applied to the node
+/// returned by `render()`.
+pub trait Plugin: Send + Sync {
+    fn name(&self) -> String;
+
+    fn arguments(&self) -> Vec<(String, ftd::p2::Kind)>;
+
+    /// `properties` only contains the arguments declared by `arguments()`,
+    /// optional arguments that were not passed are missing. `Node::text` is
+    /// written to the html as is, so anything user provided must be escaped.
+    fn render(
+        &self,
+        properties: &std::collections::BTreeMap<String, ftd::Value>,
+        doc_id: &str,
+    ) -> ftd::p1::Result<ftd::Node>;
+}
+
+/// the plugins of the document being rendered, by `ftd#<name>`
+#[derive(Clone, Default)]
+pub struct Plugins(std::collections::BTreeMap<String, std::sync::Arc<dyn Plugin>>);
+
+impl Plugins {
+    /// `plugins` are added to `bag`, a plugin can not replace a kernel
+    /// component that ships with ftd or another plugin
+    pub(crate) fn from_library(
+        plugins: Vec<std::sync::Arc<dyn Plugin>>,
+        bag: &mut std::collections::BTreeMap<String, ftd::p2::Thing>,
+        doc_id: &str,
+    ) -> ftd::p1::Result<Plugins> {
+        let mut map = std::collections::BTreeMap::new();
+        for plugin in plugins {
+            let full_name = format!("ftd#{}", plugin.name());
+            if bag.contains_key(full_name.as_str()) {
+                return ftd::e2(format!("plugin can not override {}", full_name), doc_id, 0);
+            }
+            bag.insert(
+                full_name.clone(),
+                ftd::p2::Thing::Component(ftd::p2::element::native_function(&*plugin)),
+            );
+            map.insert(full_name, plugin);
+        }
+        Ok(Plugins(map))
+    }
+
+    pub(crate) fn get(&self, full_name: &str) -> Option<&dyn Plugin> {
+        self.0.get(full_name).map(|p| &**p)
+    }
+}
+
+impl std::fmt::Debug for Plugins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.0.keys()).finish()
+    }
+}
+
+impl PartialEq for Plugins {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.keys().eq(other.0.keys())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    struct Badge(&'static str);
+
+    impl super::Plugin for Badge {
+        fn name(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn arguments(&self) -> Vec<(String, ftd::p2::Kind)> {
+            vec![
+                ("label".to_string(), ftd::p2::Kind::caption()),
+                ("tone".to_string(), ftd::p2::Kind::string().into_optional()),
+            ]
+        }
+
+        fn render(
+            &self,
+            properties: &std::collections::BTreeMap<String, ftd::Value>,
+            doc_id: &str,
+        ) -> ftd::p1::Result<ftd::Node> {
+            let label = ftd::p2::utils::string("label", properties, doc_id, 0)?;
+            let tone = ftd::p2::utils::string_optional("tone", properties, doc_id, 0)?;
+            let mut node = ftd::Node {
+                node: "span".to_string(),
+                text: Some(label),
+                ..Default::default()
+            };
+            node.classes.push("badge".to_string());
+            if let Some(tone) = tone {
+                node.attrs.insert("data-tone".to_string(), tone);
+            }
+            Ok(node)
+        }
+    }
+
+    #[test]
+    fn native_component() {
+        let lib = ftd::test::Library {
+            plugins: vec![std::sync::Arc::new(Badge("test-badge"))],
+            ..Default::default()
+        };
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- ftd.test-badge: beta
+                tone: info
+                padding: 4
+                "
+            ),
+            &lib,
+        )
+        .expect("found error");
+        let html = doc.html("main", "foo/bar");
+        assert!(html.contains("beta"), "{}", html);
+        assert!(html.contains("data-tone=\"info\""), "{}", html);
+        assert!(html.contains("padding: 4px"), "{}", html);
+
+        assert!(
+            ftd::p2::Document::from("foo/bar", "-- ftd.test-badge: beta\ncolour: red\n", &lib)
+                .is_err()
+        );
+
+        // kernel components can not be replaced
+        assert!(super::Plugins::from_library(
+            vec![std::sync::Arc::new(Badge("text"))],
+            &mut ftd::p2::interpreter::default_bag(),
+            "foo/bar",
+        )
+        .is_err());
+
+        // a document can not be rendered without the plugins it was
+        // interpreted with, eg after a round trip through serde
+        assert!(ftd::RT::from(
+            doc.name.as_str(),
+            doc.aliases.clone(),
+            doc.data.clone(),
+            doc.instructions.clone(),
+        )
+        .render()
+        .is_err());
+
+        // the plugin is only there for the library that provides it
+        assert!(ftd::p2::Document::from(
+            "foo/bar",
+            "-- ftd.test-badge: beta\n",
+            &ftd::p2::TestLibrary {},
+        )
+        .is_err());
+    }
+}
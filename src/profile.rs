@@ -0,0 +1,83 @@
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RenderProfile {
+    /// time taken by `Library::get` for every import, including nested ones
+    pub imports: Vec<Timing>,
+    /// time taken by `Library::process` for every `$processor$` section
+    pub processors: Vec<Timing>,
+    /// time taken to expand every component invocation while executing the
+    /// document, including the ones inside components. the `duration` of a
+    /// component includes the ones nested in it, its `self_duration` does not
+    pub components: Vec<Timing>,
+    pub interpret: std::time::Duration,
+    pub execute: std::time::Duration,
+    pub html: std::time::Duration,
+    pub nodes: usize,
+    pub dependencies: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Timing {
+    pub name: String,
+    pub doc_id: String,
+    pub line_number: usize,
+    pub duration: std::time::Duration,
+    /// `duration` less the time taken by the component expansions nested in
+    /// this one, the same as `duration` for imports and processors
+    #[serde(default)]
+    pub self_duration: std::time::Duration,
+}
+
+impl Timing {
+    pub(crate) fn since(
+        name: &str,
+        doc_id: &str,
+        line_number: usize,
+        start: std::time::Instant,
+    ) -> Timing {
+        let duration = start.elapsed();
+        Timing {
+            name: name.to_string(),
+            doc_id: doc_id.to_string(),
+            line_number,
+            duration,
+            self_duration: duration,
+        }
+    }
+}
+
+impl RenderProfile {
+    pub fn total_get(&self) -> std::time::Duration {
+        self.imports.iter().map(|t| t.duration).sum()
+    }
+
+    pub fn total_processor(&self) -> std::time::Duration {
+        self.processors.iter().map(|t| t.duration).sum()
+    }
+
+    /// time taken by all component expansions, each counted once
+    pub fn total_components(&self) -> std::time::Duration {
+        self.components.iter().map(|t| t.self_duration).sum()
+    }
+
+    /// the `n` component expansions that took the most time outside of the
+    /// components nested in them, slowest first
+    pub fn slowest_components(&self, n: usize) -> Vec<&Timing> {
+        let mut components: Vec<&Timing> = self.components.iter().collect();
+        components.sort_by_key(|t| std::cmp::Reverse(t.self_duration));
+        components.truncate(n);
+        components
+    }
+
+    pub(crate) fn count_nodes(node: &ftd::Node) -> usize {
+        1 + node
+            .children
+            .iter()
+            .chain(node.external_children.iter())
+            .map(Self::count_nodes)
+            .sum::<usize>()
+    }
+
+    pub(crate) fn count_dependencies(data: &ftd::DataDependenciesMap) -> usize {
+        data.values().map(|d| d.dependencies.len()).sum()
+    }
+}
@@ -0,0 +1,341 @@
+//! Selector queries over the element tree, eg `doc.query("column#sidebar text[region=h2]")`.
+//!
+//! A selector is a whitespace separated list of parts, each part matching a
+//! descendant of an element matched by the part before it. A part is an
+//! optional kernel name (`text`, `column`, `image`, ..., or `*`) followed by
+//! any number of `#<id>` and `[<key>]` / `[<key>=<value>]` filters, where key
+//! is one of:
+//!
+//! - `region`: eg `[region=h2]`
+//! - `data-id`: the `id` given in ftd, `#<id>` is short for `[data-id=<id>]`
+//! - `id`: the id of the html element
+//! - `component`: a component the element was created by, eg `[component=heading]`
+//! - `condition`: the variable in the `if` of the element, eg `[condition=is-mobile]`
+//! - `link`
+//!
+//! Names of components and variables can be given with or without the document
+//! name, `[component=heading]` and `[component=foo/bar#heading]` are the same.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Part {
+    kernel: Option<String>,
+    filters: Vec<(String, Option<String>)>,
+}
+
+const KEYS: &[&str] = &["region", "id", "data-id", "component", "condition", "link"];
+
+impl Selector {
+    pub fn parse(s: &str) -> ftd::p1::Result<Selector> {
+        let parts = s
+            .split_whitespace()
+            .map(|p| Part::parse(p, s))
+            .collect::<ftd::p1::Result<Vec<_>>>()?;
+        if parts.is_empty() {
+            return ftd::e2("empty selector", "", 0);
+        }
+        Ok(Selector { parts })
+    }
+
+    /// `ancestors` are the parents of `element`, outermost first
+    pub fn matches(&self, element: &ftd::Element, ancestors: &[&ftd::Element]) -> bool {
+        let (last, rest) = match self.parts.split_last() {
+            Some(v) => v,
+            None => return false,
+        };
+        if !last.matches(element) {
+            return false;
+        }
+
+        // descendant parts only, so matching each part with the closest
+        // ancestor it matches is enough
+        let mut ancestors = ancestors.iter().rev();
+        rest.iter()
+            .rev()
+            .all(|part| ancestors.any(|a| part.matches(a)))
+    }
+}
+
+impl Part {
+    fn parse(s: &str, selector: &str) -> ftd::p1::Result<Part> {
+        let error = || ftd::e2(format!("invalid selector: {}", selector), "", 0);
+        let filter_start = |s: &str| s.find(|c| c == '#' || c == '[').unwrap_or(s.len());
+
+        let mut part: Part = Default::default();
+        let end = filter_start(s);
+        if !s[..end].is_empty() && &s[..end] != "*" {
+            part.kernel = Some(s[..end].to_string());
+        }
+
+        let mut rest = &s[end..];
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix('#') {
+                let end = filter_start(r);
+                if end == 0 {
+                    return error();
+                }
+                part.filters
+                    .push(("data-id".to_string(), Some(r[..end].to_string())));
+                rest = &r[end..];
+            } else if let Some(r) = rest.strip_prefix('[') {
+                let end = match r.find(']') {
+                    Some(end) => end,
+                    None => return error(),
+                };
+                let (key, value) = match r[..end].split_once('=') {
+                    Some((k, v)) => (k.trim(), Some(v.trim().trim_matches('"').to_string())),
+                    None => (r[..end].trim(), None),
+                };
+                if !KEYS.contains(&key) {
+                    return ftd::e2(format!("unknown selector key: {}", key), "", 0);
+                }
+                part.filters.push((key.to_string(), value));
+                rest = &r[end + 1..];
+            } else {
+                return error();
+            }
+        }
+        Ok(part)
+    }
+
+    fn matches(&self, element: &ftd::Element) -> bool {
+        if let Some(ref kernel) = self.kernel {
+            if element.kernel_name() != kernel.as_str() {
+                return false;
+            }
+        }
+        let common = match element.get_common() {
+            Some(common) => common,
+            None => return false,
+        };
+        self.filters.iter().all(|(key, value)| {
+            let found: Vec<String> = match key.as_str() {
+                "region" => common.region.iter().map(|r| r.to_string()).collect(),
+                "id" => common.id.iter().cloned().collect(),
+                // without the index `ftd::Element::set_id` appends on render
+                "data-id" => common
+                    .data_id
+                    .iter()
+                    .map(|d| d.split(':').next().unwrap_or(d).to_string())
+                    .collect(),
+                "component" => common.components.to_vec(),
+                "condition" => common
+                    .condition
+                    .iter()
+                    .map(|c| c.variable.clone())
+                    .collect(),
+                "link" => common.link.iter().cloned().collect(),
+                _ => unreachable!(),
+            };
+            match value {
+                Some(value) => found.iter().any(|f| {
+                    f == value || f.rsplit_once('#').map(|(_, n)| n == value.as_str()) == Some(true)
+                }),
+                None => !found.is_empty(),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<'a> {
+    /// index of the element, and of each of its ancestors, in the children of
+    /// its parent; external children are counted after the children
+    pub path: Vec<usize>,
+    pub element: &'a ftd::Element,
+}
+
+impl<'a> Match<'a> {
+    /// text of `text`, `text-block`, `code` and number elements, as written
+    pub fn text(&self) -> Option<&'a str> {
+        match self.element {
+            ftd::Element::Markup(t) => Some(t.text.original.as_str()),
+            ftd::Element::Text(t)
+            | ftd::Element::Integer(t)
+            | ftd::Element::Boolean(t)
+            | ftd::Element::Decimal(t) => Some(t.text.original.as_str()),
+            ftd::Element::TextBlock(t) => Some(t.text.original.as_str()),
+            ftd::Element::Code(t) => Some(t.text.original.as_str()),
+            _ => None,
+        }
+    }
+}
+
+impl ftd::Element {
+    /// name of the kernel component this element is rendered by, eg `text`
+    pub fn kernel_name(&self) -> &str {
+        match self {
+            ftd::Element::Text(_) | ftd::Element::Markup(_) => "text",
+            ftd::Element::TextBlock(_) => "text-block",
+            ftd::Element::Code(_) => "code",
+            ftd::Element::Image(_) => "image",
+            ftd::Element::Row(_) => "row",
+            ftd::Element::Column(_) => "column",
+            ftd::Element::IFrame(_) => "iframe",
+            ftd::Element::Input(_) => "input",
+            ftd::Element::Checkbox(_) => "checkbox",
+            ftd::Element::Toggle(_) => "toggle",
+            ftd::Element::Radio(_) => "radio",
+            ftd::Element::Select(_) => "select",
+            ftd::Element::Svg(_) => "svg",
+            ftd::Element::Table(_) => "table",
+            ftd::Element::Video(_) => "video",
+            ftd::Element::Audio(_) => "audio",
+            ftd::Element::Integer(_) => "integer",
+            ftd::Element::Boolean(_) => "boolean",
+            ftd::Element::Decimal(_) => "decimal",
+            ftd::Element::Scene(_) => "scene",
+            ftd::Element::Grid(_) => "grid",
+            ftd::Element::Native(n) => n.name.as_str(),
+            ftd::Element::Toc(_) => "toc",
+            ftd::Element::Null => "null",
+        }
+    }
+
+    /// all elements in `children`, and their descendants, matching `selector`,
+    /// in document order
+    pub fn query<'a>(children: &'a [ftd::Element], selector: &Selector) -> Vec<Match<'a>> {
+        fn query_<'a>(
+            elements: &'a [ftd::Element],
+            selector: &Selector,
+            offset: usize,
+            path: &mut Vec<usize>,
+            ancestors: &mut Vec<&'a ftd::Element>,
+            matches: &mut Vec<Match<'a>>,
+        ) {
+            for (i, e) in elements.iter().enumerate() {
+                path.push(offset + i);
+                if selector.matches(e, ancestors) {
+                    matches.push(Match {
+                        path: path.clone(),
+                        element: e,
+                    });
+                }
+                if let Some(container) = e.get_container() {
+                    ancestors.push(e);
+                    query_(&container.children, selector, 0, path, ancestors, matches);
+                    if let Some((_, _, ref external_children)) = container.external_children {
+                        let offset = container.children.len();
+                        query_(
+                            external_children,
+                            selector,
+                            offset,
+                            path,
+                            ancestors,
+                            matches,
+                        );
+                    }
+                    ancestors.pop();
+                }
+                path.pop();
+            }
+        }
+
+        let mut matches = vec![];
+        query_(
+            children,
+            selector,
+            0,
+            &mut vec![],
+            &mut vec![],
+            &mut matches,
+        );
+        matches
+    }
+}
+
+impl ftd::p2::Document {
+    /// see `ftd::Selector`
+    pub fn query(&self, selector: &str) -> ftd::p1::Result<Vec<Match<'_>>> {
+        let selector = Selector::parse(selector)?;
+        Ok(ftd::Element::query(
+            &self.main.container.children,
+            &selector,
+        ))
+    }
+
+    /// the first element matching `selector`
+    pub fn query_one(&self, selector: &str) -> ftd::p1::Result<Option<Match<'_>>> {
+        Ok(self.query(selector)?.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn query() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- ftd.image-src logo:
+                light: /logo.png
+                dark: /logo-dark.png
+
+                -- ftd.text heading:
+                caption title:
+                text: $title
+                region: h2
+
+                -- ftd.column page:
+
+                --- ftd.text: Intro
+                region: h1
+
+                --- heading: First
+
+                --- heading: Second
+
+                --- ftd.image:
+                src: $logo
+                link: /home/
+
+                -- page:
+                id: page
+
+                -- ftd.text: Footer
+                id: footer
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        let headings = doc.query("[region=h2]").unwrap();
+        pretty_assertions::assert_eq!(
+            headings.iter().map(|m| m.text()).collect::<Vec<_>>(),
+            vec![Some("First"), Some("Second")]
+        );
+        pretty_assertions::assert_eq!(headings[1].path, vec![0, 2]);
+
+        pretty_assertions::assert_eq!(
+            doc.query("text[component=foo/bar#heading]").unwrap(),
+            headings
+        );
+        pretty_assertions::assert_eq!(
+            doc.query("column#page [component=heading]").unwrap(),
+            headings
+        );
+        pretty_assertions::assert_eq!(doc.query("text").unwrap().len(), 4);
+        pretty_assertions::assert_eq!(doc.query("column text#footer").unwrap(), vec![]);
+
+        let footer = doc.query_one("#footer").unwrap().unwrap();
+        pretty_assertions::assert_eq!(
+            (footer.path.clone(), footer.text()),
+            (vec![1], Some("Footer"))
+        );
+
+        let links = doc.query("[link]").unwrap();
+        pretty_assertions::assert_eq!(links.len(), 1);
+        pretty_assertions::assert_eq!(links[0].element.kernel_name(), "image");
+        pretty_assertions::assert_eq!(links[0].path, vec![0, 3]);
+
+        assert!(doc.query("text[region=h2").is_err());
+        assert!(doc.query("text[colour=red]").is_err());
+        assert!(doc.query("  ").is_err());
+    }
+}
@@ -4,6 +4,12 @@ pub struct RT {
     pub aliases: std::collections::BTreeMap<String, String>,
     pub bag: std::collections::BTreeMap<String, ftd::p2::Thing>,
     pub instructions: Vec<ftd::Instruction>,
+    #[serde(skip)]
+    pub(crate) plugins: ftd::plugin::Plugins,
+    #[serde(skip)]
+    pub(crate) embed_providers: ftd::embed::EmbedProviders,
+    #[serde(skip)]
+    pub(crate) icon_sets: ftd::icon::IconSets,
 }
 
 impl RT {
@@ -18,6 +24,9 @@ impl RT {
             aliases,
             bag,
             instructions,
+            plugins: Default::default(),
+            embed_providers: Default::default(),
+            icon_sets: Default::default(),
         }
     }
 
@@ -50,7 +59,23 @@ impl RT {
     // }
 
     pub fn render(&mut self) -> ftd::p1::Result<ftd::Column> {
-        let mut main = self.render_();
+        self.render_with_profile(None)
+    }
+
+    pub fn render_with_profile(
+        &mut self,
+        profile: Option<&mut ftd::RenderProfile>,
+    ) -> ftd::p1::Result<ftd::Column> {
+        self.render_with(profile, Default::default())
+    }
+
+    /// `budget` is what is left of the limits after interpreting the document
+    pub(crate) fn render_with(
+        &mut self,
+        profile: Option<&mut ftd::RenderProfile>,
+        budget: ftd::limits::Budget,
+    ) -> ftd::p1::Result<ftd::Column> {
+        let mut main = self.execute(profile, budget);
         if let Ok(main) = &mut main {
             ftd::Element::set_id(&mut main.container.children, &[], None);
         }
@@ -58,9 +83,27 @@ impl RT {
     }
 
     pub fn render_(&mut self) -> ftd::p1::Result<ftd::Column> {
+        self.execute(None, Default::default())
+    }
+
+    fn execute(
+        &mut self,
+        profile: Option<&mut ftd::RenderProfile>,
+        budget: ftd::limits::Budget,
+    ) -> ftd::p1::Result<ftd::Column> {
+        let start = std::time::Instant::now();
         let mut main = ftd::p2::interpreter::default_column();
         let mut invocations = Default::default();
         let mut local_variables = Default::default();
+        let mut state = ftd::execute_doc::State {
+            components: profile.as_ref().map(|_| vec![]),
+            budget,
+            plugins: self.plugins.clone(),
+            embed_providers: self.embed_providers.clone(),
+            icon_sets: self.icon_sets.clone(),
+            doc_ids: vec![],
+            nested: vec![],
+        };
         let mut element = ftd::execute_doc::ExecuteDoc {
             name: self.name.as_str(),
             aliases: &self.aliases,
@@ -68,30 +111,63 @@ impl RT {
             local_variables: &mut local_variables,
             instructions: &self.instructions,
             invocations: &mut invocations,
+            state: &mut state,
         }
-        .execute(&[], None)?
+        .execute(&[], None, 0)?
         .children;
 
+        ftd::Element::check_forms(&element, self.name.as_str())?;
+
         ftd::Element::set_default_locals(&mut element);
+        ftd::Element::set_disclosure_panels(&mut element);
         ftd::Element::renest_on_region(&mut element);
         ftd::p2::document::set_region_id(&mut element);
+        ftd::outline::set_toc(&mut element);
         ftd::p2::document::default_scene_children_position(&mut element);
 
         main.container.children.extend(element);
         store_invocations(&mut self.bag, &mut local_variables, invocations);
         self.bag.extend(local_variables);
+        set_input_validity(&mut self.bag, &main.container.children);
+        if let Some(profile) = profile {
+            profile
+                .components
+                .extend(state.components.unwrap_or_default());
+            profile.execute = start.elapsed();
+        }
         Ok(main)
     }
 }
 
+/// sets the `valid` variable of every input in `elements` to whether its
+/// initial value is valid, elements already rendered with the variable keep
+/// its old value
+fn set_input_validity(
+    bag: &mut std::collections::BTreeMap<String, ftd::p2::Thing>,
+    elements: &[ftd::Element],
+) {
+    let mut validity = vec![];
+    ftd::Element::input_validity(elements, &mut validity);
+    for (name, valid) in validity {
+        if let Some(ftd::p2::Thing::Variable(v)) = bag.get_mut(name.as_str()) {
+            v.value = ftd::PropertyValue::Value {
+                value: ftd::Value::Boolean { value: valid },
+            };
+        }
+    }
+}
+
 pub(crate) fn store_invocations(
     bag: &mut std::collections::BTreeMap<String, ftd::p2::Thing>,
     local_variables: &mut std::collections::BTreeMap<String, ftd::p2::Thing>,
-    invocations: std::collections::BTreeMap<
-        String,
-        Vec<std::collections::BTreeMap<String, ftd::Value>>,
-    >,
+    invocations: std::collections::BTreeMap<String, Vec<ftd::Invocation>>,
 ) {
+    // a document rendered again has the invocations of the last render only
+    for thing in bag.values_mut() {
+        if let ftd::p2::Thing::Component(c) = thing {
+            c.invocations.clear();
+        }
+    }
     for (k, v) in invocations.into_iter() {
         if let Some(c) = bag.get_mut(k.as_str()) {
             match c {
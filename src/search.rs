@@ -0,0 +1,301 @@
+/// Visible text of a document under one heading, for search indexes.
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub struct SearchRecord {
+    /// `None` for the text before the first heading
+    pub heading: Option<String>,
+    /// id of the heading, to link to the record as `#<anchor>`
+    pub anchor: Option<String>,
+    /// paragraphs, one per line
+    pub text: String,
+}
+
+impl ftd::p2::Document {
+    /// visible text of the document, headings included, with paragraphs
+    /// separated by an empty line
+    pub fn to_plain_text(&self) -> String {
+        let mut blocks = vec![];
+        blocks_(&self.main.container.children, &mut blocks);
+        blocks
+            .into_iter()
+            .map(|b| match b {
+                Block::Heading { text, .. } => text,
+                Block::Text(text) => text,
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// visible text of the document grouped under the closest heading before
+    /// it. Elements hidden by their `if` when the document was rendered are
+    /// left out.
+    pub fn search_records(&self) -> Vec<SearchRecord> {
+        let mut blocks = vec![];
+        blocks_(&self.main.container.children, &mut blocks);
+
+        let mut records: Vec<SearchRecord> = vec![];
+        for block in blocks {
+            match block {
+                Block::Heading { text, anchor } => records.push(SearchRecord {
+                    heading: Some(text),
+                    anchor: Some(anchor),
+                    text: "".to_string(),
+                }),
+                Block::Text(text) => match records.last_mut() {
+                    // the title of a heading row or column is its first text
+                    Some(r) if r.text.is_empty() && r.heading.as_ref() == Some(&text) => {}
+                    Some(r) if r.text.is_empty() => r.text = text,
+                    Some(r) => {
+                        r.text.push('\n');
+                        r.text.push_str(text.as_str());
+                    }
+                    None => records.push(SearchRecord {
+                        heading: None,
+                        anchor: None,
+                        text,
+                    }),
+                },
+            }
+        }
+        records
+    }
+}
+
+enum Block {
+    Heading { text: String, anchor: String },
+    Text(String),
+}
+
+fn blocks_(elements: &[ftd::Element], blocks: &mut Vec<Block>) {
+    for e in elements.iter() {
+        if !ftd::outline::is_shown(e) {
+            continue;
+        }
+
+        let text = match ftd::outline::heading(e) {
+            Some(heading) => {
+                blocks.push(Block::Heading {
+                    text: plain(heading.text.rendered.as_str()),
+                    anchor: heading.slug,
+                });
+                None
+            }
+            None => match e {
+                ftd::Element::Text(t)
+                | ftd::Element::Integer(t)
+                | ftd::Element::Boolean(t)
+                | ftd::Element::Decimal(t) => Some(strip_html(t.text.rendered.as_str())),
+                ftd::Element::TextBlock(t) => Some(strip_html(t.text.rendered.as_str())),
+                ftd::Element::Code(t) => Some(strip_html(t.text.rendered.as_str())),
+                ftd::Element::Markup(t) => Some(markups_text(t)),
+                _ => None,
+            },
+        };
+        if let Some(text) = text.map(|t| normalize(t.as_str())) {
+            if !text.is_empty() {
+                blocks.push(Block::Text(text));
+            }
+        }
+
+        if let Some(container) = e.get_container() {
+            blocks_(&container.children, blocks);
+            if let Some((_, _, ref external_children)) = container.external_children {
+                blocks_(external_children, blocks);
+            }
+        }
+    }
+}
+
+// same as `to_node()`, children replace the text if there are any
+fn markups_text(m: &ftd::Markups) -> String {
+    if m.children.is_empty() {
+        strip_html(m.text.rendered.as_str())
+    } else {
+        m.children.iter().map(markup_text).collect()
+    }
+}
+
+fn markup_text(m: &ftd::Markup) -> String {
+    if !m.children.is_empty() {
+        return m.children.iter().map(markup_text).collect();
+    }
+    match m.itext {
+        ftd::IText::Text(ref t)
+        | ftd::IText::Integer(ref t)
+        | ftd::IText::Boolean(ref t)
+        | ftd::IText::Decimal(ref t) => strip_html(t.text.rendered.as_str()),
+        ftd::IText::TextBlock(ref t) => strip_html(t.text.rendered.as_str()),
+        ftd::IText::Markup(ref t) => markups_text(t),
+    }
+}
+
+fn plain(html: &str) -> String {
+    normalize(strip_html(html).as_str())
+}
+
+// removes tags, ending lines at the end of block elements, and decodes
+// entities
+fn strip_html(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = rest[start + 1..end]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if matches!(
+            tag.as_str(),
+            "p" | "br" | "div" | "li" | "pre" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        ) {
+            text.push('\n');
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    decode_entities(text.as_str())
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut text = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find(';') {
+            Some(end) if end <= 10 => end,
+            _ => {
+                text.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        match entity(&rest[1..end]) {
+            Some(c) => {
+                text.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+            Some(hex) => u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(std::char::from_u32),
+            None => name
+                .strip_prefix('#')
+                .and_then(|d| d.parse().ok())
+                .and_then(std::char::from_u32),
+        },
+    }
+}
+
+// collapses the whitespace in each line, and drops empty lines
+fn normalize(s: &str) -> String {
+    s.lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn search_records() {
+        let doc = ftd::p2::Document::from(
+            "foo/bar",
+            indoc::indoc!(
+                "
+                -- boolean show: false
+
+                -- ftd.text: Intro with **bold** & more
+
+                -- ftd.text: Install
+                region: h2
+
+                -- ftd.text: Run `cargo install ftd`.
+
+                -- ftd.text: Hidden
+                if: $show
+
+                -- ftd.text: Usage
+                region: h2
+
+                -- ftd.text:
+
+                Read the docs.
+
+                Then write some.
+                "
+            ),
+            &ftd::p2::TestLibrary {},
+        )
+        .expect("found error");
+
+        pretty_assertions::assert_eq!(
+            doc.search_records(),
+            vec![
+                super::SearchRecord {
+                    heading: None,
+                    anchor: None,
+                    text: "Intro with bold & more".to_string(),
+                },
+                super::SearchRecord {
+                    heading: Some("Install".to_string()),
+                    anchor: Some("install".to_string()),
+                    text: "Run cargo install ftd.".to_string(),
+                },
+                super::SearchRecord {
+                    heading: Some("Usage".to_string()),
+                    anchor: Some("usage".to_string()),
+                    text: "Read the docs.\nThen write some.".to_string(),
+                },
+            ]
+        );
+        pretty_assertions::assert_eq!(
+            doc.to_plain_text(),
+            indoc::indoc!(
+                "
+                Intro with bold & more
+
+                Install
+
+                Run cargo install ftd.
+
+                Usage
+
+                Read the docs.
+                Then write some."
+            )
+        );
+    }
+
+    #[test]
+    fn strip_html() {
+        pretty_assertions::assert_eq!(
+            super::plain("<p>a &amp;&#x20;<em>b</em>&#33;</p><p>c &copy; d</p>"),
+            "a & b!\nc &copy; d"
+        );
+    }
+}
@@ -0,0 +1,503 @@
+/// Writes `value` as ftd sections. `value` has to be a struct, which becomes
+/// one `-- <name>:` section named after the struct in kebab case, or a list of
+/// structs, which becomes one section per item.
+///
+/// Fields holding scalars become headers, lists of scalars become repeated
+/// headers, and structs or lists of structs become `--- <field>:` sub-sections
+/// (which can not have sub-sections of their own). A struct variant of an
+/// enum becomes a `--- <field>.<variant>:` sub-section, same as or-type
+/// values, and a unit variant is written as its name. `None` is skipped.
+///
+/// A field renamed to `$caption` or `$body`, with `#[serde(rename = "$body")]`,
+/// is written as the caption or the body of its section.
+pub fn to_string<T: serde::Serialize + ?Sized>(value: &T) -> ftd::p1::Result<String> {
+    let sections = match value.serialize(Serializer)? {
+        Value::List(items) => items
+            .into_iter()
+            .map(to_section)
+            .collect::<ftd::p1::Result<Vec<_>>>()?,
+        v => vec![to_section(v)?],
+    };
+    Ok(format!("{}\n", ftd::p1::to_string(&sections)))
+}
+
+pub(crate) const CAPTION: &str = "$caption";
+pub(crate) const BODY: &str = "$body";
+
+// the serialized value, before it is laid out as sections
+enum Value {
+    Null,
+    Scalar(String),
+    List(Vec<Value>),
+    Record {
+        name: String,
+        variant: Option<String>,
+        fields: Vec<(String, Value)>,
+    },
+}
+
+fn to_section(value: Value) -> ftd::p1::Result<ftd::p1::Section> {
+    let (name, fields) = match value {
+        Value::Record {
+            name,
+            variant,
+            fields,
+        } if !name.is_empty() => (full_name(name, variant), fields),
+        _ => {
+            return ftd::e2(
+                "only structs, or lists of structs, can be written as ftd",
+                "",
+                0,
+            )
+        }
+    };
+    let parts = Parts::from_fields(name.as_str(), fields, true)?;
+    Ok(ftd::p1::Section {
+        name,
+        caption: parts.caption,
+        header: parts.header,
+        body: parts.body,
+        sub_sections: ftd::p1::SubSections(parts.sub_sections),
+        is_commented: false,
+        line_number: 0,
+    })
+}
+
+fn to_sub_section(key: &str, value: Value) -> ftd::p1::Result<ftd::p1::SubSection> {
+    let (name, fields) = match value {
+        Value::Record {
+            variant, fields, ..
+        } => (full_name(key.to_string(), variant), fields),
+        _ => unreachable!(),
+    };
+    let parts = Parts::from_fields(name.as_str(), fields, false)?;
+    Ok(ftd::p1::SubSection {
+        name,
+        caption: parts.caption,
+        header: parts.header,
+        body: parts.body,
+        is_commented: false,
+        line_number: 0,
+    })
+}
+
+fn full_name(name: String, variant: Option<String>) -> String {
+    match variant {
+        Some(variant) => format!("{}.{}", name, variant),
+        None => name,
+    }
+}
+
+#[derive(Default)]
+struct Parts {
+    caption: Option<String>,
+    header: ftd::p1::Header,
+    body: Option<(usize, String)>,
+    sub_sections: Vec<ftd::p1::SubSection>,
+}
+
+impl Parts {
+    fn from_fields(
+        name: &str,
+        fields: Vec<(String, Value)>,
+        can_have_sub_sections: bool,
+    ) -> ftd::p1::Result<Parts> {
+        let mut parts: Parts = Default::default();
+        for (key, value) in fields {
+            match (key.as_str(), value) {
+                (_, Value::Null) => {}
+                (CAPTION, Value::Scalar(v)) => parts.caption = Some(single_line(name, &key, v)?),
+                (BODY, Value::Scalar(v)) => parts.body = Some((0, v)),
+                (CAPTION, _) | (BODY, _) => {
+                    return ftd::e2(format!("{}.{} must be a scalar", name, key), "", 0)
+                }
+                (_, Value::Scalar(v)) => parts.header.add(&0, &key, &single_line(name, &key, v)?),
+                (_, v @ Value::Record { .. }) => {
+                    parts.add_sub_section(name, &key, v, can_have_sub_sections)?
+                }
+                (_, Value::List(items)) => {
+                    for item in items {
+                        match item {
+                            Value::Null => {}
+                            Value::Scalar(v) => {
+                                parts.header.add(&0, &key, &single_line(name, &key, v)?)
+                            }
+                            v @ Value::Record { .. } => {
+                                parts.add_sub_section(name, &key, v, can_have_sub_sections)?
+                            }
+                            Value::List(_) => {
+                                return ftd::e2(
+                                    format!("{}.{}: lists of lists are not supported", name, key),
+                                    "",
+                                    0,
+                                )
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(parts)
+    }
+
+    fn add_sub_section(
+        &mut self,
+        name: &str,
+        key: &str,
+        value: Value,
+        can_have_sub_sections: bool,
+    ) -> ftd::p1::Result<()> {
+        if !can_have_sub_sections {
+            return ftd::e2(
+                format!("{}.{}: sub-sections can not have sub-sections", name, key),
+                "",
+                0,
+            );
+        }
+        self.sub_sections.push(to_sub_section(key, value)?);
+        Ok(())
+    }
+}
+
+fn single_line(name: &str, key: &str, value: String) -> ftd::p1::Result<String> {
+    if value.contains('\n') {
+        return ftd::e2(
+            format!(
+                "{}.{} has more than one line, only the body can, rename it to \"$body\"",
+                name, key
+            ),
+            "",
+            0,
+        );
+    }
+    Ok(value)
+}
+
+/// `TocItem` -> `toc-item`, the names `ftd::FtdRecord` and `ftd::to_string()`
+/// give rust types in ftd
+pub fn kebab_case(name: &str) -> String {
+    let mut s = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if previous.map(|p| p.is_lowercase() || p.is_numeric()) == Some(true) {
+                s.push('-');
+            }
+            s.extend(c.to_lowercase());
+        } else {
+            s.push(c);
+        }
+        previous = Some(c);
+    }
+    s
+}
+
+struct Serializer;
+
+impl serde::Serializer for Serializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = serde::ser::Impossible<Value, ftd::p1::Error>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = RecordSerializer;
+    type SerializeStructVariant = RecordSerializer;
+
+    fn serialize_bool(self, v: bool) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_i8(self, v: i8) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_i16(self, v: i16) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_i32(self, v: i32) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_i64(self, v: i64) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_u8(self, v: u8) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_u16(self, v: u16) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_u32(self, v: u32) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_u64(self, v: u64) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_f32(self, v: f32) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_f64(self, v: f64) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_char(self, v: char) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(v.to_string()))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> ftd::p1::Result<Value> {
+        ftd::e2("bytes can not be written as ftd", "", 0)
+    }
+
+    fn serialize_none(self) -> ftd::p1::Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: serde::Serialize + ?Sized>(self, value: &T) -> ftd::p1::Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> ftd::p1::Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> ftd::p1::Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> ftd::p1::Result<Value> {
+        Ok(Value::Scalar(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> ftd::p1::Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> ftd::p1::Result<Value> {
+        unsupported_variant(name, variant)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> ftd::p1::Result<SeqSerializer> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> ftd::p1::Result<SeqSerializer> {
+        serde::Serializer::serialize_seq(self, Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> ftd::p1::Result<SeqSerializer> {
+        serde::Serializer::serialize_seq(self, Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> ftd::p1::Result<Self::SerializeTupleVariant> {
+        unsupported_variant(name, variant)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> ftd::p1::Result<MapSerializer> {
+        Ok(MapSerializer {
+            fields: vec![],
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> ftd::p1::Result<RecordSerializer> {
+        Ok(RecordSerializer {
+            name: kebab_case(name),
+            variant: None,
+            fields: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> ftd::p1::Result<RecordSerializer> {
+        Ok(RecordSerializer {
+            name: kebab_case(name),
+            variant: Some(variant.to_string()),
+            fields: Vec::with_capacity(len),
+        })
+    }
+}
+
+fn unsupported_variant<T>(name: &str, variant: &str) -> ftd::p1::Result<T> {
+    ftd::e2(
+        format!(
+            "{}::{} can not be written as ftd, only unit and struct variants are supported",
+            name, variant
+        ),
+        "",
+        0,
+    )
+}
+
+struct SeqSerializer(Vec<Value>);
+
+impl serde::ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> ftd::p1::Result<()> {
+        self.0.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> ftd::p1::Result<Value> {
+        Ok(Value::List(self.0))
+    }
+}
+
+impl serde::ser::SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> ftd::p1::Result<()> {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> ftd::p1::Result<Value> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+impl serde::ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> ftd::p1::Result<()> {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> ftd::p1::Result<Value> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    fields: Vec<(String, Value)>,
+    key: Option<String>,
+}
+
+impl serde::ser::SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+
+    fn serialize_key<T: serde::Serialize + ?Sized>(&mut self, key: &T) -> ftd::p1::Result<()> {
+        match key.serialize(Serializer)? {
+            Value::Scalar(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => ftd::e2("map keys must be strings", "", 0),
+        }
+    }
+
+    fn serialize_value<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> ftd::p1::Result<()> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.fields.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> ftd::p1::Result<Value> {
+        Ok(Value::Record {
+            name: "".to_string(),
+            variant: None,
+            fields: self.fields,
+        })
+    }
+}
+
+struct RecordSerializer {
+    name: String,
+    variant: Option<String>,
+    fields: Vec<(String, Value)>,
+}
+
+impl serde::ser::SerializeStruct for RecordSerializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> ftd::p1::Result<()> {
+        self.fields
+            .push((key.to_string(), value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> ftd::p1::Result<Value> {
+        Ok(Value::Record {
+            name: self.name,
+            variant: self.variant,
+            fields: self.fields,
+        })
+    }
+}
+
+impl serde::ser::SerializeStructVariant for RecordSerializer {
+    type Ok = Value;
+    type Error = ftd::p1::Error;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> ftd::p1::Result<()> {
+        serde::ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> ftd::p1::Result<Value> {
+        serde::ser::SerializeStruct::end(self)
+    }
+}
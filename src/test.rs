@@ -32,6 +32,110 @@ pub fn s(s: &str) -> String {
     s.to_string()
 }
 
+/// every one of `sources`, after `prefix`, is not a valid document
+pub fn assert_invalid(prefix: &str, sources: &[&str]) {
+    for source in sources {
+        let source = format!("{}{}", prefix, source);
+        assert!(
+            ftd::p2::Document::from("foo/bar", source.as_str(), &ftd::p2::TestLibrary {}).is_err(),
+            "{}",
+            source
+        );
+    }
+}
+
+/// `ftd::p2::TestLibrary` with `overrides` served before the files in
+/// `./tests`, and with the plugins, embed providers and icon sets set on it
+#[derive(Default)]
+pub struct Library {
+    pub overrides: std::sync::Mutex<std::collections::BTreeMap<String, String>>,
+    pub plugins: Vec<std::sync::Arc<dyn ftd::Plugin>>,
+    pub embed_providers: Vec<std::sync::Arc<dyn ftd::EmbedProvider>>,
+    pub icon_sets: Vec<std::sync::Arc<dyn ftd::IconSet>>,
+}
+
+impl Library {
+    pub fn set(&self, name: &str, source: &str) {
+        self.overrides
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), source.to_string());
+    }
+
+    fn overridden(&self, name: &str) -> Option<String> {
+        self.overrides.lock().unwrap().get(name).cloned()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl ftd::p2::Library for Library {
+    async fn get(&self, name: &str, doc: &ftd::p2::TDoc) -> Option<String> {
+        match self.overridden(name) {
+            Some(source) => Some(source),
+            None => ftd::p2::Library::get(&ftd::p2::TestLibrary {}, name, doc).await,
+        }
+    }
+
+    async fn versions(&self, name: &str, doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        ftd::p2::Library::versions(&ftd::p2::TestLibrary {}, name, doc).await
+    }
+
+    fn plugins(&self) -> Vec<std::sync::Arc<dyn ftd::Plugin>> {
+        self.plugins.clone()
+    }
+
+    fn embed_providers(&self) -> Vec<std::sync::Arc<dyn ftd::EmbedProvider>> {
+        self.embed_providers.clone()
+    }
+
+    fn icon_sets(&self) -> Vec<std::sync::Arc<dyn ftd::IconSet>> {
+        self.icon_sets.clone()
+    }
+
+    async fn process(
+        &self,
+        section: &ftd::p1::Section,
+        doc: &ftd::p2::TDoc,
+    ) -> ftd::p1::Result<ftd::Value> {
+        ftd::p2::Library::process(&ftd::p2::TestLibrary {}, section, doc).await
+    }
+}
+
+#[cfg(not(feature = "async"))]
+impl ftd::p2::Library for Library {
+    fn get(&self, name: &str, doc: &ftd::p2::TDoc) -> Option<String> {
+        match self.overridden(name) {
+            Some(source) => Some(source),
+            None => ftd::p2::Library::get(&ftd::p2::TestLibrary {}, name, doc),
+        }
+    }
+
+    fn versions(&self, name: &str, doc: &ftd::p2::TDoc) -> Vec<ftd::p2::Version> {
+        ftd::p2::Library::versions(&ftd::p2::TestLibrary {}, name, doc)
+    }
+
+    fn plugins(&self) -> Vec<std::sync::Arc<dyn ftd::Plugin>> {
+        self.plugins.clone()
+    }
+
+    fn embed_providers(&self) -> Vec<std::sync::Arc<dyn ftd::EmbedProvider>> {
+        self.embed_providers.clone()
+    }
+
+    fn icon_sets(&self) -> Vec<std::sync::Arc<dyn ftd::IconSet>> {
+        self.icon_sets.clone()
+    }
+
+    fn process(
+        &self,
+        section: &ftd::p1::Section,
+        doc: &ftd::p2::TDoc,
+    ) -> ftd::p1::Result<ftd::Value> {
+        ftd::p2::Library::process(&ftd::p2::TestLibrary {}, section, doc)
+    }
+}
+
 pub fn i(p: &str) -> ftd::ImageSrc {
     ftd::ImageSrc {
         light: s(p),
@@ -9,12 +9,22 @@ pub enum Element {
     Column(Column),
     IFrame(IFrame),
     Input(Input),
+    Checkbox(Checkbox),
+    Toggle(Checkbox),
+    Radio(Select),
+    Select(Select),
     Integer(Text),
     Boolean(Text),
     Decimal(Text),
     Scene(Scene),
     Grid(Grid),
     Markup(Markups),
+    Native(Native),
+    Toc(Toc),
+    Video(Media),
+    Audio(Media),
+    Svg(Svg),
+    Table(Table),
     Null,
 }
 
@@ -72,6 +82,16 @@ impl Element {
                     | Element::Image(ftd::Image { common, .. })
                     | Element::IFrame(ftd::IFrame { common, .. })
                     | Element::Input(ftd::Input { common, .. })
+                    | Element::Table(ftd::Table { common, .. })
+                    | Element::Svg(ftd::Svg { common, .. })
+                    | Element::Audio(ftd::Media { common, .. })
+                    | Element::Video(ftd::Media { common, .. })
+                    | Element::Checkbox(ftd::Checkbox { common, .. })
+                    | Element::Toggle(ftd::Checkbox { common, .. })
+                    | Element::Radio(ftd::Select { common, .. })
+                    | Element::Select(ftd::Select { common, .. })
+                    | Element::Toc(ftd::Toc { common, .. })
+                    | Element::Native(ftd::Native { common, .. })
                     | Element::Integer(ftd::Text { common, .. })
                     | Element::Boolean(ftd::Text { common, .. })
                     | Element::Decimal(ftd::Text { common, .. })
@@ -182,6 +202,96 @@ impl Element {
                         },
                     ..
                 })
+                | Self::Table(ftd::Table {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Svg(ftd::Svg {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Audio(ftd::Media {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Video(ftd::Media {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Checkbox(ftd::Checkbox {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Toggle(ftd::Checkbox {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Radio(ftd::Select {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Select(ftd::Select {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Toc(ftd::Toc {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
+                | Self::Native(ftd::Native {
+                    common:
+                        ftd::Common {
+                            data_id: id,
+                            is_dummy,
+                            ..
+                        },
+                    ..
+                })
                 | Self::Integer(ftd::Text {
                     common:
                         ftd::Common {
@@ -603,6 +713,16 @@ impl Element {
                 | ftd::Element::Code(ftd::Code { common, .. })
                 | ftd::Element::IFrame(ftd::IFrame { common, .. })
                 | ftd::Element::Input(ftd::Input { common, .. })
+                | ftd::Element::Table(ftd::Table { common, .. })
+                | ftd::Element::Svg(ftd::Svg { common, .. })
+                | ftd::Element::Audio(ftd::Media { common, .. })
+                | ftd::Element::Video(ftd::Media { common, .. })
+                | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+                | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+                | ftd::Element::Radio(ftd::Select { common, .. })
+                | ftd::Element::Select(ftd::Select { common, .. })
+                | ftd::Element::Toc(ftd::Toc { common, .. })
+                | ftd::Element::Native(ftd::Native { common, .. })
                 | ftd::Element::Integer(ftd::Text { common, .. })
                 | ftd::Element::Boolean(ftd::Text { common, .. })
                 | ftd::Element::Decimal(ftd::Text { common, .. }) => {
@@ -882,6 +1002,16 @@ impl Element {
                 | ftd::Element::Code(ftd::Code { common, .. })
                 | ftd::Element::IFrame(ftd::IFrame { common, .. })
                 | ftd::Element::Input(ftd::Input { common, .. })
+                | ftd::Element::Table(ftd::Table { common, .. })
+                | ftd::Element::Svg(ftd::Svg { common, .. })
+                | ftd::Element::Audio(ftd::Media { common, .. })
+                | ftd::Element::Video(ftd::Media { common, .. })
+                | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+                | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+                | ftd::Element::Radio(ftd::Select { common, .. })
+                | ftd::Element::Select(ftd::Select { common, .. })
+                | ftd::Element::Toc(ftd::Toc { common, .. })
+                | ftd::Element::Native(ftd::Native { common, .. })
                 | ftd::Element::Integer(ftd::Text { common, .. })
                 | ftd::Element::Boolean(ftd::Text { common, .. })
                 | ftd::Element::Decimal(ftd::Text { common, .. }) => (common, &common.data_id),
@@ -1069,11 +1199,28 @@ impl Element {
                 | ftd::Element::Code(ftd::Code { common, .. })
                 | ftd::Element::IFrame(ftd::IFrame { common, .. })
                 | ftd::Element::Input(ftd::Input { common, .. })
+                | ftd::Element::Table(ftd::Table { common, .. })
+                | ftd::Element::Svg(ftd::Svg { common, .. })
+                | ftd::Element::Audio(ftd::Media { common, .. })
+                | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+                | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+                | ftd::Element::Radio(ftd::Select { common, .. })
+                | ftd::Element::Select(ftd::Select { common, .. })
+                | ftd::Element::Toc(ftd::Toc { common, .. })
+                | ftd::Element::Native(ftd::Native { common, .. })
                 | ftd::Element::Integer(ftd::Text { common, .. })
                 | ftd::Element::Boolean(ftd::Text { common, .. })
                 | ftd::Element::Decimal(ftd::Text { common, .. }) => {
                     (&common.reference, &common.data_id)
                 }
+                ftd::Element::Video(ftd::Media {
+                    common,
+                    poster_reference,
+                    ..
+                }) => {
+                    value_condition_(poster_reference, &common.data_id, Some("poster"), data);
+                    (&common.reference, &common.data_id)
+                }
                 ftd::Element::Null => continue,
             };
             value_condition(reference, id, data);
@@ -1104,6 +1251,17 @@ impl Element {
             reference: &Option<String>,
             id: &Option<String>,
             data: &mut ftd::DataDependenciesMap,
+        ) {
+            value_condition_(reference, id, None, data)
+        }
+
+        // `attribute` is set to the value instead of the element's own value,
+        // eg `poster` of a video
+        fn value_condition_(
+            reference: &Option<String>,
+            id: &Option<String>,
+            attribute: Option<&str>,
+            data: &mut ftd::DataDependenciesMap,
         ) {
             if let Some(reference) = reference {
                 let id = id.clone().expect("universal id should be present");
@@ -1114,7 +1272,10 @@ impl Element {
                     let json = ftd::Dependencies {
                         dependency_type: ftd::DependencyType::Value,
                         condition: None,
-                        parameters: Default::default(),
+                        parameters: attribute
+                            .map(|a| (a.to_string(), Default::default()))
+                            .into_iter()
+                            .collect(),
                         remaining,
                     };
                     if let Some(dependencies) = dependencies.get_mut(&id) {
@@ -1445,6 +1606,16 @@ impl Element {
                 | ftd::Element::Code(ftd::Code { common, .. })
                 | ftd::Element::IFrame(ftd::IFrame { common, .. })
                 | ftd::Element::Input(ftd::Input { common, .. })
+                | ftd::Element::Table(ftd::Table { common, .. })
+                | ftd::Element::Svg(ftd::Svg { common, .. })
+                | ftd::Element::Audio(ftd::Media { common, .. })
+                | ftd::Element::Video(ftd::Media { common, .. })
+                | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+                | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+                | ftd::Element::Radio(ftd::Select { common, .. })
+                | ftd::Element::Select(ftd::Select { common, .. })
+                | ftd::Element::Toc(ftd::Toc { common, .. })
+                | ftd::Element::Native(ftd::Native { common, .. })
                 | ftd::Element::Integer(ftd::Text { common, .. })
                 | ftd::Element::Boolean(ftd::Text { common, .. })
                 | ftd::Element::Decimal(ftd::Text { common, .. }) => {
@@ -1568,6 +1739,16 @@ impl Element {
             | ftd::Element::IFrame(ftd::IFrame { common, .. })
             | ftd::Element::Markup(ftd::Markups { common, .. })
             | ftd::Element::Input(ftd::Input { common, .. })
+            | ftd::Element::Table(ftd::Table { common, .. })
+            | ftd::Element::Svg(ftd::Svg { common, .. })
+            | ftd::Element::Audio(ftd::Media { common, .. })
+            | ftd::Element::Video(ftd::Media { common, .. })
+            | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+            | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+            | ftd::Element::Radio(ftd::Select { common, .. })
+            | ftd::Element::Select(ftd::Select { common, .. })
+            | ftd::Element::Toc(ftd::Toc { common, .. })
+            | ftd::Element::Native(ftd::Native { common, .. })
             | ftd::Element::Integer(ftd::Text { common, .. })
             | ftd::Element::Boolean(ftd::Text { common, .. })
             | ftd::Element::Decimal(ftd::Text { common, .. })
@@ -1588,6 +1769,16 @@ impl Element {
             | ftd::Element::IFrame(ftd::IFrame { common, .. })
             | ftd::Element::Markup(ftd::Markups { common, .. })
             | ftd::Element::Input(ftd::Input { common, .. })
+            | ftd::Element::Table(ftd::Table { common, .. })
+            | ftd::Element::Svg(ftd::Svg { common, .. })
+            | ftd::Element::Audio(ftd::Media { common, .. })
+            | ftd::Element::Video(ftd::Media { common, .. })
+            | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+            | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+            | ftd::Element::Radio(ftd::Select { common, .. })
+            | ftd::Element::Select(ftd::Select { common, .. })
+            | ftd::Element::Toc(ftd::Toc { common, .. })
+            | ftd::Element::Native(ftd::Native { common, .. })
             | ftd::Element::Integer(ftd::Text { common, .. })
             | ftd::Element::Boolean(ftd::Text { common, .. })
             | ftd::Element::Decimal(ftd::Text { common, .. })
@@ -1609,6 +1800,16 @@ impl Element {
             | ftd::Element::IFrame(ftd::IFrame { common, .. })
             | ftd::Element::Markup(ftd::Markups { common, .. })
             | ftd::Element::Input(ftd::Input { common, .. })
+            | ftd::Element::Table(ftd::Table { common, .. })
+            | ftd::Element::Svg(ftd::Svg { common, .. })
+            | ftd::Element::Audio(ftd::Media { common, .. })
+            | ftd::Element::Video(ftd::Media { common, .. })
+            | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+            | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+            | ftd::Element::Radio(ftd::Select { common, .. })
+            | ftd::Element::Select(ftd::Select { common, .. })
+            | ftd::Element::Toc(ftd::Toc { common, .. })
+            | ftd::Element::Native(ftd::Native { common, .. })
             | ftd::Element::Integer(ftd::Text { common, .. })
             | ftd::Element::Boolean(ftd::Text { common, .. })
             | ftd::Element::Decimal(ftd::Text { common, .. })
@@ -1630,6 +1831,16 @@ impl Element {
             | ftd::Element::IFrame(ftd::IFrame { common, .. })
             | ftd::Element::Markup(ftd::Markups { common, .. })
             | ftd::Element::Input(ftd::Input { common, .. })
+            | ftd::Element::Table(ftd::Table { common, .. })
+            | ftd::Element::Svg(ftd::Svg { common, .. })
+            | ftd::Element::Audio(ftd::Media { common, .. })
+            | ftd::Element::Video(ftd::Media { common, .. })
+            | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+            | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+            | ftd::Element::Radio(ftd::Select { common, .. })
+            | ftd::Element::Select(ftd::Select { common, .. })
+            | ftd::Element::Toc(ftd::Toc { common, .. })
+            | ftd::Element::Native(ftd::Native { common, .. })
             | ftd::Element::Integer(ftd::Text { common, .. })
             | ftd::Element::Boolean(ftd::Text { common, .. })
             | ftd::Element::Decimal(ftd::Text { common, .. })
@@ -1660,6 +1871,16 @@ impl Element {
             ftd::Element::Image(e) => Some(&mut e.common),
             ftd::Element::IFrame(e) => Some(&mut e.common),
             ftd::Element::Input(e) => Some(&mut e.common),
+            ftd::Element::Table(e) => Some(&mut e.common),
+            ftd::Element::Svg(e) => Some(&mut e.common),
+            ftd::Element::Audio(e) => Some(&mut e.common),
+            ftd::Element::Video(e) => Some(&mut e.common),
+            ftd::Element::Checkbox(e) => Some(&mut e.common),
+            ftd::Element::Toggle(e) => Some(&mut e.common),
+            ftd::Element::Radio(e) => Some(&mut e.common),
+            ftd::Element::Select(e) => Some(&mut e.common),
+            ftd::Element::Toc(e) => Some(&mut e.common),
+            ftd::Element::Native(e) => Some(&mut e.common),
             ftd::Element::Integer(e) => Some(&mut e.common),
             ftd::Element::Boolean(e) => Some(&mut e.common),
             ftd::Element::Decimal(e) => Some(&mut e.common),
@@ -1680,6 +1901,16 @@ impl Element {
             ftd::Element::Image(e) => Some(&e.common),
             ftd::Element::IFrame(e) => Some(&e.common),
             ftd::Element::Input(e) => Some(&e.common),
+            ftd::Element::Table(e) => Some(&e.common),
+            ftd::Element::Svg(e) => Some(&e.common),
+            ftd::Element::Audio(e) => Some(&e.common),
+            ftd::Element::Video(e) => Some(&e.common),
+            ftd::Element::Checkbox(e) => Some(&e.common),
+            ftd::Element::Toggle(e) => Some(&e.common),
+            ftd::Element::Radio(e) => Some(&e.common),
+            ftd::Element::Select(e) => Some(&e.common),
+            ftd::Element::Toc(e) => Some(&e.common),
+            ftd::Element::Native(e) => Some(&e.common),
             ftd::Element::Integer(e) => Some(&e.common),
             ftd::Element::Boolean(e) => Some(&e.common),
             ftd::Element::Decimal(e) => Some(&e.common),
@@ -1699,6 +1930,164 @@ impl Element {
         }
     }
 
+    pub fn get_mut_container(&mut self) -> Option<&mut ftd::Container> {
+        match self {
+            ftd::Element::Column(e) => Some(&mut e.container),
+            ftd::Element::Row(e) => Some(&mut e.container),
+            ftd::Element::Scene(e) => Some(&mut e.container),
+            ftd::Element::Grid(e) => Some(&mut e.container),
+            _ => None,
+        }
+    }
+
+    /// variables bound to the inputs in the form `container`, and in their
+    /// descendants, by the name they are submitted with, eg `email` for
+    /// `foo/bar#email`; two variables submitted with the same name are an
+    /// error
+    pub fn form_fields(
+        container: &ftd::Container,
+        doc_id: &str,
+    ) -> ftd::p1::Result<std::collections::BTreeMap<String, String>> {
+        let mut fields = Default::default();
+        form_fields_(container, &mut fields, doc_id)?;
+        return Ok(fields);
+
+        fn form_fields_(
+            container: &ftd::Container,
+            fields: &mut std::collections::BTreeMap<String, String>,
+            doc_id: &str,
+        ) -> ftd::p1::Result<()> {
+            let external = container.external_children.iter().flat_map(|(_, _, e)| e);
+            for child in container.children.iter().chain(external) {
+                match child {
+                    ftd::Element::Input(ftd::Input { common, .. })
+                    | ftd::Element::Checkbox(ftd::Checkbox { common, .. })
+                    | ftd::Element::Toggle(ftd::Checkbox { common, .. })
+                    | ftd::Element::Radio(ftd::Select { common, .. })
+                    | ftd::Element::Select(ftd::Select { common, .. }) => {
+                        let reference = match common.reference {
+                            Some(ref reference) => reference,
+                            None => continue,
+                        };
+                        let name = field_name(reference);
+                        match fields.get(name.as_str()) {
+                            Some(other) if other != reference => {
+                                return ftd::e2(
+                                    format!(
+                                        "{} and {} are both submitted as {}",
+                                        other, reference, name
+                                    ),
+                                    doc_id,
+                                    0,
+                                )
+                            }
+                            _ => fields.insert(name, reference.to_string()),
+                        };
+                    }
+                    t => {
+                        if let Some(container) = t.get_container() {
+                            form_fields_(container, fields, doc_id)?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        // `foo/bar#email@0` is the `email` of the component instance `0`
+        fn field_name(reference: &str) -> String {
+            let name = reference.rsplit_once('#').map_or(reference, |(_, n)| n);
+            name.split('@').next().unwrap_or(name).to_string()
+        }
+    }
+
+    /// checks the fields of every form in `elements`, see `form_fields()`
+    pub(crate) fn check_forms(elements: &[ftd::Element], doc_id: &str) -> ftd::p1::Result<()> {
+        for element in elements.iter() {
+            let container = match element.get_container() {
+                Some(container) => container,
+                None => continue,
+            };
+            if element.get_common().map_or(false, |c| c.form.is_some()) {
+                ftd::Element::form_fields(container, doc_id)?;
+            }
+            ftd::Element::check_forms(&container.children, doc_id)?;
+            if let Some((_, _, ref external_children)) = container.external_children {
+                ftd::Element::check_forms(external_children, doc_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// the `valid` variable of every input in `elements` that has one, with
+    /// whether the input is valid
+    pub(crate) fn input_validity(elements: &[ftd::Element], validity: &mut Vec<(String, bool)>) {
+        for element in elements.iter() {
+            if let ftd::Element::Input(i) = element {
+                if let Some(ref valid) = i.valid {
+                    validity.push((valid.to_string(), i.value_is_valid));
+                }
+            }
+            if let Some(container) = element.get_container() {
+                ftd::Element::input_validity(&container.children, validity);
+                if let Some((_, _, ref external_children)) = container.external_children {
+                    ftd::Element::input_validity(external_children, validity);
+                }
+            }
+        }
+    }
+
+    /// the panels of tabs and accordions get their index, only the open one is
+    /// shown, and it is the one the variable of the tabs is set to
+    pub(crate) fn set_disclosure_panels(elements: &mut [ftd::Element]) {
+        for element in elements.iter_mut() {
+            let disclosure = element
+                .get_common()
+                .and_then(|c| c.disclosure.clone())
+                .filter(|d| {
+                    matches!(
+                        d.kind,
+                        ftd::DisclosureKind::Tabs | ftd::DisclosureKind::Accordion
+                    )
+                });
+            let container = match element.get_mut_container() {
+                Some(container) => container,
+                None => continue,
+            };
+            ftd::Element::set_disclosure_panels(&mut container.children);
+            if let Some((_, _, ref mut external_children)) = container.external_children {
+                ftd::Element::set_disclosure_panels(external_children);
+            }
+            let disclosure = match disclosure {
+                Some(disclosure) => disclosure,
+                None => continue,
+            };
+            let mut index = 0;
+            for child in container.children.iter_mut() {
+                let common = match child.get_mut_common() {
+                    Some(common) => common,
+                    None => continue,
+                };
+                let panel = match common.disclosure {
+                    Some(ref mut panel) if panel.kind == ftd::DisclosureKind::Panel => panel,
+                    _ => continue,
+                };
+                panel.selected = Some(index);
+                common.is_not_visible = disclosure.selected != Some(index);
+                // ftd.js shows the panel of the variant picked
+                if let Some(ref variable) = disclosure.variable {
+                    if common.condition.is_none() && disclosure.variants.is_empty() {
+                        common.condition = Some(ftd::Condition {
+                            variable: variable.to_string(),
+                            value: index.to_string(),
+                        });
+                    }
+                }
+                index += 1;
+            }
+        }
+    }
+
     pub fn renest_on_region(elements: &mut Vec<ftd::Element>) {
         let mut region: Option<(usize, &Region)> = None;
         let mut insert: Vec<(usize, usize)> = Default::default();
@@ -2175,6 +2564,10 @@ pub struct Common {
     pub is_dummy: bool,
     pub events: Vec<ftd::Event>,
     pub reference: Option<String>,
+    /// full names of the components this element was created by, innermost
+    /// first, eg `["foo/bar#heading", "foo/bar#h2"]` for `-- h2:` when `h2`
+    /// is a `heading`
+    pub components: Vec<String>,
     pub region: Option<Region>,
     pub padding: Option<i64>,
     pub padding_vertical: Option<i64>,
@@ -2218,6 +2611,17 @@ pub struct Common {
     pub left: Option<i64>,
     pub right: Option<i64>,
     pub submit: Option<String>,
+    /// set on the column `ftd.form` is rendered as
+    #[serde(default)]
+    pub form: Option<Form>,
+    /// set on the column `ftd.dialog`, `ftd.popover` or `ftd.tooltip` is
+    /// rendered as
+    #[serde(default)]
+    pub overlay: Option<Overlay>,
+    /// set on the column `ftd.tabs`, `ftd.accordion`, `ftd.panel` or
+    /// `ftd.details` is rendered as
+    #[serde(default)]
+    pub disclosure: Option<Disclosure>,
     pub cursor: Option<String>,
     pub shadow_offset_x: Option<i64>,
     pub shadow_offset_y: Option<i64>,
@@ -2294,9 +2698,88 @@ impl Container {
 #[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
 pub struct Image {
     pub src: ImageSrc,
+    /// shown instead of `src` on the screens they are for, see `ImageSource`
+    #[serde(default)]
+    pub sources: Vec<ImageSource>,
     pub description: String,
     pub common: Common,
     pub crop: bool,
+    #[serde(default)]
+    pub fit: Option<ImageFit>,
+    #[serde(default)]
+    pub aspect_ratio: Option<String>,
+    /// `None` leaves it to the browser, which loads the image eagerly
+    #[serde(default)]
+    pub loading: Option<Loading>,
+    #[serde(default)]
+    pub fetch_priority: Option<FetchPriority>,
+    /// the `sizes` attribute, the width the image is shown at on each screen
+    #[serde(default)]
+    pub sizes: Option<String>,
+}
+
+/// `mobile-src` and `xl-src` of `ftd.image`, `media` is the media query of the
+/// breakpoint, eg `(max-width: 768px)` for `ftd#mobile-breakpoint: 768`
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct ImageSource {
+    pub media: String,
+    pub src: ImageSrc,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum ImageFit {
+    Cover,
+    Contain,
+    Fill,
+}
+
+impl ImageFit {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<Option<ftd::ImageFit>> {
+        Ok(match l.as_deref() {
+            Some("cover") => Some(ftd::ImageFit::Cover),
+            Some("contain") => Some(ftd::ImageFit::Contain),
+            Some("fill") => Some(ftd::ImageFit::Fill),
+            Some(t) => return ftd::e2(format!("{} is not a valid fit", t), doc_id, 0),
+            None => None,
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::ImageFit::Cover => "cover",
+            ftd::ImageFit::Contain => "contain",
+            ftd::ImageFit::Fill => "fill",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum FetchPriority {
+    High,
+    Low,
+    Auto,
+}
+
+impl FetchPriority {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<Option<ftd::FetchPriority>> {
+        Ok(match l.as_deref() {
+            Some("high") => Some(ftd::FetchPriority::High),
+            Some("low") => Some(ftd::FetchPriority::Low),
+            Some("auto") => Some(ftd::FetchPriority::Auto),
+            Some(t) => return ftd::e2(format!("{} is not a valid fetch-priority", t), doc_id, 0),
+            None => None,
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::FetchPriority::High => "high",
+            ftd::FetchPriority::Low => "low",
+            ftd::FetchPriority::Auto => "auto",
+        }
+    }
 }
 
 #[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
@@ -2613,9 +3096,48 @@ impl TextFormat {
 #[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
 pub struct IFrame {
     pub src: String,
+    /// width and height, of the player of an `embed` url
+    #[serde(default)]
+    pub aspect_ratio: Option<(i64, i64)>,
+    #[serde(default)]
+    pub sandbox: Option<String>,
+    #[serde(default)]
+    pub allow: Option<String>,
+    #[serde(default)]
+    pub loading: Loading,
     pub common: Common,
 }
 
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum Loading {
+    Lazy,
+    Eager,
+}
+
+impl Default for Loading {
+    fn default() -> Self {
+        ftd::Loading::Lazy
+    }
+}
+
+impl Loading {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<ftd::Loading> {
+        Ok(match l.as_deref() {
+            Some("lazy") | None => ftd::Loading::Lazy,
+            Some("eager") => ftd::Loading::Eager,
+            Some(t) => return ftd::e2(format!("{} is not a valid loading", t), doc_id, 0),
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::Loading::Lazy => "lazy",
+            ftd::Loading::Eager => "eager",
+        }
+    }
+}
+
 #[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
 pub struct Text {
     pub text: ftd::Rendered,
@@ -2712,4 +3234,526 @@ pub struct ColorValue {
 pub struct Input {
     pub common: Common,
     pub placeholder: Option<String>,
+    #[serde(default)]
+    pub type_: InputType,
+    /// initial value, the variable in `common.reference` is kept in sync with it
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub min: Option<String>,
+    #[serde(default)]
+    pub max: Option<String>,
+    #[serde(default)]
+    pub step: Option<String>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub max_length: Option<i64>,
+    /// boolean variable kept set to `is_valid`
+    #[serde(default)]
+    pub valid: Option<String>,
+    /// `is_valid()` of the initial value
+    #[serde(default)]
+    pub value_is_valid: bool,
+}
+
+impl Input {
+    /// whether the browser would find `value` valid, it is not `pattern` if
+    /// that is not a regex rust understands too
+    pub fn is_valid(&self) -> bool {
+        let value = self.value.as_deref().unwrap_or_default();
+        if value.is_empty() {
+            return !self.required;
+        }
+        if let Some(l) = self.max_length {
+            if value.chars().count() as i64 > l {
+                return false;
+            }
+        }
+        if let Some(ref p) = self.pattern {
+            if let Ok(r) = regex::Regex::new(format!("^(?:{})$", p).as_str()) {
+                if !r.is_match(value) {
+                    return false;
+                }
+            }
+        }
+        match self.type_ {
+            ftd::InputType::Email => value
+                .split_once('@')
+                .map_or(false, |(user, host)| !user.is_empty() && !host.is_empty()),
+            ftd::InputType::Number | ftd::InputType::Range => {
+                let bound = |b: &Option<String>| b.as_ref().and_then(|b| b.parse::<f64>().ok());
+                match value.parse::<f64>() {
+                    Ok(v) => {
+                        bound(&self.min).map_or(true, |min| v >= min)
+                            && bound(&self.max).map_or(true, |max| v <= max)
+                    }
+                    Err(_) => false,
+                }
+            }
+            // `yyyy-mm-dd` dates compare as strings
+            ftd::InputType::Date => {
+                self.min.as_deref().map_or(true, |min| value >= min)
+                    && self.max.as_deref().map_or(true, |max| value <= max)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum InputType {
+    Text,
+    Number,
+    Email,
+    Password,
+    Date,
+    Range,
+    TextArea,
+}
+
+impl Default for InputType {
+    fn default() -> Self {
+        ftd::InputType::Text
+    }
+}
+
+impl InputType {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<ftd::InputType> {
+        Ok(match l.as_deref() {
+            Some("text") => ftd::InputType::Text,
+            Some("number") => ftd::InputType::Number,
+            Some("email") => ftd::InputType::Email,
+            Some("password") => ftd::InputType::Password,
+            Some("date") => ftd::InputType::Date,
+            Some("range") => ftd::InputType::Range,
+            Some("textarea") => ftd::InputType::TextArea,
+            Some(t) => return ftd::e2(format!("{} is not a valid input type", t), doc_id, 0),
+            None => return Ok(ftd::InputType::Text),
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::InputType::Text => "text",
+            ftd::InputType::Number => "number",
+            ftd::InputType::Email => "email",
+            ftd::InputType::Password => "password",
+            ftd::InputType::Date => "date",
+            ftd::InputType::Range => "range",
+            ftd::InputType::TextArea => "textarea",
+        }
+    }
+
+    /// `min`, `max` and `step` only apply to these
+    pub fn is_ordered(&self) -> bool {
+        matches!(
+            self,
+            ftd::InputType::Number | ftd::InputType::Date | ftd::InputType::Range
+        )
+    }
+}
+
+/// `ftd.checkbox` and `ftd.toggle`, the boolean variable in `common.reference`
+/// is kept in sync with `checked`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Checkbox {
+    pub common: Common,
+    #[serde(default)]
+    pub checked: bool,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// `ftd.radio` and `ftd.select`, the variable in `common.reference` is kept in
+/// sync with the selected option, for an or-type variable the options are its
+/// variants and the variant is kept in sync
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Select {
+    pub common: Common,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// `ftd.svg`, and `ftd.icon` which is the `svg` of the icon `icon` of an
+/// `ftd::IconSet`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Svg {
+    pub svg: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub common: Common,
+}
+
+/// `ftd.table`, a row for each record in `data`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Table {
+    #[serde(default)]
+    pub columns: Vec<TableColumn>,
+    #[serde(default)]
+    pub rows: Vec<Vec<TableCell>>,
+    /// rows are sorted by a column when its header is clicked
+    #[serde(default)]
+    pub sortable: bool,
+    #[serde(default)]
+    pub sticky_header: bool,
+    /// background of every other row
+    #[serde(default)]
+    pub stripe_color: Option<Color>,
+    pub common: Common,
+}
+
+/// an `ftd.table-column`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct TableColumn {
+    pub caption: String,
+    /// the field of the records in `data` shown in this column
+    pub field: String,
+    #[serde(default)]
+    pub width: Option<Length>,
+    #[serde(default)]
+    pub align: Option<TextAlign>,
+    /// component a cell is shown with, it gets the value as `value`
+    #[serde(default)]
+    pub component: Option<String>,
+}
+
+impl TableColumn {
+    pub fn from(
+        l: &std::collections::BTreeMap<String, ftd::PropertyValue>,
+        doc: &ftd::p2::TDoc,
+        line_number: usize,
+    ) -> ftd::p1::Result<TableColumn> {
+        let properties = l
+            .iter()
+            .map(|(k, v)| v.resolve(line_number, doc).map(|v| (k.to_string(), v)))
+            .collect::<ftd::p1::Result<std::collections::BTreeMap<String, ftd::Value>>>()?;
+        let align = ftd::p2::utils::string_optional("align", &properties, doc.name, line_number)?;
+        let align = match align {
+            Some(align) => Some(ftd::TextAlign::from(Some(align), doc.name)?),
+            None => None,
+        };
+        Ok(TableColumn {
+            caption: ftd::p2::utils::string("caption", &properties, doc.name, line_number)?,
+            field: ftd::p2::utils::string("field", &properties, doc.name, line_number)?,
+            width: ftd::Length::from(
+                ftd::p2::utils::string_optional("width", &properties, doc.name, line_number)?,
+                doc.name,
+            )?,
+            align,
+            component: ftd::p2::utils::string_optional(
+                "component",
+                &properties,
+                doc.name,
+                line_number,
+            )?,
+        })
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct TableCell {
+    /// the value as text, rows are sorted by it
+    pub text: String,
+    /// the cell component of the column, called with the value
+    #[serde(default)]
+    pub element: Option<Element>,
+}
+
+/// `ftd.video` and `ftd.audio`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Media {
+    pub src: ImageSrc,
+    #[serde(default)]
+    pub poster: Option<ImageSrc>,
+    /// variable `poster` is bound to, followed to switch to its dark source
+    /// the way `common.reference` is for `src`
+    #[serde(default)]
+    pub poster_reference: Option<String>,
+    #[serde(default)]
+    pub autoplay: bool,
+    #[serde(rename = "loop", default)]
+    pub loop_: bool,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub controls: bool,
+    #[serde(default)]
+    pub tracks: Vec<Track>,
+    pub common: Common,
+}
+
+/// a captions, or subtitles etc, file of a `Media`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Track {
+    pub src: String,
+    pub kind: String,
+    #[serde(default)]
+    pub lang: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub default: bool,
+}
+
+impl Track {
+    pub fn from(
+        l: &std::collections::BTreeMap<String, ftd::PropertyValue>,
+        doc: &ftd::p2::TDoc,
+        line_number: usize,
+    ) -> ftd::p1::Result<Track> {
+        let properties = l
+            .iter()
+            .map(|(k, v)| v.resolve(line_number, doc).map(|v| (k.to_string(), v)))
+            .collect::<ftd::p1::Result<std::collections::BTreeMap<String, ftd::Value>>>()?;
+        let kind = ftd::p2::utils::string_with_default(
+            "kind",
+            "captions",
+            &properties,
+            doc.name,
+            line_number,
+        )?;
+        if !["captions", "subtitles", "descriptions", "chapters"].contains(&kind.as_str()) {
+            return ftd::e2(
+                format!("{} is not a valid track kind", kind),
+                doc.name,
+                line_number,
+            );
+        }
+        Ok(Track {
+            src: ftd::p2::utils::string("src", &properties, doc.name, line_number)?,
+            kind,
+            lang: ftd::p2::utils::string_optional("lang", &properties, doc.name, line_number)?,
+            label: ftd::p2::utils::string_optional("label", &properties, doc.name, line_number)?,
+            default: ftd::p2::utils::bool_with_default(
+                "default",
+                false,
+                &properties,
+                doc.name,
+                line_number,
+            )?,
+        })
+    }
+}
+
+/// what `ftd.form` does with the variables bound to the inputs in it when
+/// submitted, the browser submits them to `action`, or the `message_host`
+/// function gets them encoded with `encoding`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Form {
+    pub action: Option<String>,
+    pub method: FormMethod,
+    pub encoding: FormEncoding,
+    pub message_host: Option<String>,
+    /// full name of the record a submission is read as, see
+    /// `Document::submission()`
+    pub record: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum FormMethod {
+    Get,
+    Post,
+}
+
+impl Default for FormMethod {
+    fn default() -> Self {
+        ftd::FormMethod::Get
+    }
+}
+
+impl FormMethod {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<ftd::FormMethod> {
+        Ok(match l.as_deref() {
+            Some("get") | None => ftd::FormMethod::Get,
+            Some("post") => ftd::FormMethod::Post,
+            Some(t) => return ftd::e2(format!("{} is not a valid form method", t), doc_id, 0),
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::FormMethod::Get => "get",
+            ftd::FormMethod::Post => "post",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum FormEncoding {
+    UrlEncoded,
+    Json,
+}
+
+impl Default for FormEncoding {
+    fn default() -> Self {
+        ftd::FormEncoding::UrlEncoded
+    }
+}
+
+impl FormEncoding {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<ftd::FormEncoding> {
+        Ok(match l.as_deref() {
+            Some("urlencoded") | None => ftd::FormEncoding::UrlEncoded,
+            Some("json") => ftd::FormEncoding::Json,
+            Some(t) => return ftd::e2(format!("{} is not a valid form encoding", t), doc_id, 0),
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::FormEncoding::UrlEncoded => "urlencoded",
+            ftd::FormEncoding::Json => "json",
+        }
+    }
+}
+
+/// a column shown over the page, its events are added by `Document::body_events()`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Overlay {
+    pub kind: OverlayKind,
+    /// the boolean variable the dialog or popover is shown with, a tooltip is
+    /// shown while its anchor is hovered or focused
+    pub open: Option<String>,
+    /// `id` of the element a popover or tooltip is placed next to
+    pub anchor: Option<String>,
+    pub placement: Placement,
+    /// closed on Esc, and on a click outside of it
+    pub dismissible: bool,
+    pub label: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum OverlayKind {
+    Dialog,
+    Popover,
+    Tooltip,
+}
+
+impl Default for OverlayKind {
+    fn default() -> Self {
+        ftd::OverlayKind::Dialog
+    }
+}
+
+impl OverlayKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::OverlayKind::Dialog => "dialog",
+            ftd::OverlayKind::Popover => "popover",
+            ftd::OverlayKind::Tooltip => "tooltip",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum Placement {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        ftd::Placement::Bottom
+    }
+}
+
+impl Placement {
+    pub fn from(l: Option<String>, doc_id: &str) -> ftd::p1::Result<ftd::Placement> {
+        Ok(match l.as_deref() {
+            Some("bottom") | None => ftd::Placement::Bottom,
+            Some("top") => ftd::Placement::Top,
+            Some("left") => ftd::Placement::Left,
+            Some("right") => ftd::Placement::Right,
+            Some(t) => return ftd::e2(format!("{} is not a valid placement", t), doc_id, 0),
+        })
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::Placement::Top => "top",
+            ftd::Placement::Bottom => "bottom",
+            ftd::Placement::Left => "left",
+            ftd::Placement::Right => "right",
+        }
+    }
+}
+
+/// a column of panels shown one at a time, or a column shown on a click on its
+/// summary
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Disclosure {
+    pub kind: DisclosureKind,
+    /// the integer variable with the index of the open panel, the variant of
+    /// the or-type variable the panels are keyed by, or the boolean variable
+    /// `ftd.details` is open with
+    pub variable: Option<String>,
+    /// index of the open panel, none of the panels of an accordion may be open,
+    /// a panel has its own index here
+    pub selected: Option<i64>,
+    /// the variants of the or-type variable, in order, the panel at an index
+    /// is for the variant at that index
+    #[serde(default)]
+    pub variants: Vec<String>,
+    /// whether `ftd.details` is open
+    pub open: bool,
+    /// title of a panel, summary of details
+    pub title: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum DisclosureKind {
+    Tabs,
+    Accordion,
+    Panel,
+    Details,
+}
+
+impl Default for DisclosureKind {
+    fn default() -> Self {
+        ftd::DisclosureKind::Tabs
+    }
+}
+
+impl DisclosureKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ftd::DisclosureKind::Tabs => "tabs",
+            ftd::DisclosureKind::Accordion => "accordion",
+            ftd::DisclosureKind::Panel => "panel",
+            ftd::DisclosureKind::Details => "details",
+        }
+    }
+}
+
+/// element produced by a `ftd::Plugin`
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Native {
+    pub name: String,
+    pub common: Common,
+    pub node: ftd::Node,
+}
+
+/// `ftd.toc`, the outline is filled in once the whole document is rendered
+#[derive(serde::Deserialize, Debug, PartialEq, Default, Clone, serde::Serialize)]
+pub struct Toc {
+    pub common: Common,
+    pub max_level: Option<i64>,
+    pub outline: Vec<ftd::OutlineItem>,
 }